@@ -0,0 +1,10 @@
+use std::{fs, path::Path};
+
+// Rewrites idl/ramp_solana.json from ramp_solana::idl, run it after changing an instruction,
+// its accounts or a state layout: cargo run --example generate_idl
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("idl").join("ramp_solana.json");
+    let idl = serde_json::to_string_pretty(&ramp_solana::idl::generate()).unwrap();
+    fs::write(&path, idl + "\n").unwrap();
+    println!("IDL written to {}", path.display());
+}
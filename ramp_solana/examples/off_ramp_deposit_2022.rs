@@ -57,11 +57,18 @@ async fn main() {
         &asset_mint_account.pubkey(),
         &TOKEN_PROGRAM_ID,
     );
+    let reference = Pubkey::new_unique().to_bytes();
+    let receipt_account = ramp_solana::state::DepositReceipt::find_address(
+        &ramp_program_id,
+        &ramp_account.0,
+        &reference,
+    );
     let off_ramp_deposit_data = ramp_solana::instructions::OffRampDepositInstruction {
         amount: 100000,
-        region: ramp_solana::models::Region::EGY,
-        medium: ramp_solana::models::Medium::Primary,
+        region: ramp_solana::models::Region::new("EGY"),
+        medium: ramp_solana::models::Medium::new("MPESA"),
         data: b"".to_vec(),
+        reference,
     };
     let accounts = vec![
         AccountMeta::new(ramp_account.0, false),
@@ -70,6 +77,8 @@ async fn main() {
         AccountMeta::new(owner_token_account, false),
         AccountMeta::new(ramp_token_account, false),
         AccountMeta::new_readonly(token_program(), false),
+        AccountMeta::new(receipt_account.0, false),
+        AccountMeta::new_readonly(solana_system_interface::program::ID, false),
     ];
     let off_ramp_deposit_instruction = Instruction::new_with_borsh(
         ramp_program_id,
@@ -47,16 +47,24 @@ async fn main() {
 
     client.request_airdrop(&signer_keypair.pubkey(), 1000000000).await.unwrap();
 
+    let reference = Pubkey::new_unique().to_bytes();
+    let receipt_account = ramp_solana::state::DepositReceipt::find_address(
+        &ramp_program_id,
+        &ramp_account.0,
+        &reference,
+    );
     let off_ramp_native = ramp_solana::instructions::OffRampDepositNativeInstruction {
         amount: 100000,
-        region: ramp_solana::models::Region::EGY,
-        medium: ramp_solana::models::Medium::Primary,
+        region: ramp_solana::models::Region::new("EGY"),
+        medium: ramp_solana::models::Medium::new("MPESA"),
         data: b"".to_vec(),
+        reference,
     };
     let accounts = vec![
         AccountMeta::new(ramp_account.0, false),
         AccountMeta::new(signer_keypair.pubkey(), true),
         AccountMeta::new_readonly(system_program_id, false),
+        AccountMeta::new(receipt_account.0, false),
     ];
     let off_ramp_native_instruction = Instruction::new_with_borsh(
         ramp_program_id,
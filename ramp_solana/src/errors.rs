@@ -44,6 +44,126 @@ pub enum RampError {
 
     #[error("Transfer failed")]
     TransferFailed,
+
+    #[error("Region already exists")]
+    RegionAlreadyExists,
+
+    #[error("Region not found")]
+    RegionNotFound,
+
+    #[error("Medium already exists")]
+    MediumAlreadyExists,
+
+    #[error("Medium not found")]
+    MediumNotFound,
+
+    #[error("Region or medium is not allowed")]
+    RegionNotAllowed,
+
+    #[error("Deposit reference already used")]
+    DuplicateReference,
+
+    #[error("Invalid receipt account")]
+    InvalidReceiptAccount,
+
+    #[error("Deposit already refunded")]
+    DepositAlreadyRefunded,
+
+    #[error("Invalid recipient")]
+    InvalidRecipient,
+
+    #[error("Escrow expiry must be in the future")]
+    InvalidExpiry,
+
+    #[error("Escrow has expired")]
+    EscrowExpired,
+
+    #[error("Escrow has not expired yet")]
+    EscrowNotExpired,
+
+    #[error("Deposit is not in a valid escrow state")]
+    InvalidEscrowState,
+
+    #[error("Invalid escrow token account")]
+    InvalidEscrowAccount,
+
+    #[error("Voucher signer not set")]
+    VoucherSignerNotSet,
+
+    #[error("Invalid voucher")]
+    InvalidVoucher,
+
+    #[error("Invalid voucher signature")]
+    InvalidVoucherSignature,
+
+    #[error("Voucher expired")]
+    VoucherExpired,
+
+    #[error("Voucher nonce already used")]
+    NonceAlreadyUsed,
+
+    #[error("Batch is empty or does not match the recipient accounts")]
+    InvalidBatch,
+
+    #[error("Batch exceeds the maximum payout count")]
+    BatchTooLarge,
+
+    #[error("Deposits are paused for this asset")]
+    AssetDepositsPaused,
+
+    #[error("Withdrawals are paused for this asset")]
+    AssetWithdrawalsPaused,
+
+    #[error("Address is on the deny list")]
+    AddressDenied,
+
+    #[error("Address is not on the allowlist")]
+    AddressNotAllowed,
+
+    #[error("Invalid access list account")]
+    InvalidAccessEntry,
+
+    #[error("Invalid SOL vault account")]
+    InvalidSolVault,
+
+    #[error("Invalid token metadata account")]
+    InvalidMetadataAccount,
+
+    #[error("Invalid fee schedule")]
+    InvalidFeeSchedule,
+
+    #[error("Invalid revenue split")]
+    InvalidRevenueSplit,
+
+    #[error("No revenue to withdraw")]
+    NoRevenue,
+
+    #[error("Invalid amount")]
+    InvalidAmount,
+
+    #[error("Asset is winding down")]
+    AssetWindingDown,
+
+    #[error("Unknown instruction discriminator")]
+    UnknownInstruction,
+
+    #[error("Ramp must be deactivated before it is closed")]
+    RampStillActive,
+
+    #[error("Invalid credit account")]
+    InvalidCreditAccount,
+
+    #[error("No credit to claim")]
+    NoCredit,
+
+    #[error("Escrowed deposits are still open")]
+    EscrowsOutstanding,
+
+    #[error("Credits are still unclaimed")]
+    CreditsOutstanding,
+
+    #[error("Ramp account already has the current layout")]
+    RampAlreadyMigrated,
 }
 
 impl From<RampError> for ProgramError {
@@ -0,0 +1,810 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    errors::RampError,
+    models::{Medium, RampDeposit, Region},
+    pod::{PodBool, PodU128, PodU16, PodU64},
+    processors::Instruction,
+    state::{
+        AccessEntry, CreditEntry, DepositReceipt, RampState, ACCESS_SEED, CREDIT_SEED, NONCE_SEED, RECEIPT_SEED,
+        SOL_VAULT_SEED,
+    },
+};
+
+// Shank flavoured IDL: the legacy Anchor layout with a u8 `discriminant` per instruction, which
+// Codama and the Anchor/Shank tooling can import. Argument, account and event layouts come from the
+// idl_struct! annotations next to each struct, the account lists below follow the
+// next_account_info order of each handler. `cargo run --example generate_idl` rewrites
+// idl/ramp_solana.json, and test_idl_matches_code fails when that file or this module drift
+
+pub type IdlTypes = BTreeMap<&'static str, Value>;
+
+pub trait IdlType {
+    fn idl_type() -> Value;
+
+    // adds the definitions of the defined types this type refers to
+    fn register(_types: &mut IdlTypes) {}
+}
+
+pub trait IdlStruct {
+    const NAME: &'static str;
+
+    fn idl_fields() -> Vec<Value>;
+
+    fn register_fields(types: &mut IdlTypes);
+}
+
+// Describes a struct in the IDL. Destructuring the struct keeps the list honest: adding, removing,
+// renaming or retyping a field without updating the annotation stops the build
+macro_rules! idl_struct {
+    ($name:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+        impl $crate::idl::IdlStruct for $name {
+            const NAME: &'static str = stringify!($name);
+
+            fn idl_fields() -> Vec<serde_json::Value> {
+                vec![$($crate::idl::field(stringify!($field), <$ty as $crate::idl::IdlType>::idl_type())),*]
+            }
+
+            fn register_fields(_types: &mut $crate::idl::IdlTypes) {
+                $(<$ty as $crate::idl::IdlType>::register(_types);)*
+            }
+        }
+
+        impl $crate::idl::IdlType for $name {
+            fn idl_type() -> serde_json::Value {
+                $crate::idl::defined(stringify!($name))
+            }
+
+            fn register(types: &mut $crate::idl::IdlTypes) {
+                if !types.contains_key(stringify!($name)) {
+                    let fields = <Self as $crate::idl::IdlStruct>::idl_fields();
+                    types.insert(stringify!($name), $crate::idl::struct_type(fields));
+                    <Self as $crate::idl::IdlStruct>::register_fields(types);
+                }
+            }
+        }
+
+        const _: fn($name) = |value| {
+            let $name { $($field),* } = value;
+            $(let _: $ty = $field;)*
+        };
+    };
+}
+
+// Describes a fieldless enum, the match stops the build when a variant is missing
+macro_rules! idl_enum {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        impl $crate::idl::IdlType for $name {
+            fn idl_type() -> serde_json::Value {
+                $crate::idl::defined(stringify!($name))
+            }
+
+            fn register(types: &mut $crate::idl::IdlTypes) {
+                types
+                    .entry(stringify!($name))
+                    .or_insert_with(|| $crate::idl::enum_type(&[$(stringify!($variant)),*]));
+            }
+        }
+
+        const _: fn($name) = |value| match value {
+            $($name::$variant => ()),*
+        };
+    };
+}
+
+pub(crate) use idl_enum;
+pub(crate) use idl_struct;
+
+macro_rules! idl_primitive {
+    ($($ty:ty => $name:literal),* $(,)?) => {
+        $(impl IdlType for $ty {
+            fn idl_type() -> Value {
+                json!($name)
+            }
+        })*
+    };
+}
+
+idl_primitive!(
+    bool => "bool",
+    u8 => "u8",
+    u16 => "u16",
+    u32 => "u32",
+    u64 => "u64",
+    u128 => "u128",
+    i64 => "i64",
+    String => "string",
+    Pubkey => "publicKey",
+    // the pod wrappers encode exactly like the plain integers and bools
+    PodBool => "bool",
+    PodU16 => "u16",
+    PodU64 => "u64",
+    PodU128 => "u128",
+);
+
+impl<T: IdlType> IdlType for Vec<T> {
+    fn idl_type() -> Value {
+        json!({ "vec": T::idl_type() })
+    }
+
+    fn register(types: &mut IdlTypes) {
+        T::register(types)
+    }
+}
+
+impl<T: IdlType> IdlType for Option<T> {
+    fn idl_type() -> Value {
+        json!({ "option": T::idl_type() })
+    }
+
+    fn register(types: &mut IdlTypes) {
+        T::register(types)
+    }
+}
+
+impl<T: IdlType, const N: usize> IdlType for [T; N] {
+    fn idl_type() -> Value {
+        json!({ "array": [T::idl_type(), N] })
+    }
+
+    fn register(types: &mut IdlTypes) {
+        T::register(types)
+    }
+}
+
+// Region and Medium are zero padded codes, described as a struct with a single `code` field
+impl IdlType for Region {
+    fn idl_type() -> Value {
+        defined("Region")
+    }
+
+    fn register(types: &mut IdlTypes) {
+        types
+            .entry("Region")
+            .or_insert_with(|| struct_type(vec![field("code", <[u8; 3]>::idl_type())]));
+    }
+}
+
+impl IdlType for Medium {
+    fn idl_type() -> Value {
+        defined("Medium")
+    }
+
+    fn register(types: &mut IdlTypes) {
+        types
+            .entry("Medium")
+            .or_insert_with(|| struct_type(vec![field("code", <[u8; 8]>::idl_type())]));
+    }
+}
+
+pub fn defined(name: &str) -> Value {
+    json!({ "defined": name })
+}
+
+pub fn field(name: &str, ty: Value) -> Value {
+    json!({ "name": camel_case(name), "type": ty })
+}
+
+pub fn struct_type(fields: Vec<Value>) -> Value {
+    json!({ "kind": "struct", "fields": fields })
+}
+
+pub fn enum_type(variants: &[&str]) -> Value {
+    let variants: Vec<Value> = variants.iter().map(|variant| json!({ "name": variant })).collect();
+    json!({ "kind": "enum", "variants": variants })
+}
+
+fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            camel.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+struct IdlAccount {
+    name: &'static str,
+    writable: bool,
+    signer: bool,
+    optional: bool,
+    docs: Option<&'static str>,
+    seeds: Vec<Value>,
+}
+
+impl IdlAccount {
+    fn writable(mut self) -> Self {
+        self.writable = true;
+        self
+    }
+
+    fn signer(mut self) -> Self {
+        self.signer = true;
+        self
+    }
+
+    fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    fn docs(mut self, docs: &'static str) -> Self {
+        self.docs = Some(docs);
+        self
+    }
+
+    fn pda(mut self, seeds: Vec<Value>) -> Self {
+        self.seeds = seeds;
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut account = json!({
+            "name": camel_case(self.name),
+            "isMut": self.writable,
+            "isSigner": self.signer,
+        });
+        if self.optional {
+            account["isOptional"] = json!(true);
+        }
+        if let Some(docs) = self.docs {
+            account["docs"] = json!([docs]);
+        }
+        if !self.seeds.is_empty() {
+            account["pda"] = json!({ "seeds": self.seeds });
+        }
+        account
+    }
+}
+
+fn account(name: &'static str) -> IdlAccount {
+    IdlAccount {
+        name,
+        writable: false,
+        signer: false,
+        optional: false,
+        docs: None,
+        seeds: vec![],
+    }
+}
+
+fn const_seed(seed: &[u8]) -> Value {
+    json!({ "kind": "const", "type": "string", "value": String::from_utf8_lossy(seed) })
+}
+
+fn account_seed(path: &str) -> Value {
+    json!({ "kind": "account", "type": "publicKey", "path": camel_case(path) })
+}
+
+fn arg_seed(path: &str, ty: Value) -> Value {
+    json!({ "kind": "arg", "type": ty, "path": camel_case(path) })
+}
+
+fn ramp() -> IdlAccount {
+    account("ramp")
+}
+
+fn owner() -> IdlAccount {
+    account("owner").signer()
+}
+
+fn system_program() -> IdlAccount {
+    account("system_program")
+}
+
+fn token_program() -> IdlAccount {
+    account("token_program")
+}
+
+fn associated_token_program() -> IdlAccount {
+    account("associated_token_program")
+}
+
+fn instructions_sysvar() -> IdlAccount {
+    account("instructions_sysvar")
+}
+
+fn sol_vault() -> IdlAccount {
+    account("sol_vault")
+        .writable()
+        .pda(vec![const_seed(SOL_VAULT_SEED), account_seed("ramp")])
+}
+
+fn receipt() -> IdlAccount {
+    account("receipt")
+        .writable()
+        .pda(vec![const_seed(RECEIPT_SEED), account_seed("ramp"), arg_seed("reference", <[u8; 32]>::idl_type())])
+}
+
+fn voucher_nonce() -> IdlAccount {
+    account("nonce")
+        .writable()
+        .pda(vec![const_seed(NONCE_SEED), account_seed("ramp"), arg_seed("voucher.nonce", u64::idl_type())])
+}
+
+fn credit() -> IdlAccount {
+    account("credit")
+        .writable()
+        .pda(vec![const_seed(CREDIT_SEED), account_seed("ramp"), account_seed("recipient"), account_seed("mint")])
+}
+
+fn access_entry(wallet: &str) -> IdlAccount {
+    account("access_entry").pda(vec![const_seed(ACCESS_SEED), account_seed("ramp"), account_seed(wallet)])
+}
+
+#[derive(Default)]
+struct Builder {
+    instructions: Vec<Value>,
+    types: IdlTypes,
+}
+
+impl Builder {
+    // The variant constructor ties the argument struct to the instruction at compile time, the
+    // arguments are listed as the fields of that struct since they encode the same
+    fn instruction<T: IdlStruct>(
+        &mut self,
+        name: &str,
+        discriminant: u8,
+        _variant: fn(T) -> Instruction,
+        accounts: Vec<IdlAccount>,
+        remaining_accounts: Option<&str>,
+    ) {
+        T::register_fields(&mut self.types);
+        let accounts: Vec<Value> = accounts.iter().map(IdlAccount::to_json).collect();
+        let mut instruction = json!({
+            "name": lower_first(name),
+            "accounts": accounts,
+            "args": T::idl_fields(),
+            "discriminant": { "type": "u8", "value": discriminant },
+        });
+        if let Some(remaining_accounts) = remaining_accounts {
+            instruction["docs"] = json!([format!("Remaining accounts: {}", remaining_accounts)]);
+        }
+        self.instructions.push(instruction);
+    }
+
+    fn account<T: IdlStruct>(&mut self) -> Value {
+        T::register_fields(&mut self.types);
+        json!({ "name": T::NAME, "type": struct_type(T::idl_fields()) })
+    }
+
+    fn event<T: IdlStruct>(&mut self) -> Value {
+        T::register_fields(&mut self.types);
+        let fields: Vec<Value> = T::idl_fields()
+            .into_iter()
+            .map(|mut field| {
+                field["index"] = json!(false);
+                field
+            })
+            .collect();
+        json!({ "name": T::NAME, "fields": fields })
+    }
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+macro_rules! idl_errors {
+    ($($error:ident),* $(,)?) => {
+        fn errors() -> Vec<Value> {
+            vec![$(json!({
+                "code": RampError::$error as u32,
+                "name": stringify!($error),
+                "msg": RampError::$error.to_string(),
+            })),*]
+        }
+
+        const _: fn(RampError) = |error| match error {
+            $(RampError::$error => ()),*
+        };
+    };
+}
+
+idl_errors!(
+    UninitializedAccount,
+    InsufficientFunds,
+    InvalidAccountOwner,
+    InvalidAccountState,
+    AccountAlreadyInitialized,
+    RentError,
+    InvalidSigner,
+    Unauthorized,
+    AssetAlreadyExists,
+    AssetNotFound,
+    InvalidFeePercentage,
+    ProgramNotActive,
+    TransferFailed,
+    RegionAlreadyExists,
+    RegionNotFound,
+    MediumAlreadyExists,
+    MediumNotFound,
+    RegionNotAllowed,
+    DuplicateReference,
+    InvalidReceiptAccount,
+    DepositAlreadyRefunded,
+    InvalidRecipient,
+    InvalidExpiry,
+    EscrowExpired,
+    EscrowNotExpired,
+    InvalidEscrowState,
+    InvalidEscrowAccount,
+    VoucherSignerNotSet,
+    InvalidVoucher,
+    InvalidVoucherSignature,
+    VoucherExpired,
+    NonceAlreadyUsed,
+    InvalidBatch,
+    BatchTooLarge,
+    AssetDepositsPaused,
+    AssetWithdrawalsPaused,
+    AddressDenied,
+    AddressNotAllowed,
+    InvalidAccessEntry,
+    InvalidSolVault,
+    InvalidMetadataAccount,
+    InvalidFeeSchedule,
+    InvalidRevenueSplit,
+    NoRevenue,
+    InvalidAmount,
+    AssetWindingDown,
+    UnknownInstruction,
+    RampStillActive,
+    InvalidCreditAccount,
+    NoCredit,
+    EscrowsOutstanding,
+    CreditsOutstanding,
+    RampAlreadyMigrated,
+);
+
+pub fn generate() -> Value {
+    let mut idl = Builder::default();
+
+    idl.instruction("SetActive", 0, Instruction::SetActive, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("RemoveAssets", 1, Instruction::RemoveAssets, vec![
+        ramp().writable(),
+        account("ramp_token_account").writable(),
+        account("mint"),
+        owner().writable(),
+        account("owner_token_account").writable(),
+        token_program(),
+    ], Some("the token accounts of the revenue split beneficiaries in split order, or the vault's token account without a split, only when there is revenue"));
+    idl.instruction("SetOwner", 2, Instruction::SetOwner, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("AddAssets", 3, Instruction::AddAssets, vec![
+        ramp().writable(),
+        account("mint"),
+        owner().writable(),
+        token_program(),
+        system_program(),
+        associated_token_program(),
+        account("owner_token_account").writable(),
+        account("ramp_token_account").writable(),
+    ], None);
+    idl.instruction("AddAssets2022", 4, Instruction::AddAssets2022, vec![
+        ramp().writable(),
+        account("mint"),
+        owner().writable(),
+        token_program(),
+        system_program(),
+        associated_token_program(),
+        account("owner_token_account").writable(),
+        account("ramp_token_account").writable(),
+    ], None);
+    idl.instruction("InitializeProgram", 5, Instruction::InitializeProgram, vec![
+        ramp().writable().pda(vec![const_seed(b"ramp"), account_seed("payer")]),
+        account("payer").writable().signer(),
+        system_program(),
+    ], None);
+    idl.instruction("OffRampDepositToken22", 6, Instruction::OffRampDepositToken22, vec![
+        ramp().writable(),
+        account("mint"),
+        account("depositor").writable().signer(),
+        account("depositor_token_account").writable(),
+        account("ramp_token_account").writable(),
+        token_program(),
+        receipt(),
+        system_program(),
+        access_entry("depositor"),
+    ], None);
+    idl.instruction("OffRampDeposit", 7, Instruction::OffRampDeposit, vec![
+        ramp().writable(),
+        account("mint"),
+        account("depositor").writable().signer(),
+        account("depositor_token_account").writable(),
+        account("ramp_token_account").writable(),
+        token_program(),
+        receipt(),
+        system_program(),
+        access_entry("depositor"),
+        account("metadata").optional().docs("Metaplex metadata of the mint, for the asset name and symbol"),
+    ], None);
+    idl.instruction("OnRampWithdraw", 8, Instruction::OnRampWithdraw, vec![
+        ramp().writable(),
+        account("mint"),
+        owner(),
+        account("recipient_token_account").writable(),
+        account("ramp_token_account").writable(),
+        token_program(),
+        access_entry("recipient").docs("access entry of the wallet owning the recipient token account"),
+    ], Some("[recipient, credit, credit_token_account, system_program, associated_token_program] when the recipient token account does not exist, the payout is then credited for the recipient to claim"));
+    idl.instruction("OffRampDepositNative", 9, Instruction::OffRampDepositNative, vec![
+        ramp().writable(),
+        account("depositor").writable().signer(),
+        system_program(),
+        receipt(),
+        access_entry("depositor"),
+        sol_vault(),
+    ], None);
+    idl.instruction("OnRampWithdrawNative", 10, Instruction::OnRampWithdrawNative, vec![
+        ramp(),
+        account("recipient").writable(),
+        owner(),
+        access_entry("recipient"),
+        sol_vault(),
+    ], None);
+    idl.instruction("SetNativeFeePercentage", 11, Instruction::SetNativeFeePercentage, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("SetAssetFee", 12, Instruction::SetAssetFee, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("AddRegion", 13, Instruction::AddRegion, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("AddRegionMedium", 14, Instruction::AddRegionMedium, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("SetRegionEnabled", 15, Instruction::SetRegionEnabled, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("SetRegionFee", 16, Instruction::SetRegionFee, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("RefundDeposit", 17, Instruction::RefundDeposit, vec![
+        ramp().writable(),
+        account("mint"),
+        owner(),
+        receipt(),
+        account("sender_token_account").writable(),
+        account("ramp_token_account").writable(),
+        token_program(),
+    ], None);
+    idl.instruction("RefundDepositNative", 18, Instruction::RefundDepositNative, vec![
+        ramp().writable(),
+        owner(),
+        receipt(),
+        account("sender").writable(),
+        sol_vault(),
+    ], None);
+    idl.instruction("EscrowDeposit", 19, Instruction::EscrowDeposit, vec![
+        ramp().writable(),
+        account("mint"),
+        account("depositor").writable().signer(),
+        account("depositor_token_account").writable(),
+        account("escrow_token_account").writable().docs("associated token account of the receipt PDA"),
+        token_program(),
+        receipt(),
+        system_program(),
+        associated_token_program(),
+        access_entry("depositor"),
+        account("metadata").optional().docs("Metaplex metadata of the mint, for the asset name and symbol"),
+    ], None);
+    idl.instruction("EscrowDepositNative", 20, Instruction::EscrowDepositNative, vec![
+        ramp().writable(),
+        account("depositor").writable().signer(),
+        system_program(),
+        receipt(),
+        access_entry("depositor"),
+    ], None);
+    idl.instruction("ReleaseEscrow", 21, Instruction::ReleaseEscrow, vec![
+        ramp().writable(),
+        account("mint"),
+        owner(),
+        receipt(),
+        account("escrow_token_account").writable(),
+        account("ramp_token_account").writable(),
+        account("sender").writable(),
+        token_program(),
+    ], None);
+    idl.instruction("ReleaseEscrowNative", 22, Instruction::ReleaseEscrowNative, vec![
+        ramp().writable(),
+        owner(),
+        receipt(),
+        sol_vault(),
+        system_program(),
+    ], None);
+    idl.instruction("ReclaimEscrow", 23, Instruction::ReclaimEscrow, vec![
+        ramp().writable(),
+        account("mint"),
+        account("sender").writable().signer(),
+        receipt(),
+        account("escrow_token_account").writable(),
+        account("sender_token_account").writable(),
+        token_program(),
+    ], None);
+    idl.instruction("ReclaimEscrowNative", 24, Instruction::ReclaimEscrowNative, vec![
+        ramp().writable(),
+        account("sender").writable().signer(),
+        receipt(),
+    ], None);
+    idl.instruction("SetVoucherSigner", 25, Instruction::SetVoucherSigner, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("VoucherWithdraw", 26, Instruction::VoucherWithdraw, vec![
+        ramp(),
+        account("mint"),
+        account("payer").writable().signer(),
+        account("recipient_token_account").writable(),
+        account("ramp_token_account").writable(),
+        token_program(),
+        voucher_nonce(),
+        system_program(),
+        instructions_sysvar(),
+        access_entry("recipient").docs("access entry of the wallet owning the recipient token account"),
+    ], None);
+    idl.instruction("VoucherWithdrawNative", 27, Instruction::VoucherWithdrawNative, vec![
+        ramp(),
+        account("payer").writable().signer(),
+        account("recipient").writable(),
+        voucher_nonce(),
+        system_program(),
+        instructions_sysvar(),
+        access_entry("recipient"),
+        sol_vault(),
+    ], None);
+    idl.instruction("BatchOnRampWithdraw", 28, Instruction::BatchOnRampWithdraw, vec![
+        ramp(),
+        account("mint"),
+        owner(),
+        account("ramp_token_account").writable(),
+        token_program(),
+    ], Some("the recipient token accounts in payout order, then the access entry of each recipient's wallet in the same order"));
+    idl.instruction("SetAssetPaused", 29, Instruction::SetAssetPaused, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("UpdateAccessList", 30, Instruction::UpdateAccessList, vec![
+        ramp(),
+        owner().writable(),
+        system_program(),
+    ], Some("one writable access entry PDA per address, in the order of `addresses`"));
+    idl.instruction("SetAllowlistEnabled", 31, Instruction::SetAllowlistEnabled, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("SetFeeSchedule", 32, Instruction::SetFeeSchedule, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("QuoteFee", 33, Instruction::QuoteFee, vec![
+        ramp(),
+    ], None);
+    idl.instruction("SetRevenueSplit", 34, Instruction::SetRevenueSplit, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("WithdrawRevenue", 35, Instruction::WithdrawRevenue, vec![
+        ramp().writable(),
+        account("mint"),
+        owner(),
+        account("ramp_token_account").writable(),
+        token_program(),
+    ], Some("the token accounts of the revenue split beneficiaries in split order, or the vault's token account without a split"));
+    idl.instruction("WithdrawRevenueNative", 36, Instruction::WithdrawRevenueNative, vec![
+        ramp().writable(),
+        owner(),
+        sol_vault(),
+    ], Some("the wallets of the revenue split beneficiaries in split order, or the vault address without a split"));
+    idl.instruction("GetAvailableLiquidity", 37, Instruction::GetAvailableLiquidity, vec![
+        ramp(),
+    ], Some("[mint, ramp_token_account, token_program] for a mint, [sol_vault] when `asset` is the default pubkey"));
+    idl.instruction("SetLowWaterMark", 38, Instruction::SetLowWaterMark, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("FundAsset", 39, Instruction::FundAsset, vec![
+        ramp(),
+        account("mint"),
+        account("funder").signer(),
+        account("funder_token_account").writable(),
+        account("ramp_token_account").writable(),
+        token_program(),
+    ], None);
+    idl.instruction("FundAssetNative", 40, Instruction::FundAssetNative, vec![
+        ramp(),
+        account("funder").writable().signer(),
+        sol_vault(),
+        system_program(),
+    ], None);
+    idl.instruction("DefundAsset", 41, Instruction::DefundAsset, vec![
+        ramp(),
+        account("mint"),
+        owner(),
+        account("recipient_token_account").writable(),
+        account("ramp_token_account").writable(),
+        token_program(),
+    ], None);
+    idl.instruction("DefundAssetNative", 42, Instruction::DefundAssetNative, vec![
+        ramp(),
+        owner(),
+        account("recipient").writable(),
+        sol_vault(),
+    ], None);
+    idl.instruction("SetAssetWindDown", 43, Instruction::SetAssetWindDown, vec![
+        ramp().writable(),
+        owner(),
+    ], None);
+    idl.instruction("CloseRamp", 44, Instruction::CloseRamp, vec![
+        ramp().writable(),
+        owner().writable().docs("receives the rent of every closed account"),
+        account("recipient").writable().docs("receives the SOL liquidity and owns the token accounts the liquidity is sent to"),
+        sol_vault(),
+    ], Some("the wallets of the revenue split beneficiaries in split order, or the vault address without a split, then [mint, ramp_token_account, recipient_token_account, token_program, beneficiary_token_accounts...] per listed mint, in listing order"));
+    idl.instruction("ClaimCredit", 45, Instruction::ClaimCredit, vec![
+        ramp().writable(),
+        account("mint"),
+        account("recipient").signer(),
+        account("recipient_token_account").writable(),
+        credit(),
+        account("credit_token_account").writable(),
+        account("payer").writable().docs("paid the rent of the credit accounts and receives it back"),
+        token_program(),
+        system_program(),
+        access_entry("recipient"),
+    ], None);
+    idl.instruction("MigrateRamp", 46, Instruction::MigrateRamp, vec![
+        ramp().writable(),
+        owner().writable().docs("pays the rent of the grown ramp account"),
+        system_program(),
+    ], Some("every escrowed deposit receipt and every credit entry of the ramp, in any order"));
+
+    let accounts = vec![
+        idl.account::<RampState>(),
+        idl.account::<DepositReceipt>(),
+        idl.account::<AccessEntry>(),
+        idl.account::<CreditEntry>(),
+    ];
+    let events = vec![idl.event::<RampDeposit>()];
+    let types: Vec<Value> = idl.types
+        .into_iter()
+        .map(|(name, ty)| json!({ "name": name, "type": ty }))
+        .collect();
+
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "name": "ramp_solana",
+        "instructions": idl.instructions,
+        "accounts": accounts,
+        "types": types,
+        "events": events,
+        "errors": errors(),
+        "metadata": { "origin": "shank" },
+    })
+}
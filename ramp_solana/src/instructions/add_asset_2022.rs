@@ -36,17 +36,16 @@ pub fn add_assets_2022(
     if args.fee_percentage > 100 {
         return Err(RampError::InvalidFeePercentage.into());
     }
-    let mut ramp_state: RampState = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        borsh::from_slice(&ramp_data)?
-    };
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
 
-    if  !ramp_state.is_active {
+    if  !ramp_state.is_active.get() {
         return Err(RampError::UninitializedAccount.into());
     }
     if owner_account.key != &ramp_state.owner {
         return Err(RampError::Unauthorized.into());
     }
+    drop(ramp_data);
     if ramp_token_account.lamports() == 0 {
         let account_instructions = create_associated_token_account(
             owner_account.key,
@@ -87,15 +86,10 @@ pub fn add_assets_2022(
     if transfer_result.is_err() {
         return Err(RampError::TransferFailed.into());
     }
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
     match ramp_state.add_asset(*asset_mint_account.key, args.fee_percentage) {
         Ok(()) => {
-            let mut ramp_data = ramp_account.try_borrow_mut_data()?;
-            ramp_data.fill(0);
-            let serialized_data = borsh::to_vec(&ramp_state).expect("Failed to serialize ramp state");
-            if serialized_data.len() > ramp_data.len() {
-                return Err(RampError::InvalidAccountState.into());
-            }
-            ramp_data[..serialized_data.len()].copy_from_slice(&serialized_data);    
             msg!("Assets added successfully");
         },
         Err(_) => {
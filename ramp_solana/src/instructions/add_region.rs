@@ -0,0 +1,51 @@
+use crate::{errors::RampError, models::{Medium, Region}, state::{RampState, MAX_REGION_MEDIUMS}};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AddRegionInstruction {
+    pub region: Region,
+    pub mediums: Vec<Medium>,
+}
+
+crate::idl::idl_struct!(AddRegionInstruction { region: Region, mediums: Vec<Medium> });
+
+pub fn add_region(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: AddRegionInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if args.mediums.len() > MAX_REGION_MEDIUMS || args.mediums.iter().any(|medium| medium.is_empty()) {
+        return Err(RampError::MediumNotFound.into());
+    }
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            match ramp_state.add_region(args.region, &args.mediums) {
+                Ok(()) => {
+                    msg!("Region added {:?} with {} mediums", args.region, args.mediums.len());
+                    Ok(())
+                },
+                Err(_) => {
+                    return Err(RampError::RegionAlreadyExists.into());
+                }
+            }
+        },
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+}
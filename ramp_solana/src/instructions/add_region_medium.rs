@@ -0,0 +1,54 @@
+use crate::{errors::RampError, models::{Medium, Region}, state::RampState};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AddRegionMediumInstruction {
+    pub region: Region,
+    pub medium: Medium,
+}
+
+crate::idl::idl_struct!(AddRegionMediumInstruction { region: Region, medium: Medium });
+
+pub fn add_region_medium(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: AddRegionMediumInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if args.medium.is_empty() {
+        return Err(RampError::MediumNotFound.into());
+    }
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            match ramp_state.get_region_entry(&args.region) {
+                Some(region_entry) => {
+                    if region_entry.add_medium(args.medium).is_err() {
+                        return Err(RampError::MediumAlreadyExists.into());
+                    }
+                },
+                None => {
+                    return Err(RampError::RegionNotFound.into());
+                }
+            }
+            msg!("Medium {:?} added to region {:?}", args.medium, args.region);
+            Ok(())
+        },
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+}
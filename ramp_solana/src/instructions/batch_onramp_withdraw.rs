@@ -0,0 +1,168 @@
+use crate::{
+    errors::RampError,
+    models::{BatchPayout, BatchPayoutResult, PayoutStatus, RampBatchWithdraw},
+    state::RampState,
+    utils::{check_low_water_mark, load_access_status, mint_decimals, token_liquidity},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    pubkey::Pubkey,
+};
+use spl_token_2022_interface::{
+    extension::StateWithExtensions,
+    instruction as token_instruction,
+    state::{Account, AccountState},
+};
+
+// Measured in test_batch_onramp_withdraw_compute_units, a full batch stays well under the
+// default 200k CU budget and its accounts still fit in a legacy transaction
+pub const MAX_BATCH_PAYOUTS: usize = 20;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct BatchOnRampWithdrawInstruction {
+    pub payouts: Vec<BatchPayout>,
+}
+
+crate::idl::idl_struct!(BatchOnRampWithdrawInstruction { payouts: Vec<BatchPayout> });
+
+// Pays several recipients of one mint. Recipient token accounts follow the fixed accounts in the
+// same order as `payouts`, then the access entry PDA of each recipient's wallet in the same order.
+// Invalid or restricted recipients are skipped and reported instead of failing the batch
+pub fn batch_onramp_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: BatchOnRampWithdrawInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let ramp_owner = next_account_info(account_info_iter)?;
+    let ramp_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let remaining_accounts = account_info_iter.as_slice();
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let (owner, signer, status) = (
+        ramp_owner.key == &ramp_state.owner,
+        ramp_owner.is_signer,
+        ramp_state.is_active.get()
+    );
+    match (owner, signer, status) {
+        (true, true, true) => {},
+        (true, true, false) => {
+            return Err(RampError::ProgramNotActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    if ramp_state.is_withdrawal_paused(asset_mint_account.key) {
+        return Err(RampError::AssetWithdrawalsPaused.into());
+    }
+    if args.payouts.is_empty() || args.payouts.len() * 2 != remaining_accounts.len() {
+        return Err(RampError::InvalidBatch.into());
+    }
+    let (recipient_accounts, access_accounts) = remaining_accounts.split_at(args.payouts.len());
+    if args.payouts.len() > MAX_BATCH_PAYOUTS {
+        return Err(RampError::BatchTooLarge.into());
+    }
+    let revenue = match ramp_state.get_asset_info_ref(asset_mint_account.key) {
+        Some(asset) => asset.asset_revenue.get(),
+        None => {
+            return Err(RampError::AssetNotFound.into());
+        }
+    };
+    let available = token_liquidity(ramp_account, asset_mint_account, ramp_token_account, token_program, revenue)?;
+
+    let mut results = Vec::with_capacity(args.payouts.len());
+    let mut total: u64 = 0;
+    for ((payout, recipient_account), access_account) in args.payouts.iter().zip(recipient_accounts).zip(access_accounts) {
+        if payout.recipient != *recipient_account.key {
+            return Err(RampError::InvalidBatch.into());
+        }
+        let recipient_wallet = if recipient_account.owner == token_program.key {
+            let recipient_data = recipient_account.try_borrow_data()?;
+            StateWithExtensions::<Account>::unpack(&recipient_data)
+                .ok()
+                .filter(|recipient| {
+                    recipient.base.mint == *asset_mint_account.key
+                        && recipient.base.state == AccountState::Initialized
+                })
+                .map(|recipient| recipient.base.owner)
+        } else {
+            None
+        };
+        let status = match recipient_wallet {
+            Some(wallet) => {
+                let access_status = load_access_status(program_id, ramp_account, access_account, &wallet)?;
+                if ramp_state.is_address_permitted(access_status) {
+                    total = total.checked_add(payout.amount).ok_or(RampError::InvalidBatch)?;
+                    PayoutStatus::Paid
+                } else {
+                    PayoutStatus::Restricted
+                }
+            },
+            None => PayoutStatus::InvalidAccount,
+        };
+        results.push(BatchPayoutResult {
+            recipient: payout.recipient,
+            amount: payout.amount,
+            status,
+        });
+    }
+
+    // liquidity is checked once for the whole batch, revenue stays in the ramp
+    if available < total {
+        return Err(RampError::InsufficientFunds.into());
+    }
+
+    let bump = [ramp_state.bump];
+    let ramp_seeds: &[&[u8]] = &[b"ramp", ramp_state.creator.as_ref(), &bump];
+    for (result, recipient_account) in results.iter().zip(recipient_accounts) {
+        if result.status != PayoutStatus::Paid {
+            continue;
+        }
+        let transfer_instructions = token_instruction::transfer(
+            token_program.key,
+            ramp_token_account.key,
+            recipient_account.key,
+            ramp_account.key,
+            &[ramp_account.key],
+            result.amount,
+        )?;
+        let transfer_result = invoke_signed(
+            &transfer_instructions,
+            &[
+                ramp_token_account.clone(),
+                recipient_account.clone(),
+                ramp_account.clone(),
+                token_program.clone(),
+            ],
+            &[ramp_seeds],
+        );
+        if transfer_result.is_err() {
+            return Err(RampError::TransferFailed.into());
+        }
+    }
+
+    let decimals = mint_decimals(asset_mint_account)?;
+    check_low_water_mark(ramp_state, asset_mint_account.key, decimals, available, available - total);
+    msg!("RampBatchWithdraw:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampBatchWithdraw {
+            asset: *asset_mint_account.key,
+            decimals,
+            total_paid: total,
+            results,
+        }).unwrap()
+    ));
+    Ok(())
+}
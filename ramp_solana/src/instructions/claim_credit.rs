@@ -0,0 +1,142 @@
+use crate::{
+    errors::RampError,
+    models::RampCreditClaimed,
+    state::{CreditEntry, RampState, CREDIT_SEED},
+    utils::{check_address_access, load_credit_entry, mint_decimals},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    pubkey::Pubkey,
+};
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+use spl_token_2022_interface::instruction as token_instruction;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ClaimCreditInstruction {}
+
+crate::idl::idl_struct!(ClaimCreditInstruction {});
+
+// Lets the recipient collect payouts that were credited because they had no token account. No
+// operator signature or active ramp needed, the rent of the credit accounts goes back to its payer
+pub fn claim_credit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _args: ClaimCreditInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let recipient_token_account = next_account_info(account_info_iter)?;
+    let credit_account = next_account_info(account_info_iter)?;
+    let credit_token_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+
+    if !recipient_account.is_signer {
+        return Err(RampError::InvalidSigner.into());
+    }
+    let entry = load_credit_entry(
+        program_id,
+        ramp_account,
+        credit_account,
+        recipient_account.key,
+        asset_mint_account.key,
+    )?
+    .ok_or(RampError::NoCredit)?;
+    if entry.amount == 0 {
+        return Err(RampError::NoCredit.into());
+    }
+    if payer_account.key != &entry.payer {
+        return Err(RampError::InvalidRecipient.into());
+    }
+    {
+        let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+        let ramp_state = RampState::load_mut(&mut ramp_data)?;
+        check_address_access(program_id, ramp_account, ramp_state, access_account, recipient_account.key)?;
+        ramp_state.settle_credit();
+    }
+    let credit_token_address = get_associated_token_address_with_program_id(
+        credit_account.key,
+        asset_mint_account.key,
+        token_program.key,
+    );
+    if credit_token_address != *credit_token_account.key {
+        return Err(RampError::InvalidCreditAccount.into());
+    }
+
+    let (_, credit_bump) = CreditEntry::find_address(
+        program_id,
+        ramp_account.key,
+        recipient_account.key,
+        asset_mint_account.key,
+    );
+    let bump = [credit_bump];
+    let credit_seeds: &[&[u8]] = &[
+        CREDIT_SEED,
+        ramp_account.key.as_ref(),
+        recipient_account.key.as_ref(),
+        asset_mint_account.key.as_ref(),
+        &bump,
+    ];
+    let transfer_result = invoke_signed(
+        &token_instruction::transfer(
+            token_program.key,
+            credit_token_account.key,
+            recipient_token_account.key,
+            credit_account.key,
+            &[credit_account.key],
+            entry.amount,
+        )?,
+        &[
+            credit_token_account.clone(),
+            recipient_token_account.clone(),
+            credit_account.clone(),
+            token_program.clone(),
+        ],
+        &[credit_seeds],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+    invoke_signed(
+        &token_instruction::close_account(
+            token_program.key,
+            credit_token_account.key,
+            payer_account.key,
+            credit_account.key,
+            &[credit_account.key],
+        )?,
+        &[
+            credit_token_account.clone(),
+            payer_account.clone(),
+            credit_account.clone(),
+            token_program.clone(),
+        ],
+        &[credit_seeds],
+    )?;
+
+    // closing returns the rent to the payer
+    let lamports = credit_account.lamports();
+    **credit_account.try_borrow_mut_lamports()? -= lamports;
+    **payer_account.try_borrow_mut_lamports()? += lamports;
+    credit_account.resize(0)?;
+    credit_account.assign(system_program.key);
+
+    msg!("RampCreditClaimed:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampCreditClaimed {
+            asset: entry.asset,
+            decimals: mint_decimals(asset_mint_account)?,
+            recipient: entry.recipient,
+            amount: entry.amount,
+        }).unwrap()
+    ));
+    Ok(())
+}
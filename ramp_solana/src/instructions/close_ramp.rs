@@ -0,0 +1,261 @@
+use crate::{
+    errors::RampError,
+    models::{ClosedAsset, RampClosed, RampRevenueWithdrawn, RevenueShare},
+    state::{find_sol_vault_address, split_amount, RampState, NATIVE_DECIMALS},
+    utils::{distribute_token_revenue, mint_decimals},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+use spl_token_2022_interface::{
+    extension::StateWithExtensions,
+    instruction as token_instruction,
+    state::Account,
+};
+
+// Accounts passed for every listed mint after the beneficiary wallets:
+// [mint, ramp_token_account, recipient_token_account, token_program], followed by the token
+// accounts of the revenue split beneficiaries in split order
+pub const CLOSE_ACCOUNTS_PER_ASSET: usize = 4;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CloseRampInstruction {}
+
+crate::idl::idl_struct!(CloseRampInstruction {});
+
+// Decommissions an inactive ramp without open escrows or unclaimed credits. For every listed mint the outstanding revenue
+// is split between the revenue split beneficiaries (the vault without a split) and the remaining
+// liquidity goes to the recipient's token account, then the ramp's token account is closed. The SOL
+// vault is emptied the same way, and the state account is closed last, with all rent returned to the owner
+pub fn close_ramp(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _args: CloseRampInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+    let remaining_accounts = account_info_iter.as_slice();
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+
+    let (owner, signer, is_active) = (
+        owner_account.key == &ramp_state.owner,
+        owner_account.is_signer,
+        ramp_state.is_active.get(),
+    );
+
+    match (owner, signer, is_active) {
+        (true, true, false) => {},
+        (true, true, true) => {
+            return Err(RampError::RampStillActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    // escrowed deposits can only be settled through the ramp, so they have to be closed first
+    if ramp_state.open_escrows.get() > 0 {
+        return Err(RampError::EscrowsOutstanding.into());
+    }
+    // credited tokens are owed to their recipients, who claim them through the ramp
+    if ramp_state.open_credits.get() > 0 {
+        return Err(RampError::CreditsOutstanding.into());
+    }
+
+    let beneficiaries = ramp_state.revenue_split.recipients(ramp_state.vault_address);
+    if remaining_accounts.len() < beneficiaries.len() {
+        return Err(RampError::InvalidRevenueSplit.into());
+    }
+    let (beneficiary_accounts, asset_accounts) = remaining_accounts.split_at(beneficiaries.len());
+    if beneficiaries.iter().zip(beneficiary_accounts).any(|(beneficiary, account)| beneficiary.address != *account.key) {
+        return Err(RampError::InvalidRecipient.into());
+    }
+
+    let listed_assets = ramp_state.listed_assets();
+    let accounts_per_asset = CLOSE_ACCOUNTS_PER_ASSET + beneficiaries.len();
+    if asset_accounts.len() != listed_assets.len() * accounts_per_asset {
+        return Err(RampError::InvalidAccountState.into());
+    }
+
+    let bump = [ramp_state.bump];
+    let ramp_seeds: &[&[u8]] = &[b"ramp", ramp_state.creator.as_ref(), &bump];
+    let mut closed_assets = Vec::with_capacity(listed_assets.len());
+    let mut rent_returned: u64 = 0;
+    for (asset, mint_accounts) in listed_assets.iter().zip(asset_accounts.chunks(accounts_per_asset)) {
+        let (mint_accounts, beneficiary_token_accounts) = mint_accounts.split_at(CLOSE_ACCOUNTS_PER_ASSET);
+        let [asset_mint_account, ramp_token_account, recipient_token_account, token_program] = mint_accounts else {
+            return Err(RampError::InvalidAccountState.into());
+        };
+        if asset_mint_account.key != asset {
+            return Err(RampError::AssetNotFound.into());
+        }
+        let ramp_token_address = get_associated_token_address_with_program_id(
+            ramp_account.key,
+            asset_mint_account.key,
+            token_program.key,
+        );
+        if ramp_token_address != *ramp_token_account.key {
+            return Err(RampError::InvalidAccountState.into());
+        }
+        let balance = {
+            let ramp_token_data = ramp_token_account.try_borrow_data()?;
+            StateWithExtensions::<Account>::unpack(&ramp_token_data)?.base.amount
+        };
+        let asset_revenue = ramp_state.get_asset_info_ref(asset)
+            .map(|info| info.asset_revenue.get())
+            .ok_or(RampError::AssetNotFound)?;
+        let revenue = asset_revenue.min(balance as u128) as u64;
+        let liquidity = balance - revenue;
+        let decimals = mint_decimals(asset_mint_account)?;
+
+        if revenue > 0 {
+            let shares = distribute_token_revenue(
+                ramp_account,
+                ramp_state,
+                asset_mint_account,
+                ramp_token_account,
+                token_program,
+                beneficiary_token_accounts,
+                revenue,
+            )?;
+            msg!("RampRevenueWithdrawn:{}", general_purpose::STANDARD.encode(
+                borsh::to_vec(&RampRevenueWithdrawn {
+                    asset: *asset,
+                    decimals,
+                    amount: revenue,
+                    shares,
+                }).unwrap()
+            ));
+        }
+        if liquidity > 0 {
+            {
+                let recipient_data = recipient_token_account.try_borrow_data()?;
+                let token_account = StateWithExtensions::<Account>::unpack(&recipient_data)
+                    .map_err(|_| RampError::InvalidRecipient)?;
+                if token_account.base.owner != *recipient_account.key || token_account.base.mint != *asset {
+                    return Err(RampError::InvalidRecipient.into());
+                }
+            }
+            let transfer_result = invoke_signed(
+                &token_instruction::transfer(
+                    token_program.key,
+                    ramp_token_account.key,
+                    recipient_token_account.key,
+                    ramp_account.key,
+                    &[ramp_account.key],
+                    liquidity,
+                )?,
+                &[
+                    ramp_token_account.clone(),
+                    recipient_token_account.clone(),
+                    ramp_account.clone(),
+                    token_program.clone(),
+                ],
+                &[ramp_seeds],
+            );
+            if transfer_result.is_err() {
+                return Err(RampError::TransferFailed.into());
+            }
+        }
+
+        rent_returned = rent_returned.saturating_add(ramp_token_account.lamports());
+        invoke_signed(
+            &token_instruction::close_account(
+                token_program.key,
+                ramp_token_account.key,
+                owner_account.key,
+                ramp_account.key,
+                &[ramp_account.key],
+            )?,
+            &[
+                ramp_token_account.clone(),
+                owner_account.clone(),
+                ramp_account.clone(),
+                token_program.clone(),
+            ],
+            &[ramp_seeds],
+        )?;
+        closed_assets.push(ClosedAsset {
+            asset: *asset,
+            decimals,
+            revenue,
+            liquidity,
+        });
+    }
+
+    let (sol_vault_address, _) = find_sol_vault_address(program_id, ramp_account.key);
+    if sol_vault_address != *sol_vault_account.key {
+        return Err(RampError::InvalidSolVault.into());
+    }
+    let (mut native_revenue, mut native_liquidity) = (0, 0);
+    // a vault that was never created holds nothing to sweep
+    if sol_vault_account.owner == program_id {
+        let balance = sol_vault_account.lamports();
+        let rent_reserve = Rent::get()
+            .map_err(|_| RampError::RentError)?
+            .minimum_balance(sol_vault_account.data_len())
+            .min(balance);
+        native_revenue = ramp_state.native_revenue.get().min((balance - rent_reserve) as u128) as u64;
+        native_liquidity = balance - rent_reserve - native_revenue;
+        **sol_vault_account.try_borrow_mut_lamports()? = 0;
+        let amounts = split_amount(&beneficiaries, native_revenue);
+        let mut shares = Vec::with_capacity(beneficiaries.len());
+        for ((beneficiary, beneficiary_account), amount) in beneficiaries.iter().zip(beneficiary_accounts).zip(amounts) {
+            **beneficiary_account.try_borrow_mut_lamports()? += amount;
+            shares.push(RevenueShare {
+                beneficiary: beneficiary.address,
+                amount,
+            });
+        }
+        **recipient_account.try_borrow_mut_lamports()? += native_liquidity;
+        **owner_account.try_borrow_mut_lamports()? += rent_reserve;
+        rent_returned = rent_returned.saturating_add(rent_reserve);
+        if native_revenue > 0 {
+            msg!("RampRevenueWithdrawn:{}", general_purpose::STANDARD.encode(
+                borsh::to_vec(&RampRevenueWithdrawn {
+                    asset: Pubkey::default(),
+                    decimals: NATIVE_DECIMALS,
+                    amount: native_revenue,
+                    shares,
+                }).unwrap()
+            ));
+        }
+    }
+    let vault = ramp_state.vault_address;
+    drop(ramp_data);
+
+    let ramp_lamports = ramp_account.lamports();
+    **ramp_account.try_borrow_mut_lamports()? = 0;
+    **owner_account.try_borrow_mut_lamports()? += ramp_lamports;
+    ramp_account.try_borrow_mut_data()?.fill(0);
+    rent_returned = rent_returned.saturating_add(ramp_lamports);
+
+    msg!("RampClosed:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampClosed {
+            owner: *owner_account.key,
+            recipient: *recipient_account.key,
+            vault,
+            assets: closed_assets,
+            native_revenue,
+            native_liquidity,
+            rent_returned,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::{
+    errors::RampError,
+    models::RampLiquidityDefunded,
+    state::RampState,
+    utils::{check_low_water_mark, mint_decimals, token_liquidity},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    pubkey::Pubkey,
+};
+use spl_token_2022_interface::instruction as token_instruction;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct DefundAssetInstruction {
+    pub amount: u64,
+}
+
+crate::idl::idl_struct!(DefundAssetInstruction { amount: u64 });
+
+// Owner drains liquidity of a listed mint without delisting it. Revenue stays in the ramp
+pub fn defund_asset(_program_id: &Pubkey, accounts: &[AccountInfo], args: DefundAssetInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let ramp_owner = next_account_info(account_info_iter)?;
+    let recipient_token_account = next_account_info(account_info_iter)?;
+    let ramp_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let (owner, signer, status) = (
+        ramp_owner.key == &ramp_state.owner,
+        ramp_owner.is_signer,
+        ramp_state.is_active.get()
+    );
+    match (owner, signer, status) {
+        (true, true, true) => {},
+        (true, true, false) => {
+            return Err(RampError::ProgramNotActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    if args.amount == 0 {
+        return Err(RampError::InvalidAmount.into());
+    }
+    let revenue = match ramp_state.get_asset_info_ref(asset_mint_account.key) {
+        Some(asset) => asset.asset_revenue.get(),
+        None => {
+            return Err(RampError::AssetNotFound.into());
+        }
+    };
+    let available = token_liquidity(ramp_account, asset_mint_account, ramp_token_account, token_program, revenue)?;
+    if available < args.amount {
+        return Err(RampError::InsufficientFunds.into());
+    }
+
+    let transfer_instructions = token_instruction::transfer(
+        token_program.key,
+        ramp_token_account.key,
+        recipient_token_account.key,
+        ramp_account.key,
+        &[ramp_account.key],
+        args.amount,
+    )?;
+    let bump = [ramp_state.bump];
+    let transfer_result = invoke_signed(
+        &transfer_instructions,
+        &[
+            ramp_token_account.clone(),
+            recipient_token_account.clone(),
+            ramp_account.clone(),
+            token_program.clone(),
+        ],
+        &[&[b"ramp", ramp_state.creator.as_ref(), &bump]],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+
+    let decimals = mint_decimals(asset_mint_account)?;
+    check_low_water_mark(ramp_state, asset_mint_account.key, decimals, available, available - args.amount);
+    msg!("RampLiquidityDefunded:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampLiquidityDefunded {
+            asset: *asset_mint_account.key,
+            decimals,
+            recipient: *recipient_token_account.key,
+            amount: args.amount,
+        }).unwrap()
+    ));
+    Ok(())
+}
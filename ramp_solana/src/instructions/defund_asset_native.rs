@@ -0,0 +1,67 @@
+use crate::{
+    errors::RampError,
+    instructions::DefundAssetInstruction,
+    models::RampLiquidityDefunded,
+    state::{RampState, NATIVE_DECIMALS},
+    utils::{check_low_water_mark, pay_from_sol_vault, sol_vault_liquidity},
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+// Owner drains SOL liquidity from the vault. Native revenue and the vault's rent reserve stay
+pub fn defund_asset_native(program_id: &Pubkey, accounts: &[AccountInfo], args: DefundAssetInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let ramp_owner = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let (owner, signer, status) = (
+        ramp_owner.key == &ramp_state.owner,
+        ramp_owner.is_signer,
+        ramp_state.is_active.get()
+    );
+    match (owner, signer, status) {
+        (true, true, true) => {},
+        (true, true, false) => {
+            return Err(RampError::ProgramNotActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    if args.amount == 0 {
+        return Err(RampError::InvalidAmount.into());
+    }
+
+    let available = sol_vault_liquidity(program_id, ramp_account, ramp_state, sol_vault_account)?;
+    pay_from_sol_vault(
+        program_id,
+        ramp_account,
+        ramp_state,
+        sol_vault_account,
+        recipient_account,
+        args.amount,
+    )?;
+    check_low_water_mark(ramp_state, &Pubkey::default(), NATIVE_DECIMALS, available, available - args.amount);
+
+    msg!("RampLiquidityDefunded:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampLiquidityDefunded {
+            asset: Pubkey::default(),
+            decimals: NATIVE_DECIMALS,
+            recipient: *recipient_account.key,
+            amount: args.amount,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use crate::{
+    errors::RampError,
+    models::{Medium, RampDeposit, RampEscrow, Region},
+    state::{DepositReceipt, RampState, ReceiptStatus},
+    utils::{check_address_access, create_deposit_receipt, load_metaplex_metadata, mint_decimals, net_amount},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022_interface::instruction as token_instruction;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct EscrowDepositInstruction {
+    pub amount: u64,
+    pub region: Region,
+    pub medium: Medium,
+    pub data: Vec<u8>,
+    pub reference: [u8; 32],
+    pub expires_at: i64,
+}
+
+crate::idl::idl_struct!(EscrowDepositInstruction {
+    amount: u64,
+    region: Region,
+    medium: Medium,
+    data: Vec<u8>,
+    reference: [u8; 32],
+    expires_at: i64,
+});
+
+pub fn escrow_deposit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: EscrowDepositInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let depositor_account = next_account_info(account_info_iter)?;
+    let depositor_token_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let associated_token_program = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+    let metadata_account = next_account_info(account_info_iter).ok();
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if !ramp_state.is_active.get() {
+        return Err(RampError::ProgramNotActive.into());
+    }
+    if ramp_state.is_deposit_paused(asset_mint_account.key) {
+        return Err(RampError::AssetDepositsPaused.into());
+    }
+    if ramp_state.is_winding_down(asset_mint_account.key) {
+        return Err(RampError::AssetWindingDown.into());
+    }
+    check_address_access(program_id, ramp_account, ramp_state, access_account, depositor_account.key)?;
+    if !ramp_state.is_allowed_region(&args.region, &args.medium) {
+        return Err(RampError::RegionNotAllowed.into());
+    }
+    if args.expires_at <= Clock::get()?.unix_timestamp {
+        return Err(RampError::InvalidExpiry.into());
+    }
+    // escrowed tokens sit in the receipt PDA's associated token account
+    let escrow_address = get_associated_token_address_with_program_id(
+        receipt_account.key,
+        asset_mint_account.key,
+        token_program.key,
+    );
+    if escrow_address != *escrow_token_account.key {
+        return Err(RampError::InvalidEscrowAccount.into());
+    }
+    let region_fee_percentage = ramp_state.get_region_fee_percentage(&args.region);
+    // revenue is only booked once the escrow is released
+    let fee = match ramp_state.quote_fee(asset_mint_account.key, args.amount, region_fee_percentage) {
+        Some(fee) => fee,
+        None => {
+            return Err(RampError::AssetNotFound.into());
+        }
+    };
+    let sequence = ramp_state.next_deposit_sequence();
+    ramp_state.open_escrow();
+    create_deposit_receipt(
+        program_id,
+        ramp_account,
+        depositor_account,
+        receipt_account,
+        system_program,
+        &DepositReceipt {
+            reference: args.reference,
+            sequence,
+            asset: *asset_mint_account.key,
+            sender: *depositor_account.key,
+            amount: args.amount,
+            fee,
+            status: ReceiptStatus::Escrowed,
+            expires_at: args.expires_at,
+        },
+    )?;
+    invoke(
+        &create_associated_token_account_idempotent(
+            depositor_account.key,
+            receipt_account.key,
+            asset_mint_account.key,
+            token_program.key,
+        ),
+        &[
+            depositor_account.clone(),
+            escrow_token_account.clone(),
+            receipt_account.clone(),
+            asset_mint_account.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+
+    let transfer_instructions = token_instruction::transfer(
+        token_program.key,
+        depositor_token_account.key,
+        escrow_token_account.key,
+        depositor_account.key,
+        &[depositor_account.key],
+        args.amount,
+    )?;
+    let transfer_result = invoke(
+        &transfer_instructions,
+        &[
+            depositor_token_account.clone(),
+            escrow_token_account.clone(),
+            depositor_account.clone(),
+            token_program.clone(),
+        ],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+
+    let (asset_name, asset_symbol) = match metadata_account {
+        Some(metadata_account) => load_metaplex_metadata(asset_mint_account, metadata_account)?,
+        None => ("".to_string(), "".to_string()),
+    };
+
+    let decimals = mint_decimals(asset_mint_account)?;
+    msg!("RampDeposit:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampDeposit {
+            asset: *asset_mint_account.key,
+            asset_name,
+            asset_symbol,
+            decimals,
+            gross_amount: args.amount,
+            fee,
+            net_amount: net_amount(args.amount, fee),
+            sender: *depositor_account.key,
+            region: args.region,
+            medium: args.medium,
+            data: args.data,
+            reference: args.reference,
+            sequence,
+        }).unwrap()
+    ));
+    msg!("RampEscrow:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampEscrow {
+            asset: *asset_mint_account.key,
+            decimals,
+            sender: *depositor_account.key,
+            gross_amount: args.amount,
+            fee,
+            net_amount: net_amount(args.amount, fee),
+            status: ReceiptStatus::Escrowed,
+            expires_at: args.expires_at,
+            reference: args.reference,
+            sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
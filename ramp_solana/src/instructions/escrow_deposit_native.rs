@@ -0,0 +1,119 @@
+use crate::{
+    errors::RampError,
+    instructions::EscrowDepositInstruction,
+    models::{RampDeposit, RampEscrow},
+    state::{compute_fee, DepositReceipt, NATIVE_DECIMALS, RampState, ReceiptStatus},
+    utils::{check_address_access, create_deposit_receipt, net_amount},
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction::transfer;
+
+pub fn escrow_deposit_native(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: EscrowDepositInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let depositor_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if !ramp_state.is_active.get() {
+        return Err(RampError::ProgramNotActive.into());
+    }
+    if ramp_state.is_deposit_paused(&Pubkey::default()) {
+        return Err(RampError::AssetDepositsPaused.into());
+    }
+    check_address_access(program_id, ramp_account, ramp_state, access_account, depositor_account.key)?;
+    if !ramp_state.is_allowed_region(&args.region, &args.medium) {
+        return Err(RampError::RegionNotAllowed.into());
+    }
+    if args.expires_at <= Clock::get()?.unix_timestamp {
+        return Err(RampError::InvalidExpiry.into());
+    }
+    // revenue is only booked once the escrow is released
+    let fee = compute_fee(
+        ramp_state.native_fee_percentage.get(),
+        &ramp_state.native_fee_schedule,
+        ramp_state.get_region_fee_percentage(&args.region),
+        args.amount,
+    );
+    let sequence = ramp_state.next_deposit_sequence();
+    ramp_state.open_escrow();
+    create_deposit_receipt(
+        program_id,
+        ramp_account,
+        depositor_account,
+        receipt_account,
+        system_program,
+        &DepositReceipt {
+            reference: args.reference,
+            sequence,
+            asset: Pubkey::default(),
+            sender: *depositor_account.key,
+            amount: args.amount,
+            fee,
+            status: ReceiptStatus::Escrowed,
+            expires_at: args.expires_at,
+        },
+    )?;
+    // the lamports are held by the receipt PDA on top of its rent
+    invoke(
+        &transfer(
+            depositor_account.key,
+            receipt_account.key,
+            args.amount
+        ),
+        &[
+            depositor_account.clone(),
+            receipt_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    msg!("RampDeposit:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampDeposit {
+            asset: Pubkey::default(),
+            asset_name: "SOL".to_string(),
+            asset_symbol: "SOL".to_string(),
+            decimals: NATIVE_DECIMALS,
+            gross_amount: args.amount,
+            fee,
+            net_amount: net_amount(args.amount, fee),
+            sender: *depositor_account.key,
+            region: args.region,
+            medium: args.medium,
+            data: args.data,
+            reference: args.reference,
+            sequence,
+        }).unwrap()
+    ));
+    msg!("RampEscrow:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampEscrow {
+            asset: Pubkey::default(),
+            decimals: NATIVE_DECIMALS,
+            sender: *depositor_account.key,
+            gross_amount: args.amount,
+            fee,
+            net_amount: net_amount(args.amount, fee),
+            status: ReceiptStatus::Escrowed,
+            expires_at: args.expires_at,
+            reference: args.reference,
+            sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
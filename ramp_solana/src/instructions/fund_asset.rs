@@ -0,0 +1,89 @@
+use crate::{
+    errors::RampError,
+    models::RampLiquidityFunded,
+    state::RampState,
+    utils::mint_decimals,
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    pubkey::Pubkey,
+};
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+use spl_token_2022_interface::instruction as token_instruction;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct FundAssetInstruction {
+    pub amount: u64,
+}
+
+crate::idl::idl_struct!(FundAssetInstruction { amount: u64 });
+
+// Tops up the liquidity of a listed mint from any funder, e.g. a treasury wallet
+pub fn fund_asset(_program_id: &Pubkey, accounts: &[AccountInfo], args: FundAssetInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let funder_account = next_account_info(account_info_iter)?;
+    let funder_token_account = next_account_info(account_info_iter)?;
+    let ramp_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    if !ramp_state.is_active.get() {
+        return Err(RampError::ProgramNotActive.into());
+    }
+    if !funder_account.is_signer {
+        return Err(RampError::InvalidSigner.into());
+    }
+    if args.amount == 0 {
+        return Err(RampError::InvalidAmount.into());
+    }
+    if ramp_state.get_asset_info_ref(asset_mint_account.key).is_none() {
+        return Err(RampError::AssetNotFound.into());
+    }
+    let ramp_token_address = get_associated_token_address_with_program_id(
+        ramp_account.key,
+        asset_mint_account.key,
+        token_program.key,
+    );
+    if ramp_token_address != *ramp_token_account.key {
+        return Err(RampError::InvalidAccountState.into());
+    }
+
+    let transfer_instructions = token_instruction::transfer(
+        token_program.key,
+        funder_token_account.key,
+        ramp_token_account.key,
+        funder_account.key,
+        &[funder_account.key],
+        args.amount,
+    )?;
+    let transfer_result = invoke(
+        &transfer_instructions,
+        &[
+            funder_token_account.clone(),
+            ramp_token_account.clone(),
+            funder_account.clone(),
+            token_program.clone(),
+        ],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+
+    msg!("RampLiquidityFunded:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampLiquidityFunded {
+            asset: *asset_mint_account.key,
+            decimals: mint_decimals(asset_mint_account)?,
+            funder: *funder_account.key,
+            amount: args.amount,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::{
+    errors::RampError,
+    instructions::FundAssetInstruction,
+    models::RampLiquidityFunded,
+    state::{RampState, NATIVE_DECIMALS},
+    utils::ensure_sol_vault,
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    pubkey::Pubkey,
+};
+use solana_system_interface::instruction::transfer;
+
+// Tops up the SOL vault from any funder
+pub fn fund_asset_native(program_id: &Pubkey, accounts: &[AccountInfo], args: FundAssetInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let funder_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    if !ramp_state.is_active.get() {
+        return Err(RampError::ProgramNotActive.into());
+    }
+    if !funder_account.is_signer {
+        return Err(RampError::InvalidSigner.into());
+    }
+    if args.amount == 0 {
+        return Err(RampError::InvalidAmount.into());
+    }
+    ensure_sol_vault(program_id, ramp_account, funder_account, sol_vault_account, system_program)?;
+    invoke(
+        &transfer(
+            funder_account.key,
+            sol_vault_account.key,
+            args.amount
+        ),
+        &[
+            funder_account.clone(),
+            sol_vault_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    msg!("RampLiquidityFunded:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampLiquidityFunded {
+            asset: Pubkey::default(),
+            decimals: NATIVE_DECIMALS,
+            funder: *funder_account.key,
+            amount: args.amount,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::{
+    errors::RampError,
+    state::RampState,
+    utils::{sol_vault_liquidity, token_liquidity},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GetAvailableLiquidityInstruction {
+    // the default pubkey reads the SOL vault
+    pub asset: Pubkey,
+}
+
+crate::idl::idl_struct!(GetAvailableLiquidityInstruction { asset: Pubkey });
+
+// Read only. Sets the liquidity available for payouts, the balance without unwithdrawn revenue,
+// as little endian u64 return data. Takes [ramp, mint, ramp_token_account, token_program] for a
+// mint and [ramp, sol_vault] for SOL
+pub fn get_available_liquidity(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: GetAvailableLiquidityInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let available = if args.asset == Pubkey::default() {
+        let sol_vault_account = next_account_info(account_info_iter)?;
+        sol_vault_liquidity(program_id, ramp_account, ramp_state, sol_vault_account)?
+    } else {
+        let asset_mint_account = next_account_info(account_info_iter)?;
+        let ramp_token_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *asset_mint_account.key != args.asset {
+            return Err(RampError::InvalidAccountState.into());
+        }
+        let revenue = match ramp_state.get_asset_info_ref(&args.asset) {
+            Some(asset) => asset.asset_revenue.get(),
+            None => {
+                return Err(RampError::AssetNotFound.into());
+            }
+        };
+        token_liquidity(ramp_account, asset_mint_account, ramp_token_account, token_program, revenue)?
+    };
+    set_return_data(&available.to_le_bytes());
+    Ok(())
+}
@@ -21,6 +21,12 @@ pub struct InitializeProgramInstruction {
     pub native_fee_percentage: u128,
 }
 
+crate::idl::idl_struct!(InitializeProgramInstruction {
+    bump: u8,
+    vault_address: Pubkey,
+    native_fee_percentage: u128,
+});
+
 pub fn initialize_program(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -30,7 +36,7 @@ pub fn initialize_program(
     let ramp_account = next_account_info(account_info_iter)?;
     let payer_account = next_account_info(account_info_iter)?;
     let system_program_account = next_account_info(account_info_iter)?;
-    let account_space = borsh::to_vec(&RampState::default()).unwrap().len();
+    let account_space = RampState::LEN;
     let rent_required = Rent::get()
         .map_err(|_| RampError::RentError)?
         .minimum_balance(account_space);
@@ -50,12 +56,13 @@ pub fn initialize_program(
         &[&[b"ramp", payer_account.key.as_ref(), &[args.bump]]],
     )?;
     let mut ramp_data = ramp_account.try_borrow_mut_data()?;
-    let mut ramp_state = RampState::default();
-    ramp_state.is_active = true;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    ramp_state.is_active = true.into();
     ramp_state.owner = *payer_account.key;
     ramp_state.vault_address = args.vault_address;
-    ramp_state.native_fee_percentage = args.native_fee_percentage;
-    ramp_state.serialize(&mut ramp_data.as_mut())?;
+    ramp_state.native_fee_percentage = args.native_fee_percentage.into();
+    ramp_state.creator = *payer_account.key;
+    ramp_state.bump = args.bump;
     msg!("account initialized");
     Ok(())
 }
\ No newline at end of file
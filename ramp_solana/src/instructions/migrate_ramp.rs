@@ -0,0 +1,114 @@
+use crate::{
+    errors::RampError,
+    state::{CreditEntry, DepositReceipt, RampState, ReceiptStatus},
+    utils::{load_credit_entry, load_deposit_receipt},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction::transfer;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MigrateRampInstruction {}
+
+crate::idl::idl_struct!(MigrateRampInstruction {});
+
+// Grows a ramp account created with an older, shorter RampState layout to the current one, with the
+// owner paying the extra rent. Fields are only ever appended, so the stored bytes keep their offsets
+// and the new fields start zeroed. The open escrow and credit counters are rebuilt from the accounts
+// following the fixed ones, which have to be every escrowed receipt and credit entry of the ramp
+pub fn migrate_ramp(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _args: MigrateRampInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let open_accounts = account_info_iter.as_slice();
+
+    if ramp_account.owner != program_id {
+        return Err(RampError::InvalidAccountState.into());
+    }
+    let stored_len = ramp_account.data_len();
+    if stored_len >= RampState::LEN {
+        return Err(RampError::RampAlreadyMigrated.into());
+    }
+    // the owner is the first field of every layout
+    let stored_owner = {
+        let ramp_data = ramp_account.try_borrow_data()?;
+        ramp_data
+            .get(..32)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or(RampError::InvalidAccountState)?
+    };
+    match (owner_account.key == &stored_owner, owner_account.is_signer) {
+        (true, true) => {},
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+
+    let mut open_escrows: u64 = 0;
+    let mut open_credits: u64 = 0;
+    for (index, open_account) in open_accounts.iter().enumerate() {
+        // counting an account twice would keep the ramp from ever being closed
+        if open_accounts[..index].iter().any(|other| other.key == open_account.key) {
+            return Err(RampError::InvalidAccountState.into());
+        }
+        if open_account.owner != program_id {
+            return Err(RampError::InvalidAccountState.into());
+        }
+        if open_account.data_len() == DepositReceipt::space() {
+            let reference = borsh::from_slice::<DepositReceipt>(&open_account.try_borrow_data()?)?.reference;
+            let receipt = load_deposit_receipt(program_id, ramp_account, open_account, &reference)?;
+            if receipt.status != ReceiptStatus::Escrowed {
+                return Err(RampError::InvalidEscrowState.into());
+            }
+            open_escrows += 1;
+        } else if open_account.data_len() == CreditEntry::space() {
+            let entry = borsh::from_slice::<CreditEntry>(&open_account.try_borrow_data()?)?;
+            load_credit_entry(program_id, ramp_account, open_account, &entry.recipient, &entry.asset)?
+                .ok_or(RampError::InvalidCreditAccount)?;
+            open_credits += 1;
+        } else {
+            return Err(RampError::InvalidAccountState.into());
+        }
+    }
+
+    let rent_required = Rent::get()
+        .map_err(|_| RampError::RentError)?
+        .minimum_balance(RampState::LEN);
+    let top_up = rent_required.saturating_sub(ramp_account.lamports());
+    if top_up > 0 {
+        invoke(
+            &transfer(owner_account.key, ramp_account.key, top_up),
+            &[
+                owner_account.clone(),
+                ramp_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+    ramp_account.resize(RampState::LEN)?;
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    ramp_data[stored_len..].fill(0);
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    ramp_state.open_escrows = open_escrows.into();
+    ramp_state.open_credits = open_credits.into();
+
+    msg!("Ramp migrated with {} open escrows and {} open credits", open_escrows, open_credits);
+    Ok(())
+}
@@ -11,6 +11,40 @@ pub mod set_native_fee_percentage;
 pub mod set_asset_fee;
 pub mod off_ramp_deposit_token22;
 pub mod add_asset_2022;
+pub mod add_region;
+pub mod add_region_medium;
+pub mod set_region_enabled;
+pub mod set_region_fee;
+pub mod refund_deposit;
+pub mod refund_deposit_native;
+pub mod escrow_deposit;
+pub mod escrow_deposit_native;
+pub mod release_escrow;
+pub mod release_escrow_native;
+pub mod reclaim_escrow;
+pub mod reclaim_escrow_native;
+pub mod set_voucher_signer;
+pub mod voucher_withdraw;
+pub mod voucher_withdraw_native;
+pub mod batch_onramp_withdraw;
+pub mod set_asset_paused;
+pub mod update_access_list;
+pub mod set_allowlist_enabled;
+pub mod set_fee_schedule;
+pub mod quote_fee;
+pub mod set_revenue_split;
+pub mod withdraw_revenue;
+pub mod withdraw_revenue_native;
+pub mod get_available_liquidity;
+pub mod set_low_water_mark;
+pub mod fund_asset;
+pub mod fund_asset_native;
+pub mod defund_asset;
+pub mod defund_asset_native;
+pub mod set_asset_wind_down;
+pub mod close_ramp;
+pub mod claim_credit;
+pub mod migrate_ramp;
 
 pub use add_assets::*;
 pub use remove_assets::*;
@@ -25,4 +59,38 @@ pub use set_native_fee_percentage::*;
 pub use set_asset_fee::*;
 pub use off_ramp_deposit_token22::*;
 pub use add_asset_2022::*;
+pub use add_region::*;
+pub use add_region_medium::*;
+pub use set_region_enabled::*;
+pub use set_region_fee::*;
+pub use refund_deposit::*;
+pub use refund_deposit_native::*;
+pub use escrow_deposit::*;
+pub use escrow_deposit_native::*;
+pub use release_escrow::*;
+pub use release_escrow_native::*;
+pub use reclaim_escrow::*;
+pub use reclaim_escrow_native::*;
+pub use set_voucher_signer::*;
+pub use voucher_withdraw::*;
+pub use voucher_withdraw_native::*;
+pub use batch_onramp_withdraw::*;
+pub use set_asset_paused::*;
+pub use update_access_list::*;
+pub use set_allowlist_enabled::*;
+pub use set_fee_schedule::*;
+pub use quote_fee::*;
+pub use set_revenue_split::*;
+pub use withdraw_revenue::*;
+pub use withdraw_revenue_native::*;
+pub use get_available_liquidity::*;
+pub use set_low_water_mark::*;
+pub use fund_asset::*;
+pub use fund_asset_native::*;
+pub use defund_asset::*;
+pub use defund_asset_native::*;
+pub use set_asset_wind_down::*;
+pub use close_ramp::*;
+pub use claim_credit::*;
+pub use migrate_ramp::*;
 
@@ -1,4 +1,9 @@
-use crate::{errors::RampError, models::{Medium, Region}, state::RampState};
+use crate::{
+    errors::RampError,
+    models::{Medium, Region},
+    state::{DepositReceipt, RampState, ReceiptStatus},
+    utils::{check_address_access, create_deposit_receipt, load_metaplex_metadata, mint_decimals, net_amount},
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo}, 
@@ -30,10 +35,19 @@ pub struct OffRampDepositInstruction {
     pub region: Region,
     pub medium: Medium,
     pub data: Vec<u8>,
+    pub reference: [u8; 32],
 }
 
+crate::idl::idl_struct!(OffRampDepositInstruction {
+    amount: u64,
+    region: Region,
+    medium: Medium,
+    data: Vec<u8>,
+    reference: [u8; 32],
+});
+
 pub fn off_ramp_deposit(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: OffRampDepositInstruction
 ) -> ProgramResult {
@@ -43,16 +57,28 @@ pub fn off_ramp_deposit(
     let asset_owner_account = next_account_info(account_info_iter)?;
     let asset_owner_token_account = next_account_info(account_info_iter)?;
     let ramp_token_account = next_account_info(account_info_iter)?;
-    //let metadata_account = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+    let metadata_account = next_account_info(account_info_iter).ok();
 
-    let mut ramp_state: RampState = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        borsh::from_slice(&ramp_data)?
-    };
-    if !ramp_state.is_active {
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if !ramp_state.is_active.get() {
         return Err(RampError::ProgramNotActive.into());
     }
+    if ramp_state.is_deposit_paused(asset_mint_account.key) {
+        return Err(RampError::AssetDepositsPaused.into());
+    }
+    if ramp_state.is_winding_down(asset_mint_account.key) {
+        return Err(RampError::AssetWindingDown.into());
+    }
+    check_address_access(program_id, ramp_account, ramp_state, access_account, asset_owner_account.key)?;
+    if !ramp_state.is_allowed_region(&args.region, &args.medium) {
+        return Err(RampError::RegionNotAllowed.into());
+    }
+    let region_fee_percentage = ramp_state.get_region_fee_percentage(&args.region);
 
     let ramp_associated_token_account = get_associated_token_address(
         ramp_account.key,
@@ -80,33 +106,54 @@ pub fn off_ramp_deposit(
     if transfer_result.is_err() {
         return Err(RampError::TransferFailed.into());
     }
-    match ramp_state.get_asset_info(asset_mint_account.clone().key) {
-        Some(asset) => {
-            let revenue = (args.amount as u128) * (asset.get_fee_percentage() / 100);
-            asset.add_revenue(revenue);
-        },
+    let fee = match ramp_state.quote_fee(asset_mint_account.key, args.amount, region_fee_percentage) {
+        Some(fee) => fee,
         None => {
             return Err(RampError::AssetNotFound.into());
         }
+    };
+    if let Some(asset) = ramp_state.get_asset_info(asset_mint_account.key) {
+        asset.add_revenue(fee);
     }
+    let sequence = ramp_state.next_deposit_sequence();
+    create_deposit_receipt(
+        program_id,
+        ramp_account,
+        asset_owner_account,
+        receipt_account,
+        system_program,
+        &DepositReceipt {
+            reference: args.reference,
+            sequence,
+            asset: *asset_mint_account.key,
+            sender: *asset_owner_account.key,
+            amount: args.amount,
+            fee,
+            status: ReceiptStatus::Deposited,
+            expires_at: 0,
+        },
+    )?;
 
-    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
-
-    ramp_state.serialize(&mut ramp_data.as_mut())?;
-
-    //let metadata_account_data = metadata_account.try_borrow_data()?;
+    let (asset_name, asset_symbol) = match metadata_account {
+        Some(metadata_account) => load_metaplex_metadata(asset_mint_account, metadata_account)?,
+        None => ("".to_string(), "".to_string()),
+    };
 
-    //let metadata: TokenMetadata = borsh::from_slice(&metadata_account_data)?;
-    
     msg!("RampDeposit:{}", general_purpose::STANDARD.encode(
         borsh::to_vec(&RampDeposit {
             asset: *asset_mint_account.key,
-            asset_name: "".to_string(),
-            amount: args.amount,
+            asset_name,
+            asset_symbol,
+            decimals: mint_decimals(asset_mint_account)?,
+            gross_amount: args.amount,
+            fee,
+            net_amount: net_amount(args.amount, fee),
             sender: *asset_owner_account.key,
             region: args.region,
             medium: args.medium,
             data: args.data,
+            reference: args.reference,
+            sequence,
         }).unwrap()
     ));    
     Ok(())
@@ -1,4 +1,4 @@
-use crate::{errors::RampError, models::{Medium, Region}, state::RampState};
+use crate::{errors::RampError, models::{Medium, Region}, state::{compute_fee, DepositReceipt, NATIVE_DECIMALS, RampState, ReceiptStatus}, utils::{check_address_access, create_deposit_receipt, ensure_sol_vault, net_amount}};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo}, 
@@ -18,10 +18,19 @@ pub struct OffRampDepositNativeInstruction {
     pub region: Region,
     pub medium: Medium,
     pub data: Vec<u8>,
+    pub reference: [u8; 32],
 }
 
+crate::idl::idl_struct!(OffRampDepositNativeInstruction {
+    amount: u64,
+    region: Region,
+    medium: Medium,
+    data: Vec<u8>,
+    reference: [u8; 32],
+});
+
 pub fn off_ramp_deposit_native(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: OffRampDepositNativeInstruction
 ) -> ProgramResult {
@@ -29,43 +38,75 @@ pub fn off_ramp_deposit_native(
     let ramp_account = next_account_info(account_info_iter)?;
     let depositor_account = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
-    let mut ramp_state: RampState = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        RampState::try_from_slice(&ramp_data)?
-    };
-    if !ramp_state.is_active {
+    let receipt_account = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if !ramp_state.is_active.get() {
         return Err(RampError::ProgramNotActive.into());
     }
+    if ramp_state.is_deposit_paused(&Pubkey::default()) {
+        return Err(RampError::AssetDepositsPaused.into());
+    }
+    check_address_access(program_id, ramp_account, ramp_state, access_account, depositor_account.key)?;
+    if !ramp_state.is_allowed_region(&args.region, &args.medium) {
+        return Err(RampError::RegionNotAllowed.into());
+    }
+    // the deposit goes to the SOL vault so the state account only ever holds its own rent
+    ensure_sol_vault(program_id, ramp_account, depositor_account, sol_vault_account, system_program)?;
     invoke(
         &transfer(
             depositor_account.key,
-            ramp_account.key,
+            sol_vault_account.key,
             args.amount
         ),
         &[
             depositor_account.clone(),
-            ramp_account.clone(),
+            sol_vault_account.clone(),
             system_program.clone(),
         ],
     )?;
-    let fee = ramp_state.native_fee_percentage * (args.amount / 100) as u128;
+    let fee = compute_fee(
+        ramp_state.native_fee_percentage.get(),
+        &ramp_state.native_fee_schedule,
+        ramp_state.get_region_fee_percentage(&args.region),
+        args.amount,
+    );
     ramp_state.update_native_revenue(fee);
-    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
-    ramp_data.fill(0);
-    let serialized_data = borsh::to_vec(&ramp_state).expect("Failed to serialize ramp state");
-    if serialized_data.len() > ramp_data.len() {
-        return Err(RampError::InvalidAccountState.into());
-    }
-    ramp_data[..serialized_data.len()].copy_from_slice(&serialized_data);
+    let sequence = ramp_state.next_deposit_sequence();
+    create_deposit_receipt(
+        program_id,
+        ramp_account,
+        depositor_account,
+        receipt_account,
+        system_program,
+        &DepositReceipt {
+            reference: args.reference,
+            sequence,
+            asset: Pubkey::default(),
+            sender: *depositor_account.key,
+            amount: args.amount,
+            fee,
+            status: ReceiptStatus::Deposited,
+            expires_at: 0,
+        },
+    )?;
     msg!("RampDeposit:{}", general_purpose::STANDARD.encode(
         borsh::to_vec(&RampDeposit {
             asset: Pubkey::default(),
             asset_name: "SOL".to_string(),
-            amount: args.amount,
+            asset_symbol: "SOL".to_string(),
+            decimals: NATIVE_DECIMALS,
+            gross_amount: args.amount,
+            fee,
+            net_amount: net_amount(args.amount, fee),
             sender: *depositor_account.key,
             region: args.region,
             medium: args.medium,
             data: args.data,
+            reference: args.reference,
+            sequence,
         }).unwrap()
     ));
     Ok(())
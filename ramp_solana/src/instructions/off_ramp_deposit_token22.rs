@@ -1,9 +1,9 @@
 use crate::{
     errors::RampError,
     instructions::OffRampDepositInstruction,
-    state::RampState
+    state::{DepositReceipt, RampState, ReceiptStatus},
+    utils::{check_address_access, create_deposit_receipt, net_amount}
 };
-use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo}, 
     entrypoint::ProgramResult, 
@@ -28,7 +28,7 @@ use spl_token_metadata_interface::state::TokenMetadata;
 
 
 pub fn off_ramp_deposit_token_22(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: OffRampDepositInstruction
 ) -> ProgramResult {
@@ -39,21 +39,33 @@ pub fn off_ramp_deposit_token_22(
     let asset_owner_token_account = next_account_info(account_info_iter)?;
     let ramp_token_account = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
-    let mut ramp_state: RampState = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        borsh::from_slice(&ramp_data)?
-    };
-    if !ramp_state.is_active {
+    let receipt_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if !ramp_state.is_active.get() {
         return Err(RampError::ProgramNotActive.into());
     }
-    match ramp_state.get_asset_info(asset_mint_account.clone().key) {
-        Some(asset) => {
-            let revenue = (args.amount as u128) * (asset.get_fee_percentage() / 100);
-            asset.add_revenue(revenue);
-        },
+    if ramp_state.is_deposit_paused(asset_mint_account.key) {
+        return Err(RampError::AssetDepositsPaused.into());
+    }
+    if ramp_state.is_winding_down(asset_mint_account.key) {
+        return Err(RampError::AssetWindingDown.into());
+    }
+    check_address_access(program_id, ramp_account, ramp_state, access_account, asset_owner_account.key)?;
+    if !ramp_state.is_allowed_region(&args.region, &args.medium) {
+        return Err(RampError::RegionNotAllowed.into());
+    }
+    let region_fee_percentage = ramp_state.get_region_fee_percentage(&args.region);
+    let fee = match ramp_state.quote_fee(asset_mint_account.key, args.amount, region_fee_percentage) {
+        Some(fee) => fee,
         None => {
             return Err(RampError::AssetNotFound.into());
         }
+    };
+    if let Some(asset) = ramp_state.get_asset_info(asset_mint_account.key) {
+        asset.add_revenue(fee);
     }
     //let ramp_associated_token_account = get_associated_token_address_with_program_id(
     //    ramp_account.key,
@@ -80,20 +92,42 @@ pub fn off_ramp_deposit_token_22(
     if transfer_result.is_err() {
         return Err(RampError::TransferFailed.into());
     }
-    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
-    ramp_state.serialize(&mut ramp_data.as_mut())?;
+    let sequence = ramp_state.next_deposit_sequence();
+    create_deposit_receipt(
+        program_id,
+        ramp_account,
+        asset_owner_account,
+        receipt_account,
+        system_program,
+        &DepositReceipt {
+            reference: args.reference,
+            sequence,
+            asset: *asset_mint_account.key,
+            sender: *asset_owner_account.key,
+            amount: args.amount,
+            fee,
+            status: ReceiptStatus::Deposited,
+            expires_at: 0,
+        },
+    )?;
     let mint_data = asset_mint_account.try_borrow_data()?;
     let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
     let metadata = mint_state.get_variable_len_extension::<TokenMetadata>()?;
     msg!("RampDeposit:{}", general_purpose::STANDARD.encode(
         borsh::to_vec(&RampDeposit {
             asset: metadata.mint,
-            asset_name: metadata.symbol,
-            amount: args.amount,
+            asset_name: metadata.name,
+            asset_symbol: metadata.symbol,
+            decimals: mint_state.base.decimals,
+            gross_amount: args.amount,
+            fee,
+            net_amount: net_amount(args.amount, fee),
             sender: *asset_owner_account.key,
             region: args.region,
             medium: args.medium,
             data: args.data,
+            reference: args.reference,
+            sequence,
         }).unwrap()
     ));    
     Ok(())
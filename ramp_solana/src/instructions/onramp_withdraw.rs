@@ -1,13 +1,19 @@
-use crate::{errors::RampError, state::RampState};
+use crate::{
+    errors::RampError,
+    state::RampState,
+    utils::{
+        check_address_access, check_low_water_mark, credit_tokens, mint_decimals, token_account_owner, token_liquidity,
+    },
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo}, 
     entrypoint::ProgramResult, 
     msg, 
     pubkey::Pubkey,
-    program::invoke,
+    program::invoke_signed,
 };
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
 use spl_token_interface::instruction as token_instruction;
 
 
@@ -16,8 +22,13 @@ pub struct OnRampWithdrawInstruction {
     pub amount: u64,
 }
 
+crate::idl::idl_struct!(OnRampWithdrawInstruction { amount: u64 });
+
+// When the receiver token account does not exist yet it must be the recipient's associated token
+// account, and these accounts follow the fixed ones so the payout is credited for the recipient to
+// claim: [recipient, credit_account, credit_token_account, system_program, associated_token_program]
 pub fn onramp_withdraw(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: OnRampWithdrawInstruction
 ) -> ProgramResult {
@@ -28,43 +39,101 @@ pub fn onramp_withdraw(
     let asset_receiver_token_account = next_account_info(account_info_iter)?;
     let ramp_token_account = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
-    let ramp_state: RampState = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        RampState::try_from_slice(&ramp_data)?
-    };
+    let access_account = next_account_info(account_info_iter)?;
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let mut opened_credit = false;
     let (owner, signer, status) = (
         ramp_owner.key == &ramp_state.owner,
         ramp_owner.is_signer,
-        ramp_state.is_active
+        ramp_state.is_active.get()
     );
     match (owner, signer, status) {
         (true, true, true) => {
+            if ramp_state.is_withdrawal_paused(asset_mint_account.key) {
+                return Err(RampError::AssetWithdrawalsPaused.into());
+            }
+            let credit_accounts = account_info_iter.as_slice();
+            let recipient = if asset_receiver_token_account.data_is_empty() {
+                let [recipient_account, ..] = credit_accounts else {
+                    return Err(RampError::InvalidRecipient.into());
+                };
+                let receiver_address = get_associated_token_address_with_program_id(
+                    recipient_account.key,
+                    asset_mint_account.key,
+                    token_program.key,
+                );
+                if receiver_address != *asset_receiver_token_account.key {
+                    return Err(RampError::InvalidRecipient.into());
+                }
+                *recipient_account.key
+            } else {
+                token_account_owner(asset_receiver_token_account)?
+            };
+            check_address_access(program_id, ramp_account, ramp_state, access_account, &recipient)?;
             match ramp_state.get_asset_info_ref(asset_mint_account.key) {
-                Some(_asset) => {
-                    let ramp_associated_token_account = get_associated_token_address(
-                        ramp_account.key,
-                        asset_mint_account.key,
-                    );
-                    let transfer_instructions = token_instruction::transfer(
-                        token_program.key,
-                        &ramp_associated_token_account,
-                        &asset_receiver_token_account.key,
-                        ramp_account.key,
-                        &[ramp_account.key],
-                        args.amount,
+                Some(asset) => {
+                    let available = token_liquidity(
+                        ramp_account,
+                        asset_mint_account,
+                        ramp_token_account,
+                        token_program,
+                        asset.asset_revenue.get(),
                     )?;
-                    let transfer_result = invoke(
-                        &transfer_instructions,
-                        &[
-                            ramp_token_account.clone(),
-                            asset_receiver_token_account.clone(),
-                            ramp_account.clone(),
-                            token_program.clone(),
-                        ],
-                    );
-                    if transfer_result.is_err() {
-                        return Err(RampError::TransferFailed.into());
+                    if available < args.amount {
+                        return Err(RampError::InsufficientFunds.into());
+                    }
+                    if asset_receiver_token_account.data_is_empty() {
+                        let [_, credit_account, credit_token_account, system_program, associated_token_program] = credit_accounts else {
+                            return Err(RampError::InvalidCreditAccount.into());
+                        };
+                        opened_credit = credit_tokens(
+                            program_id,
+                            ramp_account,
+                            ramp_state,
+                            ramp_owner,
+                            &recipient,
+                            asset_mint_account,
+                            ramp_token_account,
+                            credit_account,
+                            credit_token_account,
+                            token_program,
+                            system_program,
+                            associated_token_program,
+                            args.amount,
+                        )?;
+                    } else {
+                        let bump = [ramp_state.bump];
+                        let ramp_seeds: &[&[u8]] = &[b"ramp", ramp_state.creator.as_ref(), &bump];
+                        let transfer_instructions = token_instruction::transfer(
+                            token_program.key,
+                            ramp_token_account.key,
+                            asset_receiver_token_account.key,
+                            ramp_account.key,
+                            &[ramp_account.key],
+                            args.amount,
+                        )?;
+                        let transfer_result = invoke_signed(
+                            &transfer_instructions,
+                            &[
+                                ramp_token_account.clone(),
+                                asset_receiver_token_account.clone(),
+                                ramp_account.clone(),
+                                token_program.clone(),
+                            ],
+                            &[ramp_seeds],
+                        );
+                        if transfer_result.is_err() {
+                            return Err(RampError::TransferFailed.into());
+                        }
                     }
+                    check_low_water_mark(
+                        ramp_state,
+                        asset_mint_account.key,
+                        mint_decimals(asset_mint_account)?,
+                        available,
+                        available - args.amount,
+                    );
                 },
                 None => {
                     return Err(RampError::AssetNotFound.into());
@@ -82,6 +151,12 @@ pub fn onramp_withdraw(
             return Err(RampError::Unauthorized.into());
         }
     }  
+    drop(ramp_data);
+
+    if opened_credit {
+        let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+        RampState::load_mut(&mut ramp_data)?.open_credit();
+    }
     Ok(())
 }
 
@@ -5,15 +5,21 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-use crate::{errors::RampError, state::RampState};
+use crate::{
+    errors::RampError,
+    state::{RampState, NATIVE_DECIMALS},
+    utils::{check_address_access, check_low_water_mark, pay_from_sol_vault, sol_vault_liquidity},
+};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct OnRampWithdrawNativeInstruction {
     pub amount: u64,
 }
 
+crate::idl::idl_struct!(OnRampWithdrawNativeInstruction { amount: u64 });
+
 pub fn onramp_withdraw_native(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: OnRampWithdrawNativeInstruction
 ) -> ProgramResult {
@@ -21,30 +27,39 @@ pub fn onramp_withdraw_native(
     let ramp_account = next_account_info(account_info_iter)?;
     let recipient_account = next_account_info(account_info_iter)?;
     let owner_account = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
 
-    let ramp_state: RampState = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        RampState::try_from_slice(&ramp_data)?
-    };
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
 
-    if !ramp_state.is_active {
+    if !ramp_state.is_active.get() {
         return Err(RampError::ProgramNotActive.into());
     }
+    if ramp_state.is_withdrawal_paused(&Pubkey::default()) {
+        return Err(RampError::AssetWithdrawalsPaused.into());
+    }
+    check_address_access(program_id, ramp_account, ramp_state, access_account, recipient_account.key)?;
 
-    let (owner, signer) = (owner_account.key != &ramp_state.owner, owner_account.is_signer);
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
 
     match (owner, signer) {
         (true, true) => {
-            **ramp_account.try_borrow_mut_lamports()? -= args.amount;
-            **recipient_account.try_borrow_mut_lamports()? += args.amount;
-            
+            let available = sol_vault_liquidity(program_id, ramp_account, ramp_state, sol_vault_account)?;
+            pay_from_sol_vault(
+                program_id,
+                ramp_account,
+                ramp_state,
+                sol_vault_account,
+                recipient_account,
+                args.amount,
+            )?;
+            check_low_water_mark(ramp_state, &Pubkey::default(), NATIVE_DECIMALS, available, available - args.amount);
             Ok(())
         },
-        (false, true) => {
+        (true, false) => {
             return Err(RampError::InvalidSigner.into());
         },
         _ => return Err(RampError::Unauthorized.into()),
     }
-    
-
 }
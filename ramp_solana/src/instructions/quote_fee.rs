@@ -0,0 +1,41 @@
+use crate::{errors::RampError, models::Region, state::RampState};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct QuoteFeeInstruction {
+    // the default pubkey quotes native SOL
+    pub asset: Pubkey,
+    pub amount: u64,
+    // region of the deposit, so a region fee override is reflected in the quote
+    pub region: Option<Region>,
+}
+
+crate::idl::idl_struct!(QuoteFeeInstruction {
+    asset: Pubkey,
+    amount: u64,
+    region: Option<Region>,
+});
+
+// Read only. Sets the fee a deposit would pay as little endian u64 return data, so clients can
+// simulate it before signing
+pub fn quote_fee(_program_id: &Pubkey, accounts: &[AccountInfo], args: QuoteFeeInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let region_fee_percentage = args.region.and_then(|region| ramp_state.get_region_fee_percentage(&region));
+    let fee = match ramp_state.quote_fee(&args.asset, args.amount, region_fee_percentage) {
+        Some(fee) => fee as u64,
+        None => {
+            return Err(RampError::AssetNotFound.into());
+        }
+    };
+    set_return_data(&fee.to_le_bytes());
+    Ok(())
+}
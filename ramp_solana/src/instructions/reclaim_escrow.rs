@@ -0,0 +1,87 @@
+use crate::{
+    errors::RampError,
+    instructions::SettleEscrowInstruction,
+    models::RampEscrow,
+    state::{RampState, ReceiptStatus},
+    utils::{
+        load_deposit_receipt, mint_decimals, net_amount, save_deposit_receipt, settle_escrow_tokens,
+    },
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+// Lets the depositor take back an expired escrow, no operator signature or active ramp needed
+pub fn reclaim_escrow(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SettleEscrowInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let sender_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let mut receipt = load_deposit_receipt(program_id, ramp_account, receipt_account, &args.reference)?;
+    let (sender, signer) = (sender_account.key == &receipt.sender, sender_account.is_signer);
+    match (sender, signer) {
+        (true, true) => {},
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    if receipt.status != ReceiptStatus::Escrowed {
+        return Err(RampError::InvalidEscrowState.into());
+    }
+    if !receipt.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(RampError::EscrowNotExpired.into());
+    }
+    if receipt.asset != *asset_mint_account.key {
+        return Err(RampError::AssetNotFound.into());
+    }
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    RampState::load_mut(&mut ramp_data)?.settle_escrow();
+
+    settle_escrow_tokens(
+        program_id,
+        ramp_account,
+        receipt_account,
+        escrow_token_account,
+        sender_token_account,
+        sender_account,
+        token_program,
+        &receipt,
+    )?;
+
+    receipt.status = ReceiptStatus::Reclaimed;
+    save_deposit_receipt(receipt_account, &receipt)?;
+
+    msg!("RampEscrow:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampEscrow {
+            asset: receipt.asset,
+            decimals: mint_decimals(asset_mint_account)?,
+            sender: receipt.sender,
+            gross_amount: receipt.amount,
+            fee: receipt.fee,
+            net_amount: net_amount(receipt.amount, receipt.fee),
+            status: receipt.status,
+            expires_at: receipt.expires_at,
+            reference: receipt.reference,
+            sequence: receipt.sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::{
+    errors::RampError,
+    instructions::SettleEscrowInstruction,
+    models::RampEscrow,
+    state::{NATIVE_DECIMALS, RampState, ReceiptStatus},
+    utils::{load_deposit_receipt, net_amount, save_deposit_receipt},
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+pub fn reclaim_escrow_native(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SettleEscrowInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+
+    let mut receipt = load_deposit_receipt(program_id, ramp_account, receipt_account, &args.reference)?;
+    let (sender, signer) = (sender_account.key == &receipt.sender, sender_account.is_signer);
+    match (sender, signer) {
+        (true, true) => {},
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    if receipt.status != ReceiptStatus::Escrowed || receipt.asset != Pubkey::default() {
+        return Err(RampError::InvalidEscrowState.into());
+    }
+    if !receipt.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(RampError::EscrowNotExpired.into());
+    }
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    RampState::load_mut(&mut ramp_data)?.settle_escrow();
+
+    **receipt_account.try_borrow_mut_lamports()? -= receipt.amount;
+    **sender_account.try_borrow_mut_lamports()? += receipt.amount;
+
+    receipt.status = ReceiptStatus::Reclaimed;
+    save_deposit_receipt(receipt_account, &receipt)?;
+
+    msg!("RampEscrow:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampEscrow {
+            asset: receipt.asset,
+            decimals: NATIVE_DECIMALS,
+            sender: receipt.sender,
+            gross_amount: receipt.amount,
+            fee: receipt.fee,
+            net_amount: net_amount(receipt.amount, receipt.fee),
+            status: receipt.status,
+            expires_at: receipt.expires_at,
+            reference: receipt.reference,
+            sequence: receipt.sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
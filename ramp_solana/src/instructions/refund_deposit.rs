@@ -0,0 +1,138 @@
+use crate::{
+    errors::RampError,
+    models::RampRefund,
+    state::{RampState, ReceiptStatus},
+    utils::{load_deposit_receipt, mint_decimals, save_deposit_receipt},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+};
+use spl_token_2022_interface::{
+    extension::StateWithExtensions,
+    instruction as token_instruction,
+    state::Account,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct RefundDepositInstruction {
+    pub reference: [u8; 32],
+    pub refund_fee: bool,
+}
+
+crate::idl::idl_struct!(RefundDepositInstruction { reference: [u8; 32], refund_fee: bool });
+
+pub fn refund_deposit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RefundDepositInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let sender_token_account = next_account_info(account_info_iter)?;
+    let ramp_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {},
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    let mut receipt = load_deposit_receipt(program_id, ramp_account, receipt_account, &args.reference)?;
+    match receipt.status {
+        ReceiptStatus::Deposited | ReceiptStatus::Released => {},
+        ReceiptStatus::Refunded => {
+            return Err(RampError::DepositAlreadyRefunded.into());
+        },
+        _ => {
+            return Err(RampError::InvalidEscrowState.into());
+        }
+    }
+    if receipt.asset != *asset_mint_account.key {
+        return Err(RampError::AssetNotFound.into());
+    }
+    {
+        let sender_token_data = sender_token_account.try_borrow_data()?;
+        let sender_token_state = StateWithExtensions::<Account>::unpack(&sender_token_data)?;
+        if sender_token_state.base.owner != receipt.sender || sender_token_state.base.mint != receipt.asset {
+            return Err(RampError::InvalidRecipient.into());
+        }
+    }
+    if ramp_state.is_withdrawal_paused(&receipt.asset) {
+        return Err(RampError::AssetWithdrawalsPaused.into());
+    }
+    let fee = u64::try_from(receipt.fee).map_err(|_| RampError::InvalidAccountState)?;
+    let refund_amount = if args.refund_fee {
+        receipt.amount
+    } else {
+        receipt.amount.saturating_sub(fee)
+    };
+    if ramp_state.get_asset_info_ref(asset_mint_account.key).is_none() {
+        return Err(RampError::AssetNotFound.into());
+    }
+
+    let transfer_instructions = token_instruction::transfer(
+        token_program.key,
+        ramp_token_account.key,
+        sender_token_account.key,
+        ramp_account.key,
+        &[ramp_account.key],
+        refund_amount,
+    )?;
+    let bump = [ramp_state.bump];
+    let transfer_result = invoke_signed(
+        &transfer_instructions,
+        &[
+            ramp_token_account.clone(),
+            sender_token_account.clone(),
+            ramp_account.clone(),
+            token_program.clone(),
+        ],
+        &[&[b"ramp", ramp_state.creator.as_ref(), &bump]],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+    drop(ramp_data);
+
+    receipt.status = ReceiptStatus::Refunded;
+    save_deposit_receipt(receipt_account, &receipt)?;
+
+    if args.refund_fee {
+        let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+        let ramp_state = RampState::load_mut(&mut ramp_data)?;
+        if let Some(asset) = ramp_state.get_asset_info(asset_mint_account.key) {
+            asset.remove_revenue(receipt.fee);
+        }
+    }
+
+    msg!("RampRefund:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampRefund {
+            asset: receipt.asset,
+            decimals: mint_decimals(asset_mint_account)?,
+            recipient: receipt.sender,
+            gross_amount: receipt.amount,
+            fee: receipt.fee,
+            amount: refund_amount,
+            fee_refunded: args.refund_fee,
+            reference: args.reference,
+            sequence: receipt.sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::{
+    errors::RampError,
+    instructions::RefundDepositInstruction,
+    models::RampRefund,
+    state::{NATIVE_DECIMALS, RampState, ReceiptStatus},
+    utils::{load_deposit_receipt, pay_from_sol_vault, save_deposit_receipt},
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn refund_deposit_native(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RefundDepositInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {},
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    let mut receipt = load_deposit_receipt(program_id, ramp_account, receipt_account, &args.reference)?;
+    match receipt.status {
+        ReceiptStatus::Deposited | ReceiptStatus::Released => {},
+        ReceiptStatus::Refunded => {
+            return Err(RampError::DepositAlreadyRefunded.into());
+        },
+        _ => {
+            return Err(RampError::InvalidEscrowState.into());
+        }
+    }
+    if receipt.asset != Pubkey::default() {
+        return Err(RampError::AssetNotFound.into());
+    }
+    if receipt.sender != *sender_account.key {
+        return Err(RampError::InvalidRecipient.into());
+    }
+    if ramp_state.is_withdrawal_paused(&receipt.asset) {
+        return Err(RampError::AssetWithdrawalsPaused.into());
+    }
+    let fee = u64::try_from(receipt.fee).map_err(|_| RampError::InvalidAccountState)?;
+    let refund_amount = if args.refund_fee {
+        ramp_state.reduce_native_revenue(receipt.fee);
+        receipt.amount
+    } else {
+        receipt.amount.saturating_sub(fee)
+    };
+    pay_from_sol_vault(
+        program_id,
+        ramp_account,
+        ramp_state,
+        sol_vault_account,
+        sender_account,
+        refund_amount,
+    )?;
+
+    receipt.status = ReceiptStatus::Refunded;
+    save_deposit_receipt(receipt_account, &receipt)?;
+
+    msg!("RampRefund:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampRefund {
+            asset: Pubkey::default(),
+            decimals: NATIVE_DECIMALS,
+            recipient: receipt.sender,
+            gross_amount: receipt.amount,
+            fee: receipt.fee,
+            amount: refund_amount,
+            fee_refunded: args.refund_fee,
+            reference: args.reference,
+            sequence: receipt.sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::{
+    errors::RampError,
+    models::RampEscrow,
+    state::{RampState, ReceiptStatus},
+    utils::{
+        load_deposit_receipt, mint_decimals, net_amount, save_deposit_receipt, settle_escrow_tokens,
+    },
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SettleEscrowInstruction {
+    pub reference: [u8; 32],
+}
+
+crate::idl::idl_struct!(SettleEscrowInstruction { reference: [u8; 32] });
+
+pub fn release_escrow(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SettleEscrowInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let ramp_token_account = next_account_info(account_info_iter)?;
+    let sender_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer, status) = (
+        owner_account.key == &ramp_state.owner,
+        owner_account.is_signer,
+        ramp_state.is_active.get()
+    );
+    match (owner, signer, status) {
+        (true, true, true) => {},
+        (true, true, false) => {
+            return Err(RampError::ProgramNotActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    let mut receipt = load_deposit_receipt(program_id, ramp_account, receipt_account, &args.reference)?;
+    if receipt.status != ReceiptStatus::Escrowed {
+        return Err(RampError::InvalidEscrowState.into());
+    }
+    if receipt.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(RampError::EscrowExpired.into());
+    }
+    if receipt.asset != *asset_mint_account.key {
+        return Err(RampError::AssetNotFound.into());
+    }
+    if receipt.sender != *sender_account.key {
+        return Err(RampError::InvalidRecipient.into());
+    }
+    let ramp_token_address = get_associated_token_address_with_program_id(
+        ramp_account.key,
+        asset_mint_account.key,
+        token_program.key,
+    );
+    if ramp_token_address != *ramp_token_account.key {
+        return Err(RampError::InvalidEscrowAccount.into());
+    }
+    ramp_state.settle_escrow();
+    match ramp_state.get_asset_info(asset_mint_account.key) {
+        Some(asset) => {
+            asset.add_revenue(receipt.fee);
+        },
+        None => {
+            return Err(RampError::AssetNotFound.into());
+        }
+    }
+
+    // the escrow token account's rent goes back to the depositor who paid it
+    settle_escrow_tokens(
+        program_id,
+        ramp_account,
+        receipt_account,
+        escrow_token_account,
+        ramp_token_account,
+        sender_account,
+        token_program,
+        &receipt,
+    )?;
+
+    receipt.status = ReceiptStatus::Released;
+    save_deposit_receipt(receipt_account, &receipt)?;
+
+    msg!("RampEscrow:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampEscrow {
+            asset: receipt.asset,
+            decimals: mint_decimals(asset_mint_account)?,
+            sender: receipt.sender,
+            gross_amount: receipt.amount,
+            fee: receipt.fee,
+            net_amount: net_amount(receipt.amount, receipt.fee),
+            status: receipt.status,
+            expires_at: receipt.expires_at,
+            reference: receipt.reference,
+            sequence: receipt.sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::{
+    errors::RampError,
+    instructions::SettleEscrowInstruction,
+    models::RampEscrow,
+    state::{NATIVE_DECIMALS, RampState, ReceiptStatus},
+    utils::{ensure_sol_vault, load_deposit_receipt, net_amount, save_deposit_receipt},
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+pub fn release_escrow_native(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SettleEscrowInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer, status) = (
+        owner_account.key == &ramp_state.owner,
+        owner_account.is_signer,
+        ramp_state.is_active.get()
+    );
+    match (owner, signer, status) {
+        (true, true, true) => {},
+        (true, true, false) => {
+            return Err(RampError::ProgramNotActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    let mut receipt = load_deposit_receipt(program_id, ramp_account, receipt_account, &args.reference)?;
+    if receipt.status != ReceiptStatus::Escrowed || receipt.asset != Pubkey::default() {
+        return Err(RampError::InvalidEscrowState.into());
+    }
+    if receipt.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(RampError::EscrowExpired.into());
+    }
+    ramp_state.update_native_revenue(receipt.fee);
+    ramp_state.settle_escrow();
+
+    // both accounts are program owned, so the escrowed lamports move directly
+    ensure_sol_vault(program_id, ramp_account, owner_account, sol_vault_account, system_program)?;
+    **receipt_account.try_borrow_mut_lamports()? -= receipt.amount;
+    **sol_vault_account.try_borrow_mut_lamports()? += receipt.amount;
+
+    receipt.status = ReceiptStatus::Released;
+    save_deposit_receipt(receipt_account, &receipt)?;
+
+    msg!("RampEscrow:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampEscrow {
+            asset: receipt.asset,
+            decimals: NATIVE_DECIMALS,
+            sender: receipt.sender,
+            gross_amount: receipt.amount,
+            fee: receipt.fee,
+            net_amount: net_amount(receipt.amount, receipt.fee),
+            status: receipt.status,
+            expires_at: receipt.expires_at,
+            reference: receipt.reference,
+            sequence: receipt.sequence,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -1,19 +1,36 @@
-use crate::{errors::RampError, state::RampState};
+use crate::{
+    errors::RampError,
+    models::{RampAssetRemoved, RampRevenueWithdrawn},
+    state::RampState,
+    utils::{distribute_token_revenue, mint_decimals},
+};
+use base64::{engine::general_purpose, Engine as _};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo}, 
     entrypoint::ProgramResult, 
     msg, 
     pubkey::Pubkey,
-    program_pack::Pack,
-    program::invoke,
+    program::invoke_signed,
+};
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+use spl_token_2022_interface::{
+    extension::StateWithExtensions,
+    instruction as token_instruction,
+    state::Account,
 };
-use spl_token::state::Account;
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct RemoveAssetsInstruction {
     //pub asset: Pubkey,
 }
 
+crate::idl::idl_struct!(RemoveAssetsInstruction {});
+
+// Delists a mint. Outstanding revenue is swept first, to the revenue split beneficiaries whose token
+// accounts follow the fixed accounts (the vault's token account without a split, nothing when there
+// is no revenue). The remaining liquidity goes to the owner's token account, and the ramp's token
+// account is closed with its rent returned to the owner
 pub fn remove_assets(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -26,47 +43,113 @@ pub fn remove_assets(
     let owner_account = next_account_info(account_info_iter)?;
     let owner_token_account = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
+    let beneficiary_accounts = account_info_iter.as_slice();
 
-    // Check ramp account is signer (needed for token transfer)
-    let mut ramp_state: RampState = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        borsh::from_slice(&ramp_data)?
-    };
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
 
     let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
 
     match (owner, signer) {
         (true, true) => {
-            let ramp_associated_token_account_data = Account::unpack(&ramp_associated_token_account.try_borrow_data()?)?;
-            if ramp_associated_token_account_data.amount > 0 {
-                let transfer_instructions = spl_token::instruction::transfer(
+            let revenue = match ramp_state.get_asset_info_ref(asset_mint_account.key) {
+                Some(asset) => u64::try_from(asset.asset_revenue.get()).map_err(|_| RampError::InvalidAccountState)?,
+                None => {
+                    return Err(RampError::AssetNotFound.into());
+                }
+            };
+            let ramp_token_address = get_associated_token_address_with_program_id(
+                ramp_account.key,
+                asset_mint_account.key,
+                token_program.key,
+            );
+            if ramp_token_address != *ramp_associated_token_account.key {
+                return Err(RampError::InvalidAccountState.into());
+            }
+            let balance = {
+                let ramp_token_data = ramp_associated_token_account.try_borrow_data()?;
+                StateWithExtensions::<Account>::unpack(&ramp_token_data)?.base.amount
+            };
+            let decimals = mint_decimals(asset_mint_account)?;
+
+            if revenue > 0 {
+                let shares = distribute_token_revenue(
+                    ramp_account,
+                    ramp_state,
+                    asset_mint_account,
+                    ramp_associated_token_account,
+                    token_program,
+                    beneficiary_accounts,
+                    revenue,
+                )?;
+                msg!("RampRevenueWithdrawn:{}", general_purpose::STANDARD.encode(
+                    borsh::to_vec(&RampRevenueWithdrawn {
+                        asset: *asset_mint_account.key,
+                        decimals,
+                        amount: revenue,
+                        shares,
+                    }).unwrap()
+                ));
+            }
+
+            let bump = [ramp_state.bump];
+            let ramp_seeds: &[&[u8]] = &[b"ramp", ramp_state.creator.as_ref(), &bump];
+            let liquidity = balance.saturating_sub(revenue);
+            if liquidity > 0 {
+                let transfer_instructions = token_instruction::transfer(
                     token_program.key,
                     ramp_associated_token_account.key,
                     owner_token_account.key,
                     ramp_account.key,
                     &[ramp_account.key],
-                    ramp_associated_token_account_data.amount,
+                    liquidity,
                 )?;
-                invoke(
+                let transfer_result = invoke_signed(
                     &transfer_instructions,
                     &[
                         ramp_associated_token_account.clone(),
                         owner_token_account.clone(),
                         ramp_account.clone(),
                         token_program.clone(),
-                    ]
-                )?;
+                    ],
+                    &[ramp_seeds],
+                );
+                if transfer_result.is_err() {
+                    return Err(RampError::TransferFailed.into());
+                }
             }
+            // the emptied token account is closed so its rent goes back to the owner
+            invoke_signed(
+                &token_instruction::close_account(
+                    token_program.key,
+                    ramp_associated_token_account.key,
+                    owner_account.key,
+                    ramp_account.key,
+                    &[ramp_account.key],
+                )?,
+                &[
+                    ramp_associated_token_account.clone(),
+                    owner_account.clone(),
+                    ramp_account.clone(),
+                    token_program.clone(),
+                ],
+                &[ramp_seeds],
+            )?;
+            drop(ramp_data);
+
+            let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+            let ramp_state = RampState::load_mut(&mut ramp_data)?;
             match ramp_state.remove_asset(asset_mint_account.key) {
                 Ok(()) => {
-                    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
-                    ramp_data.fill(0);
-                    let serialized_data = borsh::to_vec(&ramp_state).expect("Failed to serialize ramp state");
-                    if serialized_data.len() > ramp_data.len() {
-                        return Err(RampError::InvalidAccountState.into());
-                    }
-                    ramp_data[..serialized_data.len()].copy_from_slice(&serialized_data);    
-                    msg!("Assets removed successfully {}", asset_mint_account.key);
+                    msg!("RampAssetRemoved:{}", general_purpose::STANDARD.encode(
+                        borsh::to_vec(&RampAssetRemoved {
+                            asset: *asset_mint_account.key,
+                            decimals,
+                            revenue,
+                            liquidity,
+                            recipient: *owner_token_account.key,
+                        }).unwrap()
+                    ));
                 },
                 Err(_) => {
                     return Err(RampError::AssetNotFound.into());
@@ -81,4 +164,4 @@ pub fn remove_assets(
             return Err(RampError::Unauthorized.into());
         }
     }
-}
\ No newline at end of file
+}
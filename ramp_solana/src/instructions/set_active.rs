@@ -12,6 +12,7 @@ pub struct SetActiveInstruction {
     pub is_active: bool,
 }
 
+crate::idl::idl_struct!(SetActiveInstruction { is_active: bool });
 
 pub fn set_active(
     _program_id: &Pubkey, 
@@ -21,10 +22,8 @@ pub fn set_active(
     let account_info_iter = &mut accounts.iter();
     let ramp_account = next_account_info(account_info_iter)?;
     let owner_account = next_account_info(account_info_iter)?;
-    let mut ramp_state: RampState  = {
-        let ramp_data = ramp_account.try_borrow_data()?;
-        borsh::from_slice(&ramp_data)?
-    };
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
     if owner_account.key != &ramp_state.owner {
         return Err(RampError::Unauthorized.into());
     }
@@ -32,13 +31,6 @@ pub fn set_active(
     match (owner, signer) {
         (true, true) => {
             ramp_state.set_active(args.is_active);
-            let mut ramp_data = ramp_account.try_borrow_mut_data()?;
-            ramp_data.fill(0);
-            let serialized_data = borsh::to_vec(&ramp_state).expect("Failed to serialize ramp state");
-            if serialized_data.len() > ramp_data.len() {
-                return Err(RampError::InvalidAccountState.into());
-            }
-            ramp_data[..serialized_data.len()].copy_from_slice(&serialized_data);
             msg!("Ramp account active state set to {}", args.is_active);
             Ok(())
         },
@@ -0,0 +1,37 @@
+use crate::{errors::RampError, state::RampState};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetAllowlistEnabledInstruction {
+    pub allowlist_enabled: bool,
+}
+
+crate::idl::idl_struct!(SetAllowlistEnabledInstruction { allowlist_enabled: bool });
+
+pub fn set_allowlist_enabled(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetAllowlistEnabledInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            ramp_state.allowlist_enabled = args.allowlist_enabled.into();
+            msg!("allowlist mode set to {}", args.allowlist_enabled);
+            Ok(())
+        },
+        (false, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        _ => {
+            return Err(RampError::InvalidSigner.into());
+        }
+    }
+}
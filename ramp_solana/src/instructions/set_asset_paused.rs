@@ -0,0 +1,64 @@
+use crate::{errors::RampError, models::RampAssetPaused, state::RampState};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetAssetPausedInstruction {
+    // the default pubkey targets native SOL
+    pub asset: Pubkey,
+    pub deposits_paused: bool,
+    pub withdrawals_paused: bool,
+}
+
+crate::idl::idl_struct!(SetAssetPausedInstruction {
+    asset: Pubkey,
+    deposits_paused: bool,
+    withdrawals_paused: bool,
+});
+
+pub fn set_asset_paused(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetAssetPausedInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            if args.asset == Pubkey::default() {
+                ramp_state.native_deposits_paused = args.deposits_paused.into();
+                ramp_state.native_withdrawals_paused = args.withdrawals_paused.into();
+            } else {
+                match ramp_state.get_asset_info(&args.asset) {
+                    Some(asset) => {
+                        asset.deposits_paused = args.deposits_paused.into();
+                        asset.withdrawals_paused = args.withdrawals_paused.into();
+                    },
+                    None => {
+                        return Err(RampError::AssetNotFound.into());
+                    }
+                }
+            }
+            msg!("RampAssetPaused:{}", general_purpose::STANDARD.encode(
+                borsh::to_vec(&RampAssetPaused {
+                    asset: args.asset,
+                    deposits_paused: args.deposits_paused,
+                    withdrawals_paused: args.withdrawals_paused,
+                }).unwrap()
+            ));
+            Ok(())
+        },
+        (false, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        _ => {
+            return Err(RampError::InvalidSigner.into());
+        }
+    }
+}
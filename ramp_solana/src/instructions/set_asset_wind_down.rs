@@ -0,0 +1,53 @@
+use crate::{errors::RampError, models::RampAssetWindDown, state::RampState};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetAssetWindDownInstruction {
+    pub asset: Pubkey,
+    pub winding_down: bool,
+}
+
+crate::idl::idl_struct!(SetAssetWindDownInstruction { asset: Pubkey, winding_down: bool });
+
+// Puts a mint into wind-down ahead of delisting. Deposits are rejected while withdrawals and
+// escrow release or reclaim keep working, so pending payouts can complete before RemoveAssets
+pub fn set_asset_wind_down(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetAssetWindDownInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            match ramp_state.get_asset_info(&args.asset) {
+                Some(asset) => {
+                    asset.winding_down = args.winding_down.into();
+                },
+                None => {
+                    return Err(RampError::AssetNotFound.into());
+                }
+            }
+            msg!("RampAssetWindDown:{}", general_purpose::STANDARD.encode(
+                borsh::to_vec(&RampAssetWindDown {
+                    asset: args.asset,
+                    winding_down: args.winding_down,
+                }).unwrap()
+            ));
+            Ok(())
+        },
+        (false, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        _ => {
+            return Err(RampError::InvalidSigner.into());
+        }
+    }
+}
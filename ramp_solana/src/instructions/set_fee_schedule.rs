@@ -0,0 +1,72 @@
+use crate::{
+    errors::RampError,
+    models::RampFeeScheduleChanged,
+    state::{FeeSchedule, FeeTier, RampState},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetFeeScheduleInstruction {
+    // the default pubkey targets native SOL
+    pub asset: Pubkey,
+    // ascending thresholds, an empty list falls back to the flat fee percentage
+    pub tiers: Vec<FeeTier>,
+    pub min_fee: Option<u64>,
+    pub max_fee: Option<u64>,
+}
+
+crate::idl::idl_struct!(SetFeeScheduleInstruction {
+    asset: Pubkey,
+    tiers: Vec<FeeTier>,
+    min_fee: Option<u64>,
+    max_fee: Option<u64>,
+});
+
+pub fn set_fee_schedule(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetFeeScheduleInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            let schedule = FeeSchedule::new(&args.tiers, args.min_fee, args.max_fee)
+                .map_err(|_| RampError::InvalidFeeSchedule)?;
+            if args.asset == Pubkey::default() {
+                ramp_state.native_fee_schedule = schedule;
+            } else {
+                match ramp_state.get_asset_info(&args.asset) {
+                    Some(asset) => {
+                        asset.fee_schedule = schedule;
+                    },
+                    None => {
+                        return Err(RampError::AssetNotFound.into());
+                    }
+                }
+            }
+            msg!("RampFeeScheduleChanged:{}", general_purpose::STANDARD.encode(
+                borsh::to_vec(&RampFeeScheduleChanged {
+                    asset: args.asset,
+                    tiers: args.tiers,
+                    min_fee: args.min_fee,
+                    max_fee: args.max_fee,
+                }).unwrap()
+            ));
+            Ok(())
+        },
+        (false, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        _ => {
+            return Err(RampError::InvalidSigner.into());
+        }
+    }
+}
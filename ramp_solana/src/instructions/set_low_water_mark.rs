@@ -0,0 +1,61 @@
+use crate::{
+    errors::RampError,
+    models::RampLowWaterMarkChanged,
+    state::RampState,
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetLowWaterMarkInstruction {
+    // the default pubkey targets native SOL
+    pub asset: Pubkey,
+    // 0 disables the RampLiquidityLow event
+    pub low_water_mark: u64,
+}
+
+crate::idl::idl_struct!(SetLowWaterMarkInstruction { asset: Pubkey, low_water_mark: u64 });
+
+pub fn set_low_water_mark(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetLowWaterMarkInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            if args.asset == Pubkey::default() {
+                ramp_state.native_low_water_mark = args.low_water_mark.into();
+            } else {
+                match ramp_state.get_asset_info(&args.asset) {
+                    Some(asset) => {
+                        asset.low_water_mark = args.low_water_mark.into();
+                    },
+                    None => {
+                        return Err(RampError::AssetNotFound.into());
+                    }
+                }
+            }
+            msg!("RampLowWaterMarkChanged:{}", general_purpose::STANDARD.encode(
+                borsh::to_vec(&RampLowWaterMarkChanged {
+                    asset: args.asset,
+                    low_water_mark: args.low_water_mark,
+                }).unwrap()
+            ));
+            Ok(())
+        },
+        (false, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        _ => {
+            return Err(RampError::InvalidSigner.into());
+        }
+    }
+}
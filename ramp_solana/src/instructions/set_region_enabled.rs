@@ -0,0 +1,64 @@
+use crate::{errors::RampError, models::{Medium, Region}, state::RampState};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+// Toggles a whole region when `medium` is None, otherwise only that medium
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetRegionEnabledInstruction {
+    pub region: Region,
+    pub medium: Option<Medium>,
+    pub is_enabled: bool,
+}
+
+crate::idl::idl_struct!(SetRegionEnabledInstruction {
+    region: Region,
+    medium: Option<Medium>,
+    is_enabled: bool,
+});
+
+pub fn set_region_enabled(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetRegionEnabledInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            match ramp_state.get_region_entry(&args.region) {
+                Some(region_entry) => {
+                    match args.medium {
+                        Some(medium) => {
+                            if region_entry.set_medium_enabled(&medium, args.is_enabled).is_err() {
+                                return Err(RampError::MediumNotFound.into());
+                            }
+                        },
+                        None => {
+                            region_entry.is_enabled = args.is_enabled.into();
+                        }
+                    }
+                },
+                None => {
+                    return Err(RampError::RegionNotFound.into());
+                }
+            }
+            msg!("Region {:?} medium {:?} enabled set to {}", args.region, args.medium, args.is_enabled);
+            Ok(())
+        },
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+}
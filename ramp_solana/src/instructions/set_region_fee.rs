@@ -0,0 +1,58 @@
+use crate::{errors::RampError, models::Region, state::RampState};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+// Overrides the asset/native fee for deposits into `region`, None clears the override
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetRegionFeeInstruction {
+    pub region: Region,
+    pub fee_percentage: Option<u128>,
+}
+
+crate::idl::idl_struct!(SetRegionFeeInstruction { region: Region, fee_percentage: Option<u128> });
+
+pub fn set_region_fee(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetRegionFeeInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer, fee) = (
+        owner_account.key == &ramp_state.owner,
+        owner_account.is_signer,
+        args.fee_percentage.map(|fee| fee <= 100).unwrap_or(true)
+    );
+    match (owner, signer, fee) {
+        (true, true, true) => {
+            match ramp_state.get_region_entry(&args.region) {
+                Some(region_entry) => {
+                    region_entry.has_fee_override = args.fee_percentage.is_some().into();
+                    region_entry.fee_percentage = args.fee_percentage.unwrap_or(0).into();
+                },
+                None => {
+                    return Err(RampError::RegionNotFound.into());
+                }
+            }
+            msg!("Region fee set to {:?} for {:?}", args.fee_percentage, args.region);
+            Ok(())
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        (false, _, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        (true, true, false) => {
+            return Err(RampError::InvalidFeePercentage.into());
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    errors::RampError,
+    models::RampRevenueSplitChanged,
+    state::{Beneficiary, RampState, RevenueSplit},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetRevenueSplitInstruction {
+    // weights must sum to 10_000 bps, an empty list sends all revenue to the vault again
+    pub beneficiaries: Vec<Beneficiary>,
+}
+
+crate::idl::idl_struct!(SetRevenueSplitInstruction { beneficiaries: Vec<Beneficiary> });
+
+pub fn set_revenue_split(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetRevenueSplitInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            ramp_state.revenue_split = RevenueSplit::new(&args.beneficiaries)
+                .map_err(|_| RampError::InvalidRevenueSplit)?;
+            msg!("RampRevenueSplitChanged:{}", general_purpose::STANDARD.encode(
+                borsh::to_vec(&RampRevenueSplitChanged {
+                    beneficiaries: args.beneficiaries,
+                }).unwrap()
+            ));
+            Ok(())
+        },
+        (false, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        _ => {
+            return Err(RampError::InvalidSigner.into());
+        }
+    }
+}
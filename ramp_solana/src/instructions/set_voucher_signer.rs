@@ -0,0 +1,39 @@
+use crate::{errors::RampError, state::RampState};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetVoucherSignerInstruction {
+    // the default pubkey disables voucher withdrawals
+    pub voucher_signer: Pubkey,
+}
+
+crate::idl::idl_struct!(SetVoucherSignerInstruction { voucher_signer: Pubkey });
+
+pub fn set_voucher_signer(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetVoucherSignerInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {
+            let old_signer = ramp_state.voucher_signer;
+            ramp_state.voucher_signer = args.voucher_signer;
+            msg!("voucher signer changed from {} to {}", old_signer, args.voucher_signer);
+            Ok(())
+        },
+        (false, _) => {
+            return Err(RampError::Unauthorized.into());
+        }
+        _ => {
+            return Err(RampError::InvalidSigner.into());
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    errors::RampError,
+    models::RampAccessListUpdate,
+    state::{AccessEntry, AccessStatus, RampState, ACCESS_SEED},
+    utils::create_pda_account,
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+// Sized so a full sync chunk fits in a legacy transaction
+pub const MAX_ACCESS_LIST_UPDATE: usize = 20;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct UpdateAccessListInstruction {
+    // None removes the addresses from both lists
+    pub status: Option<AccessStatus>,
+    pub addresses: Vec<Pubkey>,
+}
+
+crate::idl::idl_struct!(UpdateAccessListInstruction { status: Option<AccessStatus>, addresses: Vec<Pubkey> });
+
+// Adds, moves or removes addresses on the deny/allow lists. One access entry PDA per address
+// follows the fixed accounts, in the same order as `addresses`
+pub fn update_access_list(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: UpdateAccessListInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let access_accounts = account_info_iter.as_slice();
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let (owner, signer) = (owner_account.key == &ramp_state.owner, owner_account.is_signer);
+    match (owner, signer) {
+        (true, true) => {},
+        (true, false) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    if args.addresses.is_empty() || args.addresses.len() != access_accounts.len() {
+        return Err(RampError::InvalidBatch.into());
+    }
+    if args.addresses.len() > MAX_ACCESS_LIST_UPDATE {
+        return Err(RampError::BatchTooLarge.into());
+    }
+
+    for (address, access_account) in args.addresses.iter().zip(access_accounts) {
+        let (access_address, access_bump) = AccessEntry::find_address(program_id, ramp_account.key, address);
+        if access_address != *access_account.key {
+            return Err(RampError::InvalidAccessEntry.into());
+        }
+        let exists = access_account.owner == program_id && access_account.data_len() > 0;
+        match args.status {
+            Some(status) => {
+                if !exists {
+                    create_pda_account(
+                        program_id,
+                        owner_account,
+                        access_account,
+                        system_program,
+                        AccessEntry::space(),
+                        &[ACCESS_SEED, ramp_account.key.as_ref(), address.as_ref(), &[access_bump]],
+                    )?;
+                }
+                let mut access_data = access_account.try_borrow_mut_data()?;
+                AccessEntry { address: *address, status }.serialize(&mut access_data.as_mut())?;
+            },
+            None => {
+                if !exists {
+                    continue;
+                }
+                // closing returns the rent to the owner
+                let lamports = access_account.lamports();
+                **access_account.try_borrow_mut_lamports()? -= lamports;
+                **owner_account.try_borrow_mut_lamports()? += lamports;
+                access_account.resize(0)?;
+                access_account.assign(system_program.key);
+            }
+        }
+    }
+
+    msg!("RampAccessListUpdate:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampAccessListUpdate {
+            status: args.status,
+            addresses: args.addresses,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::{
+    errors::RampError,
+    models::{RampVoucherWithdraw, WithdrawVoucher},
+    state::RampState,
+    utils::{
+        check_address_access, check_low_water_mark, consume_voucher_nonce, mint_decimals, token_account_owner,
+        token_liquidity, validate_voucher, verify_voucher_signature,
+    },
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    pubkey::Pubkey,
+};
+use spl_token_2022_interface::instruction as token_instruction;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct VoucherWithdrawInstruction {
+    pub voucher: WithdrawVoucher,
+}
+
+crate::idl::idl_struct!(VoucherWithdrawInstruction { voucher: WithdrawVoucher });
+
+// Pays out a voucher signed by the ramp's voucher signer, the transaction can be sent by anyone
+// as long as the preceding instruction is the matching ed25519 signature verification
+pub fn voucher_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: VoucherWithdrawInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let recipient_token_account = next_account_info(account_info_iter)?;
+    let ramp_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let nonce_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    if !ramp_state.is_active.get() {
+        return Err(RampError::ProgramNotActive.into());
+    }
+    if ramp_state.is_withdrawal_paused(asset_mint_account.key) {
+        return Err(RampError::AssetWithdrawalsPaused.into());
+    }
+    let voucher = args.voucher;
+    validate_voucher(ramp_account, ramp_state, &voucher)?;
+    if voucher.asset != *asset_mint_account.key || voucher.recipient != *recipient_token_account.key {
+        return Err(RampError::InvalidVoucher.into());
+    }
+    let recipient = token_account_owner(recipient_token_account)?;
+    check_address_access(program_id, ramp_account, ramp_state, access_account, &recipient)?;
+    verify_voucher_signature(
+        instructions_sysvar,
+        &ramp_state.voucher_signer,
+        &borsh::to_vec(&voucher)?,
+    )?;
+    consume_voucher_nonce(
+        program_id,
+        ramp_account,
+        payer_account,
+        nonce_account,
+        system_program,
+        voucher.nonce,
+    )?;
+
+    let revenue = match ramp_state.get_asset_info_ref(asset_mint_account.key) {
+        Some(asset) => asset.asset_revenue.get(),
+        None => {
+            return Err(RampError::AssetNotFound.into());
+        }
+    };
+    // revenue stays in the ramp until it is withdrawn to the vault
+    let available = token_liquidity(ramp_account, asset_mint_account, ramp_token_account, token_program, revenue)?;
+    if available < voucher.amount {
+        return Err(RampError::InsufficientFunds.into());
+    }
+
+    let transfer_instructions = token_instruction::transfer(
+        token_program.key,
+        ramp_token_account.key,
+        recipient_token_account.key,
+        ramp_account.key,
+        &[ramp_account.key],
+        voucher.amount,
+    )?;
+    let bump = [ramp_state.bump];
+    let transfer_result = invoke_signed(
+        &transfer_instructions,
+        &[
+            ramp_token_account.clone(),
+            recipient_token_account.clone(),
+            ramp_account.clone(),
+            token_program.clone(),
+        ],
+        &[&[b"ramp", ramp_state.creator.as_ref(), &bump]],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+
+    let decimals = mint_decimals(asset_mint_account)?;
+    check_low_water_mark(ramp_state, &voucher.asset, decimals, available, available - voucher.amount);
+    msg!("RampVoucherWithdraw:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampVoucherWithdraw {
+            asset: voucher.asset,
+            decimals,
+            recipient: voucher.recipient,
+            amount: voucher.amount,
+            nonce: voucher.nonce,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::{
+    errors::RampError,
+    instructions::VoucherWithdrawInstruction,
+    models::RampVoucherWithdraw,
+    state::{NATIVE_DECIMALS, RampState},
+    utils::{
+        check_address_access, check_low_water_mark, consume_voucher_nonce, pay_from_sol_vault,
+        sol_vault_liquidity, validate_voucher, verify_voucher_signature,
+    },
+};
+use base64::{engine::general_purpose, Engine as _};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn voucher_withdraw_native(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: VoucherWithdrawInstruction
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let nonce_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+    let access_account = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    if !ramp_state.is_active.get() {
+        return Err(RampError::ProgramNotActive.into());
+    }
+    if ramp_state.is_withdrawal_paused(&Pubkey::default()) {
+        return Err(RampError::AssetWithdrawalsPaused.into());
+    }
+    let voucher = args.voucher;
+    validate_voucher(ramp_account, ramp_state, &voucher)?;
+    if voucher.asset != Pubkey::default() || voucher.recipient != *recipient_account.key {
+        return Err(RampError::InvalidVoucher.into());
+    }
+    check_address_access(program_id, ramp_account, ramp_state, access_account, recipient_account.key)?;
+    verify_voucher_signature(
+        instructions_sysvar,
+        &ramp_state.voucher_signer,
+        &borsh::to_vec(&voucher)?,
+    )?;
+    consume_voucher_nonce(
+        program_id,
+        ramp_account,
+        payer_account,
+        nonce_account,
+        system_program,
+        voucher.nonce,
+    )?;
+
+    let available = sol_vault_liquidity(program_id, ramp_account, ramp_state, sol_vault_account)?;
+    pay_from_sol_vault(
+        program_id,
+        ramp_account,
+        ramp_state,
+        sol_vault_account,
+        recipient_account,
+        voucher.amount,
+    )?;
+    check_low_water_mark(ramp_state, &Pubkey::default(), NATIVE_DECIMALS, available, available - voucher.amount);
+
+    msg!("RampVoucherWithdraw:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampVoucherWithdraw {
+            asset: Pubkey::default(),
+            decimals: NATIVE_DECIMALS,
+            recipient: voucher.recipient,
+            amount: voucher.amount,
+            nonce: voucher.nonce,
+        }).unwrap()
+    ));
+    Ok(())
+}
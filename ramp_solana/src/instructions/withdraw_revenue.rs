@@ -0,0 +1,97 @@
+use crate::{
+    errors::RampError,
+    models::RampRevenueWithdrawn,
+    state::RampState,
+    utils::{distribute_token_revenue, mint_decimals},
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct WithdrawRevenueInstruction {}
+
+crate::idl::idl_struct!(WithdrawRevenueInstruction {});
+
+// Pays out the accumulated fee revenue of one mint following the revenue split. Beneficiary token
+// accounts follow the fixed accounts in split order, or the vault's token account when no split is set
+pub fn withdraw_revenue(_program_id: &Pubkey, accounts: &[AccountInfo], _args: WithdrawRevenueInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let asset_mint_account = next_account_info(account_info_iter)?;
+    let ramp_owner = next_account_info(account_info_iter)?;
+    let ramp_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let beneficiary_accounts = account_info_iter.as_slice();
+
+    let ramp_data = ramp_account.try_borrow_data()?;
+    let ramp_state = RampState::load(&ramp_data)?;
+    let (owner, signer, status) = (
+        ramp_owner.key == &ramp_state.owner,
+        ramp_owner.is_signer,
+        ramp_state.is_active.get()
+    );
+    match (owner, signer, status) {
+        (true, true, true) => {},
+        (true, true, false) => {
+            return Err(RampError::ProgramNotActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    let revenue = match ramp_state.get_asset_info_ref(asset_mint_account.key) {
+        Some(asset) => asset.asset_revenue.get(),
+        None => {
+            return Err(RampError::AssetNotFound.into());
+        }
+    };
+    if revenue == 0 {
+        return Err(RampError::NoRevenue.into());
+    }
+    let revenue = u64::try_from(revenue).map_err(|_| RampError::InvalidAccountState)?;
+    let ramp_token_address = get_associated_token_address_with_program_id(
+        ramp_account.key,
+        asset_mint_account.key,
+        token_program.key,
+    );
+    if ramp_token_address != *ramp_token_account.key {
+        return Err(RampError::InvalidAccountState.into());
+    }
+
+    let shares = distribute_token_revenue(
+        ramp_account,
+        ramp_state,
+        asset_mint_account,
+        ramp_token_account,
+        token_program,
+        beneficiary_accounts,
+        revenue,
+    )?;
+    drop(ramp_data);
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    if let Some(asset) = ramp_state.get_asset_info(asset_mint_account.key) {
+        asset.asset_revenue = 0.into();
+    }
+
+    msg!("RampRevenueWithdrawn:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampRevenueWithdrawn {
+            asset: *asset_mint_account.key,
+            decimals: mint_decimals(asset_mint_account)?,
+            amount: revenue,
+            shares,
+        }).unwrap()
+    ));
+    Ok(())
+}
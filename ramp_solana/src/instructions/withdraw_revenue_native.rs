@@ -0,0 +1,84 @@
+use crate::{
+    errors::RampError,
+    models::{RampRevenueWithdrawn, RevenueShare},
+    state::{split_amount, RampState, NATIVE_DECIMALS},
+    utils::pay_from_sol_vault,
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct WithdrawRevenueNativeInstruction {}
+
+crate::idl::idl_struct!(WithdrawRevenueNativeInstruction {});
+
+// Pays out the accumulated native fee revenue from the SOL vault following the revenue split.
+// Beneficiary wallets follow the fixed accounts in split order, or the vault address when no split is set
+pub fn withdraw_revenue_native(program_id: &Pubkey, accounts: &[AccountInfo], _args: WithdrawRevenueNativeInstruction) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let ramp_account = next_account_info(account_info_iter)?;
+    let ramp_owner = next_account_info(account_info_iter)?;
+    let sol_vault_account = next_account_info(account_info_iter)?;
+    let beneficiary_accounts = account_info_iter.as_slice();
+
+    let mut ramp_data = ramp_account.try_borrow_mut_data()?;
+    let ramp_state = RampState::load_mut(&mut ramp_data)?;
+    let (owner, signer, status) = (
+        ramp_owner.key == &ramp_state.owner,
+        ramp_owner.is_signer,
+        ramp_state.is_active.get()
+    );
+    match (owner, signer, status) {
+        (true, true, true) => {},
+        (true, true, false) => {
+            return Err(RampError::ProgramNotActive.into());
+        },
+        (true, false, _) => {
+            return Err(RampError::InvalidSigner.into());
+        },
+        _ => {
+            return Err(RampError::Unauthorized.into());
+        }
+    }
+    if ramp_state.native_revenue.get() == 0 {
+        return Err(RampError::NoRevenue.into());
+    }
+    let revenue = u64::try_from(ramp_state.native_revenue.get()).map_err(|_| RampError::InvalidAccountState)?;
+    let beneficiaries = ramp_state.revenue_split.recipients(ramp_state.vault_address);
+    if beneficiaries.len() != beneficiary_accounts.len() {
+        return Err(RampError::InvalidRevenueSplit.into());
+    }
+    if beneficiaries.iter().zip(beneficiary_accounts).any(|(beneficiary, account)| beneficiary.address != *account.key) {
+        return Err(RampError::InvalidRecipient.into());
+    }
+
+    // the revenue is released first so the vault pays it out like regular liquidity
+    ramp_state.reduce_native_revenue(revenue as u128);
+    let amounts = split_amount(&beneficiaries, revenue);
+    let mut shares = Vec::with_capacity(beneficiaries.len());
+    for ((beneficiary, beneficiary_account), amount) in beneficiaries.iter().zip(beneficiary_accounts).zip(amounts) {
+        if amount > 0 {
+            pay_from_sol_vault(program_id, ramp_account, ramp_state, sol_vault_account, beneficiary_account, amount)?;
+        }
+        shares.push(RevenueShare {
+            beneficiary: beneficiary.address,
+            amount,
+        });
+    }
+
+    msg!("RampRevenueWithdrawn:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampRevenueWithdrawn {
+            asset: Pubkey::default(),
+            decimals: NATIVE_DECIMALS,
+            amount: revenue,
+            shares,
+        }).unwrap()
+    ));
+    Ok(())
+}
@@ -3,6 +3,9 @@ pub mod errors;
 pub mod instructions;
 pub mod processors;
 pub mod models;
+pub mod utils;
+pub mod pod;
+pub mod idl;
 
 
  use solana_program::{
@@ -32,6 +35,7 @@ mod test {
     //use solana_client::nonblocking::rpc_client::RpcClient;
     //use solana_commitment_config::CommitmentConfig;
     use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_program::sysvar;
     use solana_sdk::{
         account::Account, message::{
             AccountMeta,
@@ -61,17 +65,46 @@ mod test {
     use crate::{
         errors::RampError, instructions::{
             AddAssetsInstruction,
+            AddRegionInstruction,
+            AddRegionMediumInstruction,
+            BatchOnRampWithdrawInstruction,
+            ClaimCreditInstruction,
+            CloseRampInstruction,
+            DefundAssetInstruction,
+            EscrowDepositInstruction,
+            FundAssetInstruction,
+            GetAvailableLiquidityInstruction,
             InitializeProgramInstruction,
+            MigrateRampInstruction,
             OffRampDepositInstruction,
             OffRampDepositNativeInstruction,
             OnRampWithdrawInstruction,
             OnRampWithdrawNativeInstruction,
+            RefundDepositInstruction,
             RemoveAssetsInstruction,
             SetActiveInstruction,
             SetAssetFeeInstruction,
+            SetAssetPausedInstruction,
+            SetAssetWindDownInstruction,
+            SetFeeScheduleInstruction,
+            QuoteFeeInstruction,
             SetNativeFeePercentageInstruction,
-            SetOwnerInstruction
-        }, models::{Medium, Region}, processors
+            SetOwnerInstruction,
+            SetRegionEnabledInstruction,
+            SetRegionFeeInstruction,
+            SettleEscrowInstruction,
+            SetAllowlistEnabledInstruction,
+            SetLowWaterMarkInstruction,
+            SetRevenueSplitInstruction,
+            SetVoucherSignerInstruction,
+            UpdateAccessListInstruction,
+            VoucherWithdrawInstruction,
+            WithdrawRevenueInstruction,
+            WithdrawRevenueNativeInstruction,
+            MAX_BATCH_PAYOUTS
+        }, models::{BatchPayout, Medium, Region, WithdrawVoucher}, processors,
+        state::{find_nonce_address, find_sol_vault_address, AccessEntry, AccessStatus, Beneficiary, CreditEntry, DepositReceipt, FeeTier, RampState, ReceiptStatus},
+        utils::{find_metadata_address, metadata_program_id},
         //state::RampState
     };
     use mollusk_svm::{result::Check, Mollusk, program::keyed_account_for_system_program as mollusk_system_program};
@@ -228,7 +261,7 @@ mod test {
             ramp_program_id,
             &processors::Instruction::SetActive(set_active_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
             ],
         );
@@ -794,7 +827,7 @@ mod test {
             ramp_program_id,
             &processors::Instruction::SetAssetFee(set_asset_fee_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
             ],
         );
@@ -901,7 +934,7 @@ mod test {
             ramp_program_id,
             &processors::Instruction::InitializeProgram(initialize_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
                 AccountMeta::new_readonly(system_program.0, false),
             ],
@@ -936,13 +969,33 @@ mod test {
             &instruction,
             &[Check::success()]
         );
+        let wind_down_instruction = |signer: Pubkey| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetAssetWindDown(SetAssetWindDownInstruction {
+                asset: mint.0,
+                winding_down: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new_readonly(signer, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &wind_down_instruction(Pubkey::new_unique()),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &wind_down_instruction(payer.0),
+            &[Check::success()]
+        );
+
         let remove_asset_instruction = RemoveAssetsInstruction {};
 
         let instruction = Instruction::new_with_borsh(
             ramp_program_id,
             &processors::Instruction::RemoveAssets(remove_asset_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(ramp_token_account.0, true),
                 AccountMeta::new(mint.0, false),
                 AccountMeta::new(payer.0, true),
@@ -951,9 +1004,13 @@ mod test {
             ],
         );
 
+        // without revenue no beneficiary accounts are needed, and the emptied token account is closed
         client.process_and_validate_instruction(
             &instruction,
-            &[Check::success()]
+            &[
+                Check::success(),
+                Check::account(&ramp_token_account.0).lamports(0).build(),
+            ]
         );
     }
 
@@ -1133,6 +1190,28 @@ mod test {
                 rent_epoch: 0
             }
         );
+        // Metaplex MetadataV1 layout with the fixed width name, symbol and uri padding
+        let mut metaplex_data = borsh::to_vec(&(
+            4u8,
+            payer.0,
+            mint.0,
+            format!("{:\0<32}", "Tether USD"),
+            format!("{:\0<10}", "USDT"),
+            format!("{:\0<200}", "https://example.com/image.json"),
+            0u16,
+        )).unwrap();
+        // creators, primary sale, mutable, edition nonce, token standard, collection, uses,
+        // collection details, programmable config
+        metaplex_data.extend_from_slice(&[0, 0, 1, 0, 0, 0, 0, 0, 0]);
+        let metaplex_account = (find_metadata_address(&mint.0).0,
+            Account {
+                lamports: 1000000000,
+                data: metaplex_data,
+                owner: metadata_program_id(),
+                executable: false,
+                rent_epoch: 0
+            }
+        );
         let token_program = token::keyed_account();
 
         let system_program = mollusk_system_program();
@@ -1169,8 +1248,9 @@ mod test {
         //ramp token account
         accounts.insert(ramp_token_account.0, ramp_token_account.1);
 
-        //metadata account
-        //accounts.insert(metadata_account.0, metadata_account.1);
+        //metadata accounts
+        accounts.insert(metadata_account.0, metadata_account.1);
+        accounts.insert(metaplex_account.0, metaplex_account.1);
 
         let initialize_instruction = InitializeProgramInstruction {
             bump: ramp_pda.1,
@@ -1225,7 +1305,7 @@ mod test {
             ramp_program_id,
             &processors::Instruction::SetActive(set_active_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
             ],
         );
@@ -1234,16 +1314,31 @@ mod test {
             &[Check::success()]
         );
 
-        let off_ramp_deposit_instruction = OffRampDepositInstruction {
-            amount: 100000000,
-            region: Region::KEN,
-            medium: Medium::Primary,
-            data: vec![]
-        };
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA"), Medium::new("AIRTEL")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
 
-        let instruction = Instruction::new_with_borsh(
+        let deposit_instruction = |metadata: Pubkey| Instruction::new_with_borsh(
             ramp_program_id,
-            &processors::Instruction::OffRampDeposit(off_ramp_deposit_instruction),
+            &processors::Instruction::OffRampDeposit(OffRampDepositInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference: [1u8; 32]
+            }),
             vec![
                 AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(mint.0, false),
@@ -1251,12 +1346,21 @@ mod test {
                 AccountMeta::new(mint_vault.0, false),
                 AccountMeta::new(ramp_token_account.0, false),
                 AccountMeta::new_readonly(token::ID, false),
-
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(system_program.0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new_readonly(metadata, false),
             ],
         );
 
+        // the metadata account has to be the Metaplex PDA of the mint
         client.process_and_validate_instruction(
-            &instruction,
+            &deposit_instruction(metadata_account.0),
+            &[Check::err(RampError::InvalidMetadataAccount.into())]
+        );
+
+        client.process_and_validate_instruction(
+            &deposit_instruction(metaplex_account.0),
             &[Check::success()]
         );
     }
@@ -1308,7 +1412,7 @@ mod test {
             ramp_program_id,
             &processors::Instruction::SetActive(set_active_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
             ],
         );
@@ -1317,11 +1421,28 @@ mod test {
             &[Check::success()]
         );
 
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA"), Medium::new("AIRTEL")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
         let off_ramp_deposit_instruction = OffRampDepositNativeInstruction {
             amount: 100000000,
-            region: Region::KEN,
-            medium: Medium::Primary,
-            data: vec![]
+            region: Region::new("KEN"),
+            medium: Medium::new("MPESA"),
+            data: vec![],
+            reference: [1u8; 32]
         };
         let instruction = Instruction::new_with_borsh(
             ramp_program_id,
@@ -1330,6 +1451,9 @@ mod test {
                 AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
                 AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
             ],
         );
         client.process_and_validate_instruction(
@@ -1496,7 +1620,7 @@ mod test {
             ramp_program_id,
             &processors::Instruction::SetActive(set_active_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
             ],
         );
@@ -1505,11 +1629,28 @@ mod test {
             &[Check::success()]
         );
 
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA"), Medium::new("AIRTEL")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
         let off_ramp_deposit_instruction = OffRampDepositInstruction {
             amount: 100000000,
-            region: Region::KEN,
-            medium: Medium::Primary,
-            data: vec![]
+            region: Region::new("KEN"),
+            medium: Medium::new("MPESA"),
+            data: vec![],
+            reference: [1u8; 32]
         };
 
         let instruction = Instruction::new_with_borsh(
@@ -1521,9 +1662,10 @@ mod test {
                 AccountMeta::new(payer.0, true),
                 AccountMeta::new(mint_vault.0, false),
                 AccountMeta::new(ramp_token_account.0, false),
-                AccountMeta::new(metadata_account.0, false),
                 AccountMeta::new_readonly(token::ID, false),
-
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(system_program.0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
             ],
         );
 
@@ -1539,13 +1681,13 @@ mod test {
             ramp_program_id,
             &processors::Instruction::OnRampWithdraw(onramp_withdraw_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(mint.0, false),
                 AccountMeta::new(payer.0, true),
                 AccountMeta::new(mint_vault.0, false),
                 AccountMeta::new(ramp_token_account.0, false),
                 AccountMeta::new_readonly(token::ID, false),
-
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
             ],
         );
 
@@ -1602,7 +1744,7 @@ mod test {
             ramp_program_id,
             &processors::Instruction::SetActive(set_active_instruction),
             vec![
-                AccountMeta::new(ramp_account.0, true),
+                AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
             ],
         );
@@ -1611,41 +1753,2784 @@ mod test {
             &[Check::success()]
         );
 
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA"), Medium::new("AIRTEL")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
         let off_ramp_deposit_instruction = OffRampDepositNativeInstruction {
             amount: 100000000,
-            region: Region::KEN,
-            medium: Medium::Primary,
-            data: vec![]
+            region: Region::new("KEN"),
+            medium: Medium::new("MPESA"),
+            data: vec![],
+            reference: [1u8; 32]
         };
         let instruction = Instruction::new_with_borsh(
             ramp_program_id,
             &processors::Instruction::OffRampDepositNative(off_ramp_deposit_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        // the deposit lands in the SOL vault, the state account keeps only its rent
+        let sol_vault = find_sol_vault_address(&ramp_program_id, &ramp_account.0).0;
+        let vault_rent = client.mollusk.sysvars.rent.minimum_balance(0);
+        let ramp_lamports = client.account_store.borrow().get(&ramp_account.0).unwrap().lamports;
+        assert_eq!(client.account_store.borrow().get(&sol_vault).unwrap().lamports, vault_rent + 100000000);
+
+        let recipient = Pubkey::new_unique();
+        let withdraw_instruction = |signer: Pubkey, amount: u64| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OnRampWithdrawNative(OnRampWithdrawNativeInstruction {
+                amount,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new(signer, true),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient).0, false),
+                AccountMeta::new(sol_vault, false),
+            ],
+        );
+
+        // only the owner can pay out
+        client.process_and_validate_instruction(
+            &withdraw_instruction(Pubkey::new_unique(), 1000),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+
+        // the 10% fee stays in the vault as native revenue
+        client.process_and_validate_instruction(
+            &withdraw_instruction(payer.0, 90000001),
+            &[Check::err(RampError::InsufficientFunds.into())]
+        );
+        client.process_and_validate_instruction(
+            &withdraw_instruction(payer.0, 90000000),
+            &[
+                Check::success(),
+                Check::account(&recipient).lamports(90000000).build(),
+                Check::account(&sol_vault).lamports(vault_rent + 10000000).build(),
+                Check::account(&ramp_account.0).lamports(ramp_lamports).build(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_available_liquidity_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
             vec![
                 AccountMeta::new(ramp_account.0, false),
                 AccountMeta::new(payer.0, true),
                 AccountMeta::new_readonly(system_program::id(), false),
             ],
         );
+
         client.process_and_validate_instruction(
             &instruction,
             &[Check::success()]
         );
 
-        let onramp_withdraw_native_instruction = OnRampWithdrawNativeInstruction {
+        let set_active_instruction = SetActiveInstruction {
+            is_active: true,
+        };
+        let active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(set_active_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA"), Medium::new("AIRTEL")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        let off_ramp_deposit_instruction = OffRampDepositNativeInstruction {
             amount: 100000000,
+            region: Region::new("KEN"),
+            medium: Medium::new("MPESA"),
+            data: vec![],
+            reference: [1u8; 32]
         };
         let instruction = Instruction::new_with_borsh(
             ramp_program_id,
-            &processors::Instruction::OnRampWithdrawNative(onramp_withdraw_native_instruction),
+            &processors::Instruction::OffRampDepositNative(off_ramp_deposit_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let sol_vault = find_sol_vault_address(&ramp_program_id, &ramp_account.0).0;
+
+        let recipient = Pubkey::new_unique();
+        let withdraw_instruction = |signer: Pubkey, amount: u64| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OnRampWithdrawNative(OnRampWithdrawNativeInstruction {
+                amount,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new(signer, true),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient).0, false),
+                AccountMeta::new(sol_vault, false),
+            ],
+        );
+
+        let liquidity_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::GetAvailableLiquidity(GetAvailableLiquidityInstruction {
+                asset: Pubkey::default(),
+            }),
+            vec![
+                AccountMeta::new_readonly(ramp_account.0, false),
+                AccountMeta::new_readonly(sol_vault, false),
+            ],
+        );
+        let low_water_mark_instruction = |signer: Pubkey, asset: Pubkey| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetLowWaterMark(SetLowWaterMarkInstruction {
+                asset,
+                low_water_mark: 50000000,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new_readonly(signer, true),
+            ],
+        );
+
+        // the 10% fee is revenue, not liquidity
+        client.process_and_validate_instruction(
+            &liquidity_instruction,
+            &[Check::success(), Check::return_data(&90000000u64.to_le_bytes())]
+        );
+
+        client.process_and_validate_instruction(
+            &low_water_mark_instruction(recipient, Pubkey::default()),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &low_water_mark_instruction(payer.0, Pubkey::new_unique()),
+            &[Check::err(RampError::AssetNotFound.into())]
+        );
+        client.process_and_validate_instruction(
+            &low_water_mark_instruction(payer.0, Pubkey::default()),
+            &[Check::success()]
+        );
+
+        // crossing the low-water mark only logs, the withdrawal still goes through
+        client.process_and_validate_instruction(
+            &withdraw_instruction(payer.0, 60000000),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &liquidity_instruction,
+            &[Check::success(), Check::return_data(&30000000u64.to_le_bytes())]
+        );
+    }
+
+    #[test]
+    fn test_fund_defund_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let sol_vault = find_sol_vault_address(&ramp_program_id, &ramp_account.0).0;
+        let treasury = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(treasury, Account::new(10000000000, 0, &system_program.0));
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
             vec![
                 AccountMeta::new(ramp_account.0, false),
-                AccountMeta::new(Pubkey::new_unique(), false),
                 AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
             ],
         );
         client.process_and_validate_instruction(
             &instruction,
             &[Check::success()]
         );
+
+        let active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction,
+            &[Check::success()]
+        );
+
+        let fund_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::FundAssetNative(FundAssetInstruction {
+                amount: 500000000,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(treasury, true),
+                AccountMeta::new(sol_vault, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        let defund_instruction = |signer: Pubkey, amount: u64| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::DefundAssetNative(DefundAssetInstruction {
+                amount,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new_readonly(signer, true),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new(sol_vault, false),
+            ],
+        );
+        let liquidity_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::GetAvailableLiquidity(GetAvailableLiquidityInstruction {
+                asset: Pubkey::default(),
+            }),
+            vec![
+                AccountMeta::new_readonly(ramp_account.0, false),
+                AccountMeta::new_readonly(sol_vault, false),
+            ],
+        );
+
+        // any funder can top up the vault
+        client.process_and_validate_instruction(
+            &fund_instruction,
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &liquidity_instruction,
+            &[Check::success(), Check::return_data(&500000000u64.to_le_bytes())]
+        );
+
+        // only the owner drains, and never more than the available liquidity
+        client.process_and_validate_instruction(
+            &defund_instruction(treasury, 200000000),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &defund_instruction(payer.0, 500000001),
+            &[Check::err(RampError::InsufficientFunds.into())]
+        );
+        client.process_and_validate_instruction(
+            &defund_instruction(payer.0, 0),
+            &[Check::err(RampError::InvalidAmount.into())]
+        );
+        client.process_and_validate_instruction(
+            &defund_instruction(payer.0, 200000000),
+            &[
+                Check::success(),
+                Check::account(&recipient).lamports(200000000).build(),
+            ]
+        );
+        client.process_and_validate_instruction(
+            &liquidity_instruction,
+            &[Check::success(), Check::return_data(&300000000u64.to_le_bytes())]
+        );
+    }
+
+    #[test]
+    fn test_close_ramp_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let sol_vault = find_sol_vault_address(&ramp_program_id, &ramp_account.0).0;
+        let vault_address = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+        let vault_rent = mollusk.sysvars.rent.minimum_balance(0);
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(treasury, Account::new(10000000000, 0, &system_program.0));
+
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(InitializeProgramInstruction {
+                bump: ramp_pda.1,
+                vault_address,
+                native_fee_percentage: 10,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let active_instruction = |is_active: bool| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction(true),
+            &[Check::success()]
+        );
+
+        let fund_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::FundAssetNative(FundAssetInstruction {
+                amount: 500000000,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(treasury, true),
+                AccountMeta::new(sol_vault, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &fund_instruction,
+            &[Check::success()]
+        );
+
+        let close_instruction = |signer: Pubkey, vault: Pubkey| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::CloseRamp(CloseRampInstruction {}),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(signer, true),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new(sol_vault, false),
+                AccountMeta::new(vault, false),
+            ],
+        );
+
+        // an active ramp cannot be closed, and only by the owner
+        client.process_and_validate_instruction(
+            &close_instruction(payer.0, vault_address),
+            &[Check::err(RampError::RampStillActive.into())]
+        );
+        client.process_and_validate_instruction(
+            &active_instruction(false),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &close_instruction(treasury, vault_address),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &close_instruction(payer.0, Pubkey::new_unique()),
+            &[Check::err(RampError::InvalidRecipient.into())]
+        );
+
+        let ramp_lamports = client.account_store.borrow().get(&ramp_account.0).unwrap().lamports;
+        let payer_lamports = client.account_store.borrow().get(&payer.0).unwrap().lamports;
+        client.process_and_validate_instruction(
+            &close_instruction(payer.0, vault_address),
+            &[
+                Check::success(),
+                Check::account(&recipient).lamports(500000000).build(),
+                Check::account(&sol_vault).lamports(0).build(),
+                Check::account(&ramp_account.0).lamports(0).build(),
+                Check::account(&payer.0).lamports(payer_lamports + ramp_lamports + vault_rent).build(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_off_ramp_deposit_region_not_allowed() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA"), Medium::new("AIRTEL")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        // unregistered region
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("NGA"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference: [1u8; 32]
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(RampError::RegionNotAllowed.into())]
+        );
+
+        let disable_medium_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetRegionEnabled(SetRegionEnabledInstruction {
+                region: Region::new("KEN"),
+                medium: Some(Medium::new("AIRTEL")),
+                is_enabled: false,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &disable_medium_instruction,
+            &[Check::success()]
+        );
+
+        // disabled medium
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("AIRTEL"),
+                data: vec![],
+                reference: [1u8; 32]
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(RampError::RegionNotAllowed.into())]
+        );
+
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference: [1u8; 32]
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[1u8; 32]).0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+    }
+
+    #[test]
+    fn test_off_ramp_deposit_duplicate_reference() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let receipt = DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[7u8; 32]).0;
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference: [7u8; 32]
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(receipt, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[
+                Check::success(),
+                Check::account(&receipt)
+                    .owner(&ramp_program_id)
+                    .space(DepositReceipt::space())
+                    .build(),
+            ]
+        );
+
+        // same order reference again
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::err(RampError::DuplicateReference.into())]
+        );
+    }
+
+    #[test]
+    fn test_refund_deposit_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let receipt = DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[3u8; 32]).0;
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference: [3u8; 32]
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(receipt, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let sol_vault = find_sol_vault_address(&ramp_program_id, &ramp_account.0).0;
+        let vault_lamports = client.account_store.borrow().get(&sol_vault).unwrap().lamports;
+        let refund_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::RefundDepositNative(RefundDepositInstruction {
+                reference: [3u8; 32],
+                refund_fee: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(receipt, false),
+                AccountMeta::new(payer.0, false),
+                AccountMeta::new(sol_vault, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &refund_instruction,
+            &[
+                Check::success(),
+                Check::account(&sol_vault)
+                    .lamports(vault_lamports - 100000000)
+                    .build(),
+            ]
+        );
+
+        // a receipt can only be refunded once
+        client.process_and_validate_instruction(
+            &refund_instruction,
+            &[Check::err(RampError::DepositAlreadyRefunded.into())]
+        );
+    }
+
+    #[test]
+    fn test_set_asset_paused_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let receipt = DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[6u8; 32]).0;
+        let other = Pubkey::new_unique();
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(other, Account::new(1000000000, 0, &system_program.0));
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        let pause_instruction = |signer: Pubkey, deposits_paused: bool, withdrawals_paused: bool| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetAssetPaused(SetAssetPausedInstruction {
+                asset: Pubkey::default(),
+                deposits_paused,
+                withdrawals_paused,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(signer, true),
+            ],
+        );
+        let deposit_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference: [6u8; 32]
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(receipt, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        let recipient = Pubkey::new_unique();
+        let withdraw_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OnRampWithdrawNative(OnRampWithdrawNativeInstruction {
+                amount: 1000,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+
+        // only the owner can flip the switches
+        client.process_and_validate_instruction(
+            &pause_instruction(other, true, false),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+
+        client.process_and_validate_instruction(
+            &pause_instruction(payer.0, true, false),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &deposit_instruction,
+            &[Check::err(RampError::AssetDepositsPaused.into())]
+        );
+
+        // withdrawals are paused independently of deposits
+        client.process_and_validate_instruction(
+            &pause_instruction(payer.0, false, true),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &deposit_instruction,
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &withdraw_instruction,
+            &[Check::err(RampError::AssetWithdrawalsPaused.into())]
+        );
+    }
+
+    #[test]
+    fn test_fee_schedule_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let other = Pubkey::new_unique();
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(other, Account::new(1000000000, 0, &system_program.0));
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let schedule_instruction = |signer: Pubkey, tiers: Vec<FeeTier>| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetFeeSchedule(SetFeeScheduleInstruction {
+                asset: Pubkey::default(),
+                tiers,
+                min_fee: Some(1000000),
+                max_fee: Some(50000000),
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new_readonly(signer, true),
+            ],
+        );
+        let quote_instruction = |asset: Pubkey, amount: u64| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::QuoteFee(QuoteFeeInstruction {
+                asset,
+                amount,
+                region: None,
+            }),
+            vec![
+                AccountMeta::new_readonly(ramp_account.0, false),
+            ],
+        );
+        let tiers = vec![
+            FeeTier::new(0, 200),
+            FeeTier::new(1000000000, 100),
+        ];
+
+        // without a schedule the flat 10% applies
+        client.process_and_validate_instruction(
+            &quote_instruction(Pubkey::default(), 100000000),
+            &[Check::success(), Check::return_data(&10000000u64.to_le_bytes())]
+        );
+
+        client.process_and_validate_instruction(
+            &schedule_instruction(other, tiers.clone()),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &schedule_instruction(payer.0, tiers.iter().rev().cloned().collect()),
+            &[Check::err(RampError::InvalidFeeSchedule.into())]
+        );
+        client.process_and_validate_instruction(
+            &schedule_instruction(payer.0, tiers),
+            &[Check::success()]
+        );
+
+        // 2% bracket
+        client.process_and_validate_instruction(
+            &quote_instruction(Pubkey::default(), 100000000),
+            &[Check::success(), Check::return_data(&2000000u64.to_le_bytes())]
+        );
+        // 2% is below the minimum fee
+        client.process_and_validate_instruction(
+            &quote_instruction(Pubkey::default(), 10000000),
+            &[Check::success(), Check::return_data(&1000000u64.to_le_bytes())]
+        );
+        // 1% bracket capped at the maximum fee
+        client.process_and_validate_instruction(
+            &quote_instruction(Pubkey::default(), 10000000000),
+            &[Check::success(), Check::return_data(&50000000u64.to_le_bytes())]
+        );
+        client.process_and_validate_instruction(
+            &quote_instruction(Pubkey::new_unique(), 100000000),
+            &[Check::err(RampError::AssetNotFound.into())]
+        );
+    }
+
+    #[test]
+    fn test_revenue_split_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let payer_entry = AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0;
+        let sol_vault = find_sol_vault_address(&ramp_program_id, &ramp_account.0).0;
+        let beneficiaries = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        for beneficiary in beneficiaries {
+            accounts.insert(beneficiary, Account::new(1000000000, 0, &system_program.0));
+        }
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        // a 10% fee on this amount leaves 10000001 lamports of revenue
+        let reference = [11u8; 32];
+        let deposit_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000010,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &reference).0, false),
+                AccountMeta::new_readonly(payer_entry, false),
+                AccountMeta::new(sol_vault, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &deposit_instruction,
+            &[Check::success()]
+        );
+
+        let split_instruction = |signer: Pubkey, weights: &[u16]| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetRevenueSplit(SetRevenueSplitInstruction {
+                beneficiaries: beneficiaries.iter()
+                    .zip(weights)
+                    .map(|(address, weight_bps)| Beneficiary::new(*address, *weight_bps))
+                    .collect(),
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new_readonly(signer, true),
+            ],
+        );
+        let withdraw_instruction = |wallets: Vec<Pubkey>| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::WithdrawRevenueNative(WithdrawRevenueNativeInstruction {}),
+            [
+                vec![
+                    AccountMeta::new(ramp_account.0, false),
+                    AccountMeta::new_readonly(payer.0, true),
+                    AccountMeta::new(sol_vault, false),
+                ],
+                wallets.into_iter().map(|wallet| AccountMeta::new(wallet, false)).collect(),
+            ].concat(),
+        );
+
+        client.process_and_validate_instruction(
+            &split_instruction(beneficiaries[0], &[3333, 3333, 3334]),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &split_instruction(payer.0, &[3333, 3333, 3333]),
+            &[Check::err(RampError::InvalidRevenueSplit.into())]
+        );
+        client.process_and_validate_instruction(
+            &split_instruction(payer.0, &[5000, 0, 5000]),
+            &[Check::err(RampError::InvalidRevenueSplit.into())]
+        );
+        client.process_and_validate_instruction(
+            &split_instruction(payer.0, &[3333, 3333, 3334]),
+            &[Check::success()]
+        );
+
+        // beneficiaries have to be passed in split order
+        client.process_and_validate_instruction(
+            &withdraw_instruction(beneficiaries.iter().rev().cloned().collect()),
+            &[Check::err(RampError::InvalidRecipient.into())]
+        );
+        // the rounding dust goes to the first beneficiary
+        client.process_and_validate_instruction(
+            &withdraw_instruction(beneficiaries.to_vec()),
+            &[
+                Check::success(),
+                Check::account(&beneficiaries[0]).lamports(1000000000 + 3333001).build(),
+                Check::account(&beneficiaries[1]).lamports(1000000000 + 3333000).build(),
+                Check::account(&beneficiaries[2]).lamports(1000000000 + 3334000).build(),
+            ]
+        );
+        client.process_and_validate_instruction(
+            &withdraw_instruction(beneficiaries.to_vec()),
+            &[Check::err(RampError::NoRevenue.into())]
+        );
+    }
+
+    #[test]
+    fn test_access_list_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let payer_entry = AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0;
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        let access_instruction = |status: Option<AccessStatus>| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::UpdateAccessList(UpdateAccessListInstruction {
+                status,
+                addresses: vec![payer.0],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(payer_entry, false),
+            ],
+        );
+        let deposit_instruction = |reference: [u8; 32]| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &reference).0, false),
+                AccountMeta::new_readonly(payer_entry, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        let allowlist_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetAllowlistEnabled(SetAllowlistEnabledInstruction {
+                allowlist_enabled: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+
+        client.process_and_validate_instruction(
+            &access_instruction(Some(AccessStatus::Denied)),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &deposit_instruction([8u8; 32]),
+            &[Check::err(RampError::AddressDenied.into())]
+        );
+
+        // removing the entry closes the PDA and lets the sender deposit again
+        client.process_and_validate_instruction(
+            &access_instruction(None),
+            &[
+                Check::success(),
+                Check::account(&payer_entry).lamports(0).build(),
+            ]
+        );
+        client.process_and_validate_instruction(
+            &deposit_instruction([8u8; 32]),
+            &[Check::success()]
+        );
+
+        // in allowlist mode only allowed senders pass
+        client.process_and_validate_instruction(
+            &allowlist_instruction,
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &deposit_instruction([9u8; 32]),
+            &[Check::err(RampError::AddressNotAllowed.into())]
+        );
+        client.process_and_validate_instruction(
+            &access_instruction(Some(AccessStatus::Allowed)),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &deposit_instruction([9u8; 32]),
+            &[Check::success()]
+        );
+
+        // the entry has to be the PDA of the checked address
+        let mut spoofed = deposit_instruction([10u8; 32]);
+        spoofed.accounts[4] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+        client.process_and_validate_instruction(
+            &spoofed,
+            &[Check::err(RampError::InvalidAccessEntry.into())]
+        );
+    }
+
+    #[test]
+    fn test_escrow_deposit_native_expiry() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let released_receipt = DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[4u8; 32]).0;
+        let reclaimed_receipt = DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[5u8; 32]).0;
+        let open_receipt = DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[6u8; 32]).0;
+        let vault_address = Pubkey::new_unique();
+
+        let mut mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+        mollusk.sysvars.clock.unix_timestamp = 1000;
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address,
+            native_fee_percentage: 10,
+        };
+        let mut client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: true,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &active_instruction,
+            &[Check::success()]
+        );
+
+        let add_region_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddRegion(AddRegionInstruction {
+                region: Region::new("KEN"),
+                mediums: vec![Medium::new("MPESA")],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &add_region_instruction,
+            &[Check::success()]
+        );
+
+        let escrow_instruction = |reference: [u8; 32], receipt: Pubkey, expires_at: i64| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::EscrowDepositNative(EscrowDepositInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference,
+                expires_at,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(receipt, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0, false),
+            ],
+        );
+
+        // expiry has to be strictly in the future
+        client.process_and_validate_instruction(
+            &escrow_instruction([4u8; 32], released_receipt, 1000),
+            &[Check::err(RampError::InvalidExpiry.into())]
+        );
+        client.process_and_validate_instruction(
+            &escrow_instruction([4u8; 32], released_receipt, 1100),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &escrow_instruction([5u8; 32], reclaimed_receipt, 1100),
+            &[Check::success()]
+        );
+
+        let release_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::ReleaseEscrowNative(SettleEscrowInstruction {
+                reference: [4u8; 32],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(released_receipt, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        let reclaim_instruction = |reference: [u8; 32], receipt: Pubkey| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::ReclaimEscrowNative(SettleEscrowInstruction {
+                reference,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(receipt, false),
+            ],
+        );
+
+        // one second before expiry the operator can still release, the sender cannot reclaim
+        client.mollusk.sysvars.clock.unix_timestamp = 1099;
+        client.process_and_validate_instruction(
+            &reclaim_instruction([5u8; 32], reclaimed_receipt),
+            &[Check::err(RampError::EscrowNotExpired.into())]
+        );
+        // the first release creates the SOL vault and moves the escrow into it
+        let vault_rent = client.mollusk.sysvars.rent.minimum_balance(0);
+        client.process_and_validate_instruction(
+            &release_instruction,
+            &[
+                Check::success(),
+                Check::account(&find_sol_vault_address(&ramp_program_id, &ramp_account.0).0)
+                    .lamports(vault_rent + 100000000)
+                    .build(),
+            ]
+        );
+        client.process_and_validate_instruction(
+            &reclaim_instruction([4u8; 32], released_receipt),
+            &[Check::err(RampError::InvalidEscrowState.into())]
+        );
+
+        // at the expiry timestamp the escrow belongs back to the sender
+        client.mollusk.sysvars.clock.unix_timestamp = 1100;
+        let release_expired_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::ReleaseEscrowNative(SettleEscrowInstruction {
+                reference: [5u8; 32],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(reclaimed_receipt, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &release_expired_instruction,
+            &[Check::err(RampError::EscrowExpired.into())]
+        );
+        let receipt_lamports = client.account_store.borrow().get(&reclaimed_receipt).unwrap().lamports;
+        client.process_and_validate_instruction(
+            &reclaim_instruction([5u8; 32], reclaimed_receipt),
+            &[
+                Check::success(),
+                Check::account(&reclaimed_receipt)
+                    .lamports(receipt_lamports - 100000000)
+                    .build(),
+            ]
+        );
+        client.process_and_validate_instruction(
+            &reclaim_instruction([5u8; 32], reclaimed_receipt),
+            &[Check::err(RampError::InvalidEscrowState.into())]
+        );
+
+        // the ramp cannot be closed while an escrow is still open
+        client.process_and_validate_instruction(
+            &escrow_instruction([6u8; 32], open_receipt, 1200),
+            &[Check::success()]
+        );
+        let deactivate_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: false,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &deactivate_instruction,
+            &[Check::success()]
+        );
+        let close_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::CloseRamp(CloseRampInstruction {}),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+                AccountMeta::new(vault_address, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &close_instruction,
+            &[Check::err(RampError::EscrowsOutstanding.into())]
+        );
+    }
+
+    #[test]
+    fn test_voucher_withdraw_native_checks() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let recipient = Pubkey::new_unique();
+        let voucher_signer = Pubkey::new_unique();
+
+        let mut mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+        mollusk.sysvars.clock.unix_timestamp = 1000;
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(recipient, Account::default());
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let voucher_instruction = |voucher: WithdrawVoucher| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::VoucherWithdrawNative(VoucherWithdrawInstruction {
+                voucher: voucher.clone(),
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new(find_nonce_address(&ramp_program_id, &ramp_account.0, voucher.nonce).0, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::instructions::id(), false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient).0, false),
+                AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+            ],
+        );
+        let voucher = WithdrawVoucher {
+            ramp: ramp_account.0,
+            recipient,
+            asset: Pubkey::default(),
+            amount: 1000,
+            nonce: 1,
+            expires_at: 1100,
+        };
+
+        // vouchers are rejected until a signer is configured
+        client.process_and_validate_instruction(
+            &voucher_instruction(voucher.clone()),
+            &[Check::err(RampError::VoucherSignerNotSet.into())]
+        );
+
+        let set_signer_instruction = |owner: Pubkey| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetVoucherSigner(SetVoucherSignerInstruction {
+                voucher_signer,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(owner, true),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &set_signer_instruction(recipient),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &set_signer_instruction(payer.0),
+            &[Check::success()]
+        );
+
+        client.process_and_validate_instruction(
+            &voucher_instruction(WithdrawVoucher {
+                ramp: Pubkey::new_unique(),
+                ..voucher.clone()
+            }),
+            &[Check::err(RampError::InvalidVoucher.into())]
+        );
+        client.process_and_validate_instruction(
+            &voucher_instruction(WithdrawVoucher {
+                expires_at: 1000,
+                ..voucher.clone()
+            }),
+            &[Check::err(RampError::VoucherExpired.into())]
+        );
+    }
+
+    #[test]
+    fn test_batch_onramp_withdraw_compute_units() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let mint = (Pubkey::new_unique(),
+            token::create_account_for_mint(
+            Mint {
+                    mint_authority: Some(payer.0).into(),
+                    supply: 100000000000,
+                    freeze_authority: Some(payer.0).into(),
+                    decimals: 9,
+                    is_initialized: true
+                }
+            )
+        );
+        let token_account = |mint: Pubkey, owner: Pubkey, amount: u64| {
+            associated_token::create_account_for_associated_token_account(
+                spl_token::state::Account {
+                    mint,
+                    owner,
+                    amount,
+                    delegate: None.into(),
+                    state: spl_token::state::AccountState::Initialized,
+                    is_native: None.into(),
+                    delegated_amount: 0,
+                    close_authority: None.into()
+                }
+            )
+        };
+        let mint_vault = token_account(mint.0, payer.0, 100000000000);
+        let ramp_token_account = token_account(mint.0, ramp_account.0, 0);
+        let recipient_wallets: Vec<Pubkey> = (0..MAX_BATCH_PAYOUTS).map(|_| Pubkey::new_unique()).collect();
+        let recipients: Vec<(Pubkey, Account)> = recipient_wallets.iter()
+            .map(|wallet| token_account(mint.0, *wallet, 0))
+            .collect();
+        // token account of another mint, skipped by the batch
+        let foreign_wallet = Pubkey::new_unique();
+        let foreign_token_account = token_account(Pubkey::new_unique(), foreign_wallet, 0);
+        let token_program = token::keyed_account();
+        let system_program = mollusk_system_program();
+        let associated_token_program = associated_token::keyed_account();
+
+        let mut mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        token::add_program(&mut mollusk);
+        associated_token::add_program(&mut mollusk);
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(mint.0, mint.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(token_program.0, token_program.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(associated_token_program.0, associated_token_program.1);
+        accounts.insert(mint_vault.0, mint_vault.1);
+        accounts.insert(ramp_token_account.0, ramp_token_account.1);
+        accounts.insert(foreign_token_account.0, foreign_token_account.1.clone());
+        for recipient in recipients.iter() {
+            accounts.insert(recipient.0, recipient.1.clone());
+        }
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program.0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddAssets(AddAssetsInstruction {
+                initial_amount: 100000000,
+                fee_percentage: 10,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(mint.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(token::ID, false),
+                AccountMeta::new_readonly(system_program.0, false),
+                AccountMeta::new_readonly(associated_token_program.0, false),
+                AccountMeta::new(mint_vault.0, false),
+                AccountMeta::new(ramp_token_account.0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        // (recipient token account, recipient wallet) pairs
+        let batch_instruction = |recipients: &[(Pubkey, Pubkey)], amount: u64| {
+            let mut metas = vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new_readonly(mint.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(ramp_token_account.0, false),
+                AccountMeta::new_readonly(token::ID, false),
+            ];
+            metas.extend(recipients.iter().map(|(recipient, _)| AccountMeta::new(*recipient, false)));
+            metas.extend(recipients.iter().map(|(_, wallet)| AccountMeta::new_readonly(
+                AccessEntry::find_address(&ramp_program_id, &ramp_account.0, wallet).0,
+                false,
+            )));
+            Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::BatchOnRampWithdraw(BatchOnRampWithdrawInstruction {
+                    payouts: recipients.iter().map(|(recipient, _)| BatchPayout {
+                        recipient: *recipient,
+                        amount,
+                    }).collect(),
+                }),
+                metas,
+            )
+        };
+        let recipient_keys: Vec<(Pubkey, Pubkey)> = recipients.iter()
+            .map(|recipient| recipient.0)
+            .zip(recipient_wallets.iter().copied())
+            .collect();
+
+        // a full batch must fit the default compute budget of a single instruction
+        let result = client.process_and_validate_instruction(
+            &batch_instruction(&recipient_keys, 1000),
+            &[Check::success()]
+        );
+        println!("batch of {} payouts consumed {} CU", MAX_BATCH_PAYOUTS, result.compute_units_consumed);
+        assert!(result.compute_units_consumed < 200_000);
+
+        // liquidity is checked for the batch total
+        client.process_and_validate_instruction(
+            &batch_instruction(&recipient_keys, 100000000 / MAX_BATCH_PAYOUTS as u64 + 1),
+            &[Check::err(RampError::InsufficientFunds.into())]
+        );
+
+        let mut too_large = recipient_keys.clone();
+        too_large.push((foreign_token_account.0, foreign_wallet));
+        client.process_and_validate_instruction(
+            &batch_instruction(&too_large, 1),
+            &[Check::err(RampError::BatchTooLarge.into())]
+        );
+
+        // invalid recipients are skipped without failing the batch
+        client.process_and_validate_instruction(
+            &batch_instruction(&[recipient_keys[0], (foreign_token_account.0, foreign_wallet)], 1000),
+            &[
+                Check::success(),
+                Check::account(&foreign_token_account.0)
+                    .data(&foreign_token_account.1.data)
+                    .build(),
+            ]
+        );
+
+        // deny listed recipients are skipped the same way
+        let deny_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::UpdateAccessList(UpdateAccessListInstruction {
+                status: Some(AccessStatus::Denied),
+                addresses: vec![recipient_wallets[1]],
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program.0, false),
+                AccountMeta::new(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient_wallets[1]).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &deny_instruction,
+            &[Check::success()]
+        );
+        let denied_before = client.account_store.borrow().get(&recipient_keys[1].0).unwrap().data.clone();
+        client.process_and_validate_instruction(
+            &batch_instruction(&recipient_keys[..2], 1000),
+            &[
+                Check::success(),
+                Check::account(&recipient_keys[1].0)
+                    .data(&denied_before)
+                    .build(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_onramp_withdraw_credit_and_claim() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let mint = (Pubkey::new_unique(),
+            token::create_account_for_mint(
+            Mint {
+                    mint_authority: Some(payer.0).into(),
+                    supply: 100000000000,
+                    freeze_authority: Some(payer.0).into(),
+                    decimals: 9,
+                    is_initialized: true
+                }
+            )
+        );
+        let token_account = |mint: Pubkey, owner: Pubkey, amount: u64| {
+            associated_token::create_account_for_associated_token_account(
+                spl_token::state::Account {
+                    mint,
+                    owner,
+                    amount,
+                    delegate: None.into(),
+                    state: spl_token::state::AccountState::Initialized,
+                    is_native: None.into(),
+                    delegated_amount: 0,
+                    close_authority: None.into()
+                }
+            )
+        };
+        let mint_vault = token_account(mint.0, payer.0, 100000000000);
+        let ramp_token_account = token_account(mint.0, ramp_account.0, 0);
+        // the recipient has no token account for the mint yet
+        let recipient = Pubkey::new_unique();
+        let recipient_token_account = token_account(mint.0, recipient, 0);
+        let credit_account = CreditEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient, &mint.0).0;
+        let credit_token_account = spl_associated_token_account::get_associated_token_address(&credit_account, &mint.0);
+        let token_program = token::keyed_account();
+        let system_program = mollusk_system_program();
+        let associated_token_program = associated_token::keyed_account();
+
+        let mut mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        token::add_program(&mut mollusk);
+        associated_token::add_program(&mut mollusk);
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(mint.0, mint.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(token_program.0, token_program.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(associated_token_program.0, associated_token_program.1);
+        accounts.insert(mint_vault.0, mint_vault.1);
+        accounts.insert(ramp_token_account.0, ramp_token_account.1);
+
+        let initialize_instruction = InitializeProgramInstruction {
+            bump: ramp_pda.1,
+            vault_address: Pubkey::new_unique(),
+            native_fee_percentage: 10,
+        };
+        let client = mollusk.with_context(accounts);
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::InitializeProgram(initialize_instruction),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program.0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::AddAssets(AddAssetsInstruction {
+                initial_amount: 100000000,
+                fee_percentage: 10,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(mint.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(token::ID, false),
+                AccountMeta::new_readonly(system_program.0, false),
+                AccountMeta::new_readonly(associated_token_program.0, false),
+                AccountMeta::new(mint_vault.0, false),
+                AccountMeta::new(ramp_token_account.0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &instruction,
+            &[Check::success()]
+        );
+
+        let withdraw_instruction = |credit_accounts: bool| {
+            let mut metas = vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(mint.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new(recipient_token_account.0, false),
+                AccountMeta::new(ramp_token_account.0, false),
+                AccountMeta::new_readonly(token::ID, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient).0, false),
+            ];
+            if credit_accounts {
+                metas.extend([
+                    AccountMeta::new_readonly(recipient, false),
+                    AccountMeta::new(credit_account, false),
+                    AccountMeta::new(credit_token_account, false),
+                    AccountMeta::new_readonly(system_program.0, false),
+                    AccountMeta::new_readonly(associated_token_program.0, false),
+                ]);
+            }
+            Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::OnRampWithdraw(OnRampWithdrawInstruction { amount: 40000000 }),
+                metas,
+            )
+        };
+
+        // without the credit accounts a payout to a missing token account fails as before
+        client.process_and_validate_instruction(
+            &withdraw_instruction(false),
+            &[Check::err(RampError::InvalidRecipient.into())]
+        );
+
+        // payouts are credited and accumulate for the recipient
+        client.process_and_validate_instruction(
+            &withdraw_instruction(true),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &withdraw_instruction(true),
+            &[Check::success()]
+        );
+        let credit: CreditEntry = borsh::from_slice(
+            &client.account_store.borrow().get(&credit_account).unwrap().data
+        ).unwrap();
+        assert_eq!(credit.recipient, recipient);
+        assert_eq!(credit.asset, mint.0);
+        assert_eq!(credit.amount, 80000000);
+        assert_eq!(credit.payer, payer.0);
+
+        // credited tokens left the ramp, so they no longer count as liquidity
+        client.process_and_validate_instruction(
+            &withdraw_instruction(true),
+            &[Check::err(RampError::InsufficientFunds.into())]
+        );
+
+        // the ramp cannot be closed while a credit is unclaimed
+        client.process_and_validate_instruction(
+            &Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::SetActive(SetActiveInstruction {
+                    is_active: false,
+                }),
+                vec![
+                    AccountMeta::new(ramp_account.0, false),
+                    AccountMeta::new(payer.0, true),
+                ],
+            ),
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::CloseRamp(CloseRampInstruction {}),
+                vec![
+                    AccountMeta::new(ramp_account.0, false),
+                    AccountMeta::new(payer.0, true),
+                    AccountMeta::new(Pubkey::new_unique(), false),
+                    AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_account.0).0, false),
+                ],
+            ),
+            &[Check::err(RampError::CreditsOutstanding.into())]
+        );
+
+        let claim_instruction = |signer: bool| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::ClaimCredit(ClaimCreditInstruction {}),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new_readonly(mint.0, false),
+                AccountMeta::new_readonly(recipient, signer),
+                AccountMeta::new(recipient_token_account.0, false),
+                AccountMeta::new(credit_account, false),
+                AccountMeta::new(credit_token_account, false),
+                AccountMeta::new(payer.0, false),
+                AccountMeta::new_readonly(token::ID, false),
+                AccountMeta::new_readonly(system_program.0, false),
+                AccountMeta::new_readonly(AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient).0, false),
+            ],
+        );
+        client.process_and_validate_instruction(
+            &claim_instruction(false),
+            &[Check::err(RampError::InvalidSigner.into())]
+        );
+
+        // the recipient opens a token account and claims, the credit accounts are closed
+        client.account_store.borrow_mut().insert(recipient_token_account.0, recipient_token_account.1.clone());
+        client.process_and_validate_instruction(
+            &claim_instruction(true),
+            &[
+                Check::success(),
+                Check::account(&credit_account).lamports(0).build(),
+                Check::account(&credit_token_account).lamports(0).build(),
+                Check::account(&recipient_token_account.0)
+                    .data(&token_account(mint.0, recipient, 80000000).1.data)
+                    .build(),
+            ]
+        );
+
+        client.process_and_validate_instruction(
+            &claim_instruction(true),
+            &[Check::err(RampError::NoCredit.into())]
+        );
+    }
+
+    #[test]
+    fn test_migrate_ramp() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda = Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let system_program = mollusk_system_program();
+        let program_account = |data: Vec<u8>| Account {
+            lamports: 1000000000,
+            data,
+            owner: ramp_program_id,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+        let rent = mollusk.sysvars.rent.clone();
+
+        // a ramp written before the open escrow and credit counters were appended to the layout
+        let mut legacy_state: RampState = bytemuck::Zeroable::zeroed();
+        legacy_state.owner = payer.0;
+        legacy_state.is_active = true.into();
+        legacy_state.creator = payer.0;
+        legacy_state.bump = ramp_pda.1;
+        let legacy_len = RampState::LEN - 16;
+        let ramp_account = (ramp_pda.0, Account {
+            lamports: rent.minimum_balance(legacy_len),
+            ..program_account(bytemuck::bytes_of(&legacy_state)[..legacy_len].to_vec())
+        });
+
+        let receipt = |reference: [u8; 32], status: ReceiptStatus| (
+            DepositReceipt::find_address(&ramp_program_id, &ramp_pda.0, &reference).0,
+            program_account(borsh::to_vec(&DepositReceipt { reference, status, ..DepositReceipt::default() }).unwrap()),
+        );
+        let escrowed = receipt([1u8; 32], ReceiptStatus::Escrowed);
+        let released = receipt([2u8; 32], ReceiptStatus::Released);
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let credit = (
+            CreditEntry::find_address(&ramp_program_id, &ramp_pda.0, &recipient, &mint).0,
+            program_account(borsh::to_vec(&CreditEntry { recipient, asset: mint, amount: 10, payer: payer.0 }).unwrap()),
+        );
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        accounts.insert(escrowed.0, escrowed.1);
+        accounts.insert(released.0, released.1);
+        accounts.insert(credit.0, credit.1);
+        let client = mollusk.with_context(accounts);
+
+        let set_active_instruction = Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::SetActive(SetActiveInstruction {
+                is_active: false,
+            }),
+            vec![
+                AccountMeta::new(ramp_pda.0, false),
+                AccountMeta::new(payer.0, true),
+            ],
+        );
+        let migrate_instruction = |owner: Pubkey, open_accounts: &[Pubkey]| {
+            let mut metas = vec![
+                AccountMeta::new(ramp_pda.0, false),
+                AccountMeta::new(owner, true),
+                AccountMeta::new_readonly(system_program.0, false),
+            ];
+            metas.extend(open_accounts.iter().map(|key| AccountMeta::new_readonly(*key, false)));
+            Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::MigrateRamp(MigrateRampInstruction {}),
+                metas,
+            )
+        };
+
+        // the shorter account cannot be loaded until it is migrated
+        client.process_and_validate_instruction(
+            &set_active_instruction,
+            &[Check::err(RampError::InvalidAccountState.into())]
+        );
+
+        // only the owner migrates, with every open escrow and credit passed once
+        client.process_and_validate_instruction(
+            &migrate_instruction(Pubkey::new_unique(), &[escrowed.0, credit.0]),
+            &[Check::err(RampError::Unauthorized.into())]
+        );
+        client.process_and_validate_instruction(
+            &migrate_instruction(payer.0, &[escrowed.0, released.0]),
+            &[Check::err(RampError::InvalidEscrowState.into())]
+        );
+        client.process_and_validate_instruction(
+            &migrate_instruction(payer.0, &[escrowed.0, escrowed.0]),
+            &[Check::err(RampError::InvalidAccountState.into())]
+        );
+        client.process_and_validate_instruction(
+            &migrate_instruction(payer.0, &[escrowed.0, credit.0]),
+            &[
+                Check::success(),
+                Check::account(&ramp_pda.0)
+                    .space(RampState::LEN)
+                    .lamports(rent.minimum_balance(RampState::LEN))
+                    .build(),
+            ]
+        );
+        let migrated_state = *RampState::load(&client.account_store.borrow().get(&ramp_pda.0).unwrap().data).unwrap();
+        assert_eq!(migrated_state.owner, payer.0);
+        assert_eq!(migrated_state.bump, ramp_pda.1);
+        assert_eq!(migrated_state.open_escrows.get(), 1);
+        assert_eq!(migrated_state.open_credits.get(), 1);
+        client.process_and_validate_instruction(
+            &migrate_instruction(payer.0, &[]),
+            &[Check::err(RampError::RampAlreadyMigrated.into())]
+        );
+
+        // the migrated ramp loads again, and the rebuilt counters keep it from being closed
+        client.process_and_validate_instruction(
+            &set_active_instruction,
+            &[Check::success()]
+        );
+        client.process_and_validate_instruction(
+            &Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::CloseRamp(CloseRampInstruction {}),
+                vec![
+                    AccountMeta::new(ramp_pda.0, false),
+                    AccountMeta::new(payer.0, true),
+                    AccountMeta::new(Pubkey::new_unique(), false),
+                    AccountMeta::new(find_sol_vault_address(&ramp_program_id, &ramp_pda.0).0, false),
+                ],
+            ),
+            &[Check::err(RampError::EscrowsOutstanding.into())]
+        );
+    }
+
+    #[test]
+    fn test_compute_units_native() {
+        let ramp_program_id = Pubkey::new_unique();
+        let payer = (Pubkey::new_unique(), Account::new(
+            1000000000000000,
+            0,
+            &mollusk_system_program().0
+        ));
+        let ramp_pda= Pubkey::find_program_address(&[b"ramp", &payer.0.as_ref()], &ramp_program_id);
+        let ramp_account = (ramp_pda.0, Account::default());
+        let system_program = mollusk_system_program();
+        let vault_address = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let sol_vault = find_sol_vault_address(&ramp_program_id, &ramp_account.0).0;
+        let payer_access = AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &payer.0).0;
+        let recipient_access = AccessEntry::find_address(&ramp_program_id, &ramp_account.0, &recipient).0;
+
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        let mut accounts  = HashMap::new();
+        accounts.insert(ramp_account.0, ramp_account.1);
+        accounts.insert(payer.0, payer.1);
+        accounts.insert(system_program.0, system_program.1);
+        let client = mollusk.with_context(accounts);
+
+        let owner_metas = || vec![
+            AccountMeta::new(ramp_account.0, false),
+            AccountMeta::new(payer.0, true),
+        ];
+        let deposit_instruction = |reference: [u8; 32]| Instruction::new_with_borsh(
+            ramp_program_id,
+            &processors::Instruction::OffRampDepositNative(OffRampDepositNativeInstruction {
+                amount: 100000000,
+                region: Region::new("KEN"),
+                medium: Medium::new("MPESA"),
+                data: vec![],
+                reference,
+            }),
+            vec![
+                AccountMeta::new(ramp_account.0, false),
+                AccountMeta::new(payer.0, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &reference).0, false),
+                AccountMeta::new_readonly(payer_access, false),
+                AccountMeta::new(sol_vault, false),
+            ],
+        );
+
+        // one instruction per handler shape: the RampState setters, a deposit updating revenue and
+        // the deposit sequence, read only payouts and views, and a refund updating revenue in place
+        let instructions = [
+            ("InitializeProgram", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::InitializeProgram(InitializeProgramInstruction {
+                    bump: ramp_pda.1,
+                    vault_address,
+                    native_fee_percentage: 10,
+                }),
+                vec![
+                    AccountMeta::new(ramp_account.0, false),
+                    AccountMeta::new(payer.0, true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            )),
+            ("SetActive", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::SetActive(SetActiveInstruction {
+                    is_active: true,
+                }),
+                owner_metas(),
+            )),
+            ("SetNativeFeePercentage", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::SetNativeFeePercentage(SetNativeFeePercentageInstruction {
+                    fee_percentage: 1,
+                }),
+                owner_metas(),
+            )),
+            ("AddRegion", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::AddRegion(AddRegionInstruction {
+                    region: Region::new("KEN"),
+                    mediums: vec![Medium::new("MPESA")],
+                }),
+                owner_metas(),
+            )),
+            ("SetFeeSchedule", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::SetFeeSchedule(SetFeeScheduleInstruction {
+                    asset: Pubkey::default(),
+                    tiers: vec![FeeTier::new(0, 200), FeeTier::new(1000000000, 100)],
+                    min_fee: Some(1000),
+                    max_fee: None,
+                }),
+                owner_metas(),
+            )),
+            ("SetLowWaterMark", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::SetLowWaterMark(SetLowWaterMarkInstruction {
+                    asset: Pubkey::default(),
+                    low_water_mark: 1000,
+                }),
+                owner_metas(),
+            )),
+            ("OffRampDepositNative", deposit_instruction([1u8; 32])),
+            ("OffRampDepositNative (vault created)", deposit_instruction([2u8; 32])),
+            ("QuoteFee", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::QuoteFee(QuoteFeeInstruction {
+                    asset: Pubkey::default(),
+                    amount: 100000000,
+                    region: Some(Region::new("KEN")),
+                }),
+                vec![AccountMeta::new_readonly(ramp_account.0, false)],
+            )),
+            ("GetAvailableLiquidity", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::GetAvailableLiquidity(GetAvailableLiquidityInstruction {
+                    asset: Pubkey::default(),
+                }),
+                vec![
+                    AccountMeta::new_readonly(ramp_account.0, false),
+                    AccountMeta::new_readonly(sol_vault, false),
+                ],
+            )),
+            ("OnRampWithdrawNative", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::OnRampWithdrawNative(OnRampWithdrawNativeInstruction {
+                    amount: 1000000,
+                }),
+                vec![
+                    AccountMeta::new(ramp_account.0, false),
+                    AccountMeta::new(recipient, false),
+                    AccountMeta::new(payer.0, true),
+                    AccountMeta::new_readonly(recipient_access, false),
+                    AccountMeta::new(sol_vault, false),
+                ],
+            )),
+            ("RefundDepositNative", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::RefundDepositNative(RefundDepositInstruction {
+                    reference: [2u8; 32],
+                    refund_fee: true,
+                }),
+                vec![
+                    AccountMeta::new(ramp_account.0, false),
+                    AccountMeta::new(payer.0, true),
+                    AccountMeta::new(DepositReceipt::find_address(&ramp_program_id, &ramp_account.0, &[2u8; 32]).0, false),
+                    AccountMeta::new(payer.0, false),
+                    AccountMeta::new(sol_vault, false),
+                ],
+            )),
+            ("WithdrawRevenueNative", Instruction::new_with_borsh(
+                ramp_program_id,
+                &processors::Instruction::WithdrawRevenueNative(WithdrawRevenueNativeInstruction {}),
+                vec![
+                    AccountMeta::new(ramp_account.0, false),
+                    AccountMeta::new(payer.0, true),
+                    AccountMeta::new(sol_vault, false),
+                    AccountMeta::new(vault_address, false),
+                ],
+            )),
+        ];
+
+        // the table is printed with --nocapture, to compare compute units across changes to the state handling
+        for (name, instruction) in instructions.iter() {
+            let result = client.process_and_validate_instruction(
+                instruction,
+                &[Check::success()]
+            );
+            println!("{:<40} {:>8} CU", name, result.compute_units_consumed);
+            assert!(result.compute_units_consumed < 200_000);
+        }
+    }
+
+    #[test]
+    fn test_instruction_discriminators() {
+        use processors::Instruction as I;
+        use solana_program::program_error::ProgramError;
+
+        let key = Pubkey::new_from_array([7; 32]);
+        let region = Region::new("KEN");
+        let medium = Medium::new("MPESA");
+        let deposit = || OffRampDepositInstruction {
+            amount: 5,
+            region,
+            medium,
+            data: vec![9],
+            reference: [1; 32],
+        };
+        let escrow = || EscrowDepositInstruction {
+            amount: 5,
+            region,
+            medium,
+            data: vec![9],
+            reference: [1; 32],
+            expires_at: 60,
+        };
+        let voucher = || VoucherWithdrawInstruction {
+            voucher: WithdrawVoucher {
+                ramp: key,
+                recipient: key,
+                asset: key,
+                amount: 5,
+                nonce: 1,
+                expires_at: 60,
+            },
+        };
+        let settle = || SettleEscrowInstruction { reference: [1; 32] };
+        let refund = || RefundDepositInstruction { reference: [1; 32], refund_fee: true };
+
+        let key_bytes: &[u8] = &[7; 32];
+        let amount_bytes: &[u8] = &5u64.to_le_bytes();
+        let fee_bytes: &[u8] = &2u128.to_le_bytes();
+        let region_bytes: &[u8] = b"KEN";
+        let medium_bytes: &[u8] = b"MPESA\0\0\0";
+        let deposit_bytes = [amount_bytes, region_bytes, medium_bytes, &1u32.to_le_bytes(), &[9], &[1; 32]].concat();
+        let escrow_bytes = [&deposit_bytes[..], &60i64.to_le_bytes()].concat();
+        let voucher_bytes = [key_bytes, key_bytes, key_bytes, amount_bytes, &1u64.to_le_bytes(), &60i64.to_le_bytes()].concat();
+        let refund_bytes = [&[1; 32][..], &[1]].concat();
+
+        // the leading byte is the frozen discriminator, a failure here means deployed clients break
+        let pinned: Vec<(I, Vec<u8>)> = vec![
+            (I::SetActive(SetActiveInstruction { is_active: true }), vec![0, 1]),
+            (I::RemoveAssets(RemoveAssetsInstruction {}), vec![1]),
+            (I::SetOwner(SetOwnerInstruction { new_owner: key }), [&[2][..], key_bytes].concat()),
+            (I::AddAssets(AddAssetsInstruction { initial_amount: 5, fee_percentage: 2 }), [&[3][..], amount_bytes, fee_bytes].concat()),
+            (I::AddAssets2022(AddAssetsInstruction { initial_amount: 5, fee_percentage: 2 }), [&[4][..], amount_bytes, fee_bytes].concat()),
+            (
+                I::InitializeProgram(InitializeProgramInstruction { bump: 254, vault_address: key, native_fee_percentage: 2 }),
+                [&[5, 254][..], key_bytes, fee_bytes].concat(),
+            ),
+            (I::OffRampDepositToken22(deposit()), [&[6][..], &deposit_bytes[..]].concat()),
+            (I::OffRampDeposit(deposit()), [&[7][..], &deposit_bytes[..]].concat()),
+            (I::OnRampWithdraw(OnRampWithdrawInstruction { amount: 5 }), [&[8][..], amount_bytes].concat()),
+            (
+                I::OffRampDepositNative(OffRampDepositNativeInstruction {
+                    amount: 5,
+                    region,
+                    medium,
+                    data: vec![9],
+                    reference: [1; 32],
+                }),
+                [&[9][..], &deposit_bytes[..]].concat(),
+            ),
+            (I::OnRampWithdrawNative(OnRampWithdrawNativeInstruction { amount: 5 }), [&[10][..], amount_bytes].concat()),
+            (I::SetNativeFeePercentage(SetNativeFeePercentageInstruction { fee_percentage: 2 }), [&[11][..], fee_bytes].concat()),
+            (I::SetAssetFee(SetAssetFeeInstruction { asset_mint: key, fee_percentage: 2 }), [&[12][..], key_bytes, fee_bytes].concat()),
+            (
+                I::AddRegion(AddRegionInstruction { region, mediums: vec![medium] }),
+                [&[13][..], region_bytes, &1u32.to_le_bytes(), medium_bytes].concat(),
+            ),
+            (I::AddRegionMedium(AddRegionMediumInstruction { region, medium }), [&[14][..], region_bytes, medium_bytes].concat()),
+            (
+                I::SetRegionEnabled(SetRegionEnabledInstruction { region, medium: Some(medium), is_enabled: false }),
+                [&[15][..], region_bytes, &[1], medium_bytes, &[0]].concat(),
+            ),
+            (I::SetRegionFee(SetRegionFeeInstruction { region, fee_percentage: None }), [&[16][..], region_bytes, &[0]].concat()),
+            (I::RefundDeposit(refund()), [&[17][..], &refund_bytes[..]].concat()),
+            (I::RefundDepositNative(refund()), [&[18][..], &refund_bytes[..]].concat()),
+            (I::EscrowDeposit(escrow()), [&[19][..], &escrow_bytes[..]].concat()),
+            (I::EscrowDepositNative(escrow()), [&[20][..], &escrow_bytes[..]].concat()),
+            (I::ReleaseEscrow(settle()), [&[21][..], &[1; 32][..]].concat()),
+            (I::ReleaseEscrowNative(settle()), [&[22][..], &[1; 32][..]].concat()),
+            (I::ReclaimEscrow(settle()), [&[23][..], &[1; 32][..]].concat()),
+            (I::ReclaimEscrowNative(settle()), [&[24][..], &[1; 32][..]].concat()),
+            (I::SetVoucherSigner(SetVoucherSignerInstruction { voucher_signer: key }), [&[25][..], key_bytes].concat()),
+            (I::VoucherWithdraw(voucher()), [&[26][..], &voucher_bytes[..]].concat()),
+            (I::VoucherWithdrawNative(voucher()), [&[27][..], &voucher_bytes[..]].concat()),
+            (
+                I::BatchOnRampWithdraw(BatchOnRampWithdrawInstruction { payouts: vec![BatchPayout { recipient: key, amount: 5 }] }),
+                [&[28][..], &1u32.to_le_bytes(), key_bytes, amount_bytes].concat(),
+            ),
+            (
+                I::SetAssetPaused(SetAssetPausedInstruction { asset: key, deposits_paused: true, withdrawals_paused: false }),
+                [&[29][..], key_bytes, &[1, 0]].concat(),
+            ),
+            (
+                I::UpdateAccessList(UpdateAccessListInstruction { status: Some(AccessStatus::Allowed), addresses: vec![key] }),
+                [&[30, 1, 1][..], &1u32.to_le_bytes(), key_bytes].concat(),
+            ),
+            (I::SetAllowlistEnabled(SetAllowlistEnabledInstruction { allowlist_enabled: true }), vec![31, 1]),
+            (
+                I::SetFeeSchedule(SetFeeScheduleInstruction {
+                    asset: key,
+                    tiers: vec![FeeTier::new(1000, 200)],
+                    min_fee: Some(5),
+                    max_fee: None,
+                }),
+                [&[32][..], key_bytes, &1u32.to_le_bytes(), &1000u64.to_le_bytes(), &200u16.to_le_bytes(), &[1], amount_bytes, &[0]].concat(),
+            ),
+            (
+                I::QuoteFee(QuoteFeeInstruction { asset: key, amount: 5, region: Some(region) }),
+                [&[33][..], key_bytes, amount_bytes, &[1], region_bytes].concat(),
+            ),
+            (
+                I::SetRevenueSplit(SetRevenueSplitInstruction { beneficiaries: vec![Beneficiary::new(key, 10000)] }),
+                [&[34][..], &1u32.to_le_bytes(), key_bytes, &10000u16.to_le_bytes()].concat(),
+            ),
+            (I::WithdrawRevenue(WithdrawRevenueInstruction {}), vec![35]),
+            (I::WithdrawRevenueNative(WithdrawRevenueNativeInstruction {}), vec![36]),
+            (I::GetAvailableLiquidity(GetAvailableLiquidityInstruction { asset: key }), [&[37][..], key_bytes].concat()),
+            (
+                I::SetLowWaterMark(SetLowWaterMarkInstruction { asset: key, low_water_mark: 5 }),
+                [&[38][..], key_bytes, amount_bytes].concat(),
+            ),
+            (I::FundAsset(FundAssetInstruction { amount: 5 }), [&[39][..], amount_bytes].concat()),
+            (I::FundAssetNative(FundAssetInstruction { amount: 5 }), [&[40][..], amount_bytes].concat()),
+            (I::DefundAsset(DefundAssetInstruction { amount: 5 }), [&[41][..], amount_bytes].concat()),
+            (I::DefundAssetNative(DefundAssetInstruction { amount: 5 }), [&[42][..], amount_bytes].concat()),
+            (I::SetAssetWindDown(SetAssetWindDownInstruction { asset: key, winding_down: true }), [&[43][..], key_bytes, &[1]].concat()),
+            (I::CloseRamp(CloseRampInstruction {}), vec![44]),
+            (I::ClaimCredit(ClaimCreditInstruction {}), vec![45]),
+            (I::MigrateRamp(MigrateRampInstruction {}), vec![46]),
+        ];
+
+        assert_eq!(pinned.len(), I::LAST_DISCRIMINATOR as usize + 1);
+        for (instruction, expected) in pinned {
+            let name = format!("{:?}", instruction);
+            let bytes = borsh::to_vec(&processors::RampInstruction { instruction }).unwrap();
+            assert_eq!(bytes, expected, "{}", name);
+
+            let decoded = processors::RampInstruction::unpack(&bytes).unwrap();
+            assert_eq!(format!("{:?}", decoded.instruction), name);
+        }
+
+        let unknown = processors::RampInstruction::unpack(&[I::LAST_DISCRIMINATOR + 1]).unwrap_err();
+        assert_eq!(unknown, ProgramError::from(RampError::UnknownInstruction));
+        let empty = processors::RampInstruction::unpack(&[]).unwrap_err();
+        assert_eq!(empty, ProgramError::from(RampError::UnknownInstruction));
+    }
+
+    #[test]
+    fn test_unknown_instruction() {
+        let ramp_program_id = Pubkey::new_unique();
+        let mollusk = Mollusk::new(&ramp_program_id, "target/deploy/ramp_solana");
+
+        // a discriminator past the last instruction fails before any account is read
+        mollusk.process_and_validate_instruction(
+            &Instruction::new_with_bytes(ramp_program_id, &[255, 0, 0], vec![]),
+            &[],
+            &[Check::err(RampError::UnknownInstruction.into())]
+        );
+    }
+
+    // Borsh bytes for a value of an IDL type, every integer byte set to 1 and every collection or
+    // option holding one element, so decoding them exercises each field of the layout
+    fn idl_sample(ty: &serde_json::Value, types: &HashMap<String, serde_json::Value>) -> Vec<u8> {
+        if let Some(name) = ty.as_str() {
+            return match name {
+                "bool" | "u8" => vec![1],
+                "u16" => vec![1; 2],
+                "u32" => vec![1; 4],
+                "u64" | "i64" => vec![1; 8],
+                "u128" => vec![1; 16],
+                "string" => vec![1, 0, 0, 0, b'a'],
+                "publicKey" => vec![7; 32],
+                _ => panic!("unknown IDL type {}", name),
+            };
+        }
+        if let Some(inner) = ty.get("vec") {
+            return [vec![1, 0, 0, 0], idl_sample(inner, types)].concat();
+        }
+        if let Some(inner) = ty.get("option") {
+            return [vec![1], idl_sample(inner, types)].concat();
+        }
+        if let Some(array) = ty.get("array") {
+            return idl_sample(&array[0], types).repeat(array[1].as_u64().unwrap() as usize);
+        }
+        let name = ty["defined"].as_str().unwrap();
+        let definition = &types[name];
+        match definition["kind"].as_str().unwrap() {
+            "struct" => definition["fields"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .flat_map(|field| idl_sample(&field["type"], types))
+                .collect(),
+            _ => vec![0],
+        }
+    }
+
+    #[test]
+    fn test_idl_matches_code() {
+        use borsh::BorshDeserialize;
+        use crate::{idl, models::RampDeposit, state::RampState};
+
+        let idl = idl::generate();
+        let committed: serde_json::Value = serde_json::from_str(include_str!("../idl/ramp_solana.json")).unwrap();
+        assert!(idl == committed, "idl/ramp_solana.json is out of date, run `cargo run --example generate_idl`");
+
+        let mut types = HashMap::new();
+        for definition in idl["types"].as_array().unwrap().iter().chain(idl["accounts"].as_array().unwrap()) {
+            types.insert(definition["name"].as_str().unwrap().to_string(), definition["type"].clone());
+        }
+
+        // bytes laid out from the IDL must decode to the instruction it names and encode back unchanged
+        let instructions = idl["instructions"].as_array().unwrap();
+        assert_eq!(instructions.len(), processors::Instruction::LAST_DISCRIMINATOR as usize + 1);
+        for instruction in instructions {
+            let name = instruction["name"].as_str().unwrap();
+            let mut bytes = vec![instruction["discriminant"]["value"].as_u64().unwrap() as u8];
+            for arg in instruction["args"].as_array().unwrap() {
+                bytes.extend(idl_sample(&arg["type"], &types));
+            }
+            let decoded = processors::RampInstruction::unpack(&bytes).unwrap();
+            let debug = format!("{:?}", decoded.instruction).to_lowercase();
+            assert!(debug.starts_with(&format!("{}(", name.to_lowercase())), "{} decoded as {}", name, debug);
+            assert_eq!(borsh::to_vec(&decoded).unwrap(), bytes, "{}", name);
+        }
+
+        let sample = |name: &str| idl_sample(&serde_json::json!({ "defined": name }), &types);
+        let ramp_state = sample("RampState");
+        assert_eq!(ramp_state.len(), RampState::LEN);
+        assert!(RampState::load(&ramp_state).is_ok());
+        let receipt = sample("DepositReceipt");
+        assert_eq!(borsh::to_vec(&DepositReceipt::try_from_slice(&receipt).unwrap()).unwrap(), receipt);
+        let access_entry = sample("AccessEntry");
+        assert_eq!(borsh::to_vec(&AccessEntry::try_from_slice(&access_entry).unwrap()).unwrap(), access_entry);
+
+        let event = &idl["events"][0];
+        assert_eq!(event["name"], "RampDeposit");
+        let deposit: Vec<u8> = event["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|field| idl_sample(&field["type"], &types))
+            .collect();
+        assert_eq!(borsh::to_vec(&RampDeposit::try_from_slice(&deposit).unwrap()).unwrap(), deposit);
+
+        let errors = idl["errors"].as_array().unwrap();
+        for (code, error) in errors.iter().enumerate() {
+            assert_eq!(error["code"].as_u64().unwrap(), code as u64);
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,5 @@
 pub mod off_ramp_models;
+pub mod on_ramp_models;
 
 pub use off_ramp_models::*;
+pub use on_ramp_models::*;
@@ -1,32 +1,187 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::pubkey::Pubkey;
+use crate::state::{AccessStatus, Beneficiary, FeeTier, ReceiptStatus};
 
+fn pad_code<const N: usize>(code: &str) -> [u8; N] {
+    let mut padded = [0u8; N];
+    let len = code.len().min(N);
+    padded[..len].copy_from_slice(&code.as_bytes()[..len]);
+    padded
+}
 
-#[derive(BorshDeserialize, BorshSerialize, Debug)]
-pub enum Region {
-    KEN,
-    NGA,
-    UGA,
-    RWA,
-    GHN,
-    EGY,
+// Region code (ISO 3166 alpha-3, e.g. KEN) registered by the ramp owner
+#[repr(transparent)]
+#[derive(BorshDeserialize, BorshSerialize, Pod, Zeroable, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Region(pub [u8; 3]);
+
+impl Region {
+    pub fn new(code: &str) -> Self {
+        Self(pad_code(code))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == [0u8; 3]
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Debug)]
-pub enum Medium {
-    Primary,
-    Secondary,
-    Tertiary,
+// Payment medium code (e.g. MPESA, AIRTEL), zero padded to 8 bytes
+#[repr(transparent)]
+#[derive(BorshDeserialize, BorshSerialize, Pod, Zeroable, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Medium(pub [u8; 8]);
+
+impl Medium {
+    pub fn new(code: &str) -> Self {
+        Self(pad_code(code))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == [0u8; 8]
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Debug)]
 pub struct RampDeposit {
     pub asset: Pubkey,
     pub asset_name: String,
-    pub amount: u64,
+    pub asset_symbol: String,
+    pub decimals: u8,
+    pub gross_amount: u64,
+    pub fee: u128,
+    pub net_amount: u64,
     pub sender: Pubkey,
     pub medium: Medium,
     pub region: Region,
     pub data: Vec<u8>,
+    pub reference: [u8; 32],
+    pub sequence: u64,
+}
+
+crate::idl::idl_struct!(RampDeposit {
+    asset: Pubkey,
+    asset_name: String,
+    asset_symbol: String,
+    decimals: u8,
+    gross_amount: u64,
+    fee: u128,
+    net_amount: u64,
+    sender: Pubkey,
+    medium: Medium,
+    region: Region,
+    data: Vec<u8>,
+    reference: [u8; 32],
+    sequence: u64,
+});
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampRefund {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub recipient: Pubkey,
+    pub gross_amount: u64,
+    pub fee: u128,
+    // amount paid back, the gross amount when the fee was refunded
+    pub amount: u64,
+    pub fee_refunded: bool,
+    pub reference: [u8; 32],
+    pub sequence: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampEscrow {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub sender: Pubkey,
+    pub gross_amount: u64,
+    pub fee: u128,
+    pub net_amount: u64,
+    pub status: ReceiptStatus,
+    pub expires_at: i64,
+    pub reference: [u8; 32],
+    pub sequence: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampAssetPaused {
+    pub asset: Pubkey,
+    pub deposits_paused: bool,
+    pub withdrawals_paused: bool,
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampAssetWindDown {
+    pub asset: Pubkey,
+    pub winding_down: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampAssetRemoved {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    // revenue swept to the vault or the revenue split before the liquidity was returned
+    pub revenue: u64,
+    pub liquidity: u64,
+    pub recipient: Pubkey,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct ClosedAsset {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    // revenue swept to the vault before the liquidity was returned
+    pub revenue: u64,
+    pub liquidity: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampClosed {
+    pub owner: Pubkey,
+    pub recipient: Pubkey,
+    pub vault: Pubkey,
+    pub assets: Vec<ClosedAsset>,
+    pub native_revenue: u64,
+    pub native_liquidity: u64,
+    // lamports of the closed token accounts, SOL vault and state account returned to the owner
+    pub rent_returned: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampAccessListUpdate {
+    // None when the addresses were removed from the lists
+    pub status: Option<AccessStatus>,
+    pub addresses: Vec<Pubkey>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampFeeScheduleChanged {
+    pub asset: Pubkey,
+    pub tiers: Vec<FeeTier>,
+    pub min_fee: Option<u64>,
+    pub max_fee: Option<u64>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampRevenueSplitChanged {
+    // empty when the split was cleared and revenue goes to the vault again
+    pub beneficiaries: Vec<Beneficiary>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy)]
+pub struct RevenueShare {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampRevenueWithdrawn {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub amount: u64,
+    pub shares: Vec<RevenueShare>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampLowWaterMarkChanged {
+    pub asset: Pubkey,
+    pub low_water_mark: u64,
+}
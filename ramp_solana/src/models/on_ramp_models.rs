@@ -0,0 +1,111 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Payout authorization signed off-chain by the ramp's voucher signer, the signed message
+// is the borsh encoding of this struct
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawVoucher {
+    pub ramp: Pubkey,
+    // token account for SPL payouts, wallet for native payouts
+    pub recipient: Pubkey,
+    // mint, or the default pubkey for native payouts
+    pub asset: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub expires_at: i64,
+}
+
+crate::idl::idl_struct!(WithdrawVoucher {
+    ramp: Pubkey,
+    recipient: Pubkey,
+    asset: Pubkey,
+    amount: u64,
+    nonce: u64,
+    expires_at: i64,
+});
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampVoucherWithdraw {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy)]
+pub struct BatchPayout {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+crate::idl::idl_struct!(BatchPayout { recipient: Pubkey, amount: u64 });
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutStatus {
+    Paid,
+    // recipient is not an initialized, unfrozen token account of the batch mint
+    InvalidAccount,
+    // recipient wallet is denied, or not allowlisted while allowlist mode is on
+    Restricted,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct BatchPayoutResult {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub status: PayoutStatus,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampBatchWithdraw {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub total_paid: u64,
+    pub results: Vec<BatchPayoutResult>,
+}
+
+// Logged when a withdrawal takes the available liquidity from at or above the low-water mark to below it
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampLiquidityLow {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub available: u64,
+    pub low_water_mark: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampLiquidityFunded {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampLiquidityDefunded {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    // token account for SPL liquidity, wallet for SOL
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+// Logged instead of a transfer when the recipient has no token account for the mint
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampCreditCreated {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    // the recipient's outstanding credit for the mint, including this payout
+    pub credit: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RampCreditClaimed {
+    pub asset: Pubkey,
+    pub decimals: u8,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
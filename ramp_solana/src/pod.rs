@@ -0,0 +1,70 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
+use std::fmt;
+
+// Little endian integers and bools with an alignment of 1, so RampState has no padding and can be
+// cast straight from the account data. Their Borsh encoding is the same as the plain types, which
+// keeps both the account layout and the instruction wire format unchanged
+macro_rules! pod_int {
+    ($name:ident, $int:ty) => {
+        #[repr(transparent)]
+        #[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name([u8; std::mem::size_of::<$int>()]);
+
+        impl $name {
+            pub fn get(&self) -> $int {
+                <$int>::from_le_bytes(self.0)
+            }
+        }
+
+        impl From<$int> for $name {
+            fn from(value: $int) -> Self {
+                Self(value.to_le_bytes())
+            }
+        }
+
+        impl From<$name> for $int {
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.get().fmt(f)
+            }
+        }
+    };
+}
+
+pod_int!(PodU16, u16);
+pod_int!(PodU64, u64);
+pod_int!(PodU128, u128);
+
+#[repr(transparent)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PodBool(u8);
+
+impl PodBool {
+    pub fn get(&self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl From<bool> for PodBool {
+    fn from(value: bool) -> Self {
+        Self(value as u8)
+    }
+}
+
+impl From<PodBool> for bool {
+    fn from(value: PodBool) -> Self {
+        value.get()
+    }
+}
+
+impl fmt::Debug for PodBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
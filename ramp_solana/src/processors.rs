@@ -13,22 +13,84 @@ use {
             set_native_fee_percentage,
             set_owner,
             off_ramp_deposit_token22,
+            add_region,
+            add_region_medium,
+            set_region_enabled,
+            set_region_fee,
+            refund_deposit,
+            refund_deposit_native,
+            escrow_deposit,
+            escrow_deposit_native,
+            release_escrow,
+            release_escrow_native,
+            reclaim_escrow,
+            reclaim_escrow_native,
+            set_voucher_signer,
+            voucher_withdraw,
+            voucher_withdraw_native,
+            batch_onramp_withdraw,
+            set_asset_paused,
+            update_access_list,
+            set_allowlist_enabled,
+            set_fee_schedule,
+            quote_fee,
+            set_revenue_split,
+            withdraw_revenue,
+            withdraw_revenue_native,
+            get_available_liquidity,
+            set_low_water_mark,
+            fund_asset,
+            fund_asset_native,
+            defund_asset,
+            defund_asset_native,
+            set_asset_wind_down,
+            close_ramp,
+            claim_credit,
+            migrate_ramp,
             AddAssetsInstruction,
+            AddRegionInstruction,
+            AddRegionMediumInstruction,
+            EscrowDepositInstruction,
             InitializeProgramInstruction,
             OffRampDepositInstruction,
             OffRampDepositNativeInstruction,
             OnRampWithdrawInstruction,
             OnRampWithdrawNativeInstruction,
+            RefundDepositInstruction,
             RemoveAssetsInstruction,
             SetActiveInstruction,
             SetAssetFeeInstruction,
             SetNativeFeePercentageInstruction,
             SetOwnerInstruction,
+            SetRegionEnabledInstruction,
+            SetRegionFeeInstruction,
+            SettleEscrowInstruction,
+            SetVoucherSignerInstruction,
+            VoucherWithdrawInstruction,
+            BatchOnRampWithdrawInstruction,
+            SetAssetPausedInstruction,
+            SetAllowlistEnabledInstruction,
+            UpdateAccessListInstruction,
+            QuoteFeeInstruction,
+            SetFeeScheduleInstruction,
+            SetRevenueSplitInstruction,
+            WithdrawRevenueInstruction,
+            WithdrawRevenueNativeInstruction,
+            GetAvailableLiquidityInstruction,
+            SetLowWaterMarkInstruction,
+            DefundAssetInstruction,
+            FundAssetInstruction,
+            SetAssetWindDownInstruction,
+            CloseRampInstruction,
+            ClaimCreditInstruction,
+            MigrateRampInstruction,
         },
+        crate::errors::RampError,
         borsh::{BorshDeserialize, BorshSerialize},
         solana_program::{
             account_info::AccountInfo,
             entrypoint::ProgramResult,
+            program_error::ProgramError,
             pubkey::Pubkey
         },
     };
@@ -37,21 +99,76 @@ pub struct RampInstruction {
     pub instruction: Instruction,
 }
 
+impl RampInstruction {
+    pub fn unpack(instruction_data: &[u8]) -> Result<Self, ProgramError> {
+        match instruction_data.first() {
+            Some(discriminator) if *discriminator <= Instruction::LAST_DISCRIMINATOR => {
+                Ok(Self::try_from_slice(instruction_data)?)
+            },
+            _ => Err(RampError::UnknownInstruction.into()),
+        }
+    }
+}
+
+// The first byte of the instruction data is the discriminator. Values are frozen once deployed:
+// new instructions take the next free value and existing ones are never renumbered or reused,
+// whatever their position in the enum
+#[repr(u8)]
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[borsh(use_discriminant = true)]
 pub enum Instruction {
-    SetActive(SetActiveInstruction),
-    RemoveAssets(RemoveAssetsInstruction),
-    SetOwner(SetOwnerInstruction),
-    AddAssets(AddAssetsInstruction),
-    AddAssets2022(AddAssetsInstruction),
-    InitializeProgram(InitializeProgramInstruction),
-    OffRampDepositToken22(OffRampDepositInstruction),
-    OffRampDeposit(OffRampDepositInstruction),
-    OnRampWithdraw(OnRampWithdrawInstruction),
-    OffRampDepositNative(OffRampDepositNativeInstruction),
-    OnRampWithdrawNative(OnRampWithdrawNativeInstruction),
-    SetNativeFeePercentage(SetNativeFeePercentageInstruction),
-    SetAssetFee(SetAssetFeeInstruction)
+    SetActive(SetActiveInstruction) = 0,
+    RemoveAssets(RemoveAssetsInstruction) = 1,
+    SetOwner(SetOwnerInstruction) = 2,
+    AddAssets(AddAssetsInstruction) = 3,
+    AddAssets2022(AddAssetsInstruction) = 4,
+    InitializeProgram(InitializeProgramInstruction) = 5,
+    OffRampDepositToken22(OffRampDepositInstruction) = 6,
+    OffRampDeposit(OffRampDepositInstruction) = 7,
+    OnRampWithdraw(OnRampWithdrawInstruction) = 8,
+    OffRampDepositNative(OffRampDepositNativeInstruction) = 9,
+    OnRampWithdrawNative(OnRampWithdrawNativeInstruction) = 10,
+    SetNativeFeePercentage(SetNativeFeePercentageInstruction) = 11,
+    SetAssetFee(SetAssetFeeInstruction) = 12,
+    AddRegion(AddRegionInstruction) = 13,
+    AddRegionMedium(AddRegionMediumInstruction) = 14,
+    SetRegionEnabled(SetRegionEnabledInstruction) = 15,
+    SetRegionFee(SetRegionFeeInstruction) = 16,
+    RefundDeposit(RefundDepositInstruction) = 17,
+    RefundDepositNative(RefundDepositInstruction) = 18,
+    EscrowDeposit(EscrowDepositInstruction) = 19,
+    EscrowDepositNative(EscrowDepositInstruction) = 20,
+    ReleaseEscrow(SettleEscrowInstruction) = 21,
+    ReleaseEscrowNative(SettleEscrowInstruction) = 22,
+    ReclaimEscrow(SettleEscrowInstruction) = 23,
+    ReclaimEscrowNative(SettleEscrowInstruction) = 24,
+    SetVoucherSigner(SetVoucherSignerInstruction) = 25,
+    VoucherWithdraw(VoucherWithdrawInstruction) = 26,
+    VoucherWithdrawNative(VoucherWithdrawInstruction) = 27,
+    BatchOnRampWithdraw(BatchOnRampWithdrawInstruction) = 28,
+    SetAssetPaused(SetAssetPausedInstruction) = 29,
+    UpdateAccessList(UpdateAccessListInstruction) = 30,
+    SetAllowlistEnabled(SetAllowlistEnabledInstruction) = 31,
+    SetFeeSchedule(SetFeeScheduleInstruction) = 32,
+    QuoteFee(QuoteFeeInstruction) = 33,
+    SetRevenueSplit(SetRevenueSplitInstruction) = 34,
+    WithdrawRevenue(WithdrawRevenueInstruction) = 35,
+    WithdrawRevenueNative(WithdrawRevenueNativeInstruction) = 36,
+    GetAvailableLiquidity(GetAvailableLiquidityInstruction) = 37,
+    SetLowWaterMark(SetLowWaterMarkInstruction) = 38,
+    FundAsset(FundAssetInstruction) = 39,
+    FundAssetNative(FundAssetInstruction) = 40,
+    DefundAsset(DefundAssetInstruction) = 41,
+    DefundAssetNative(DefundAssetInstruction) = 42,
+    SetAssetWindDown(SetAssetWindDownInstruction) = 43,
+    CloseRamp(CloseRampInstruction) = 44,
+    ClaimCredit(ClaimCreditInstruction) = 45,
+    MigrateRamp(MigrateRampInstruction) = 46,
+}
+
+impl Instruction {
+    // discriminators are contiguous from 0, bump this when appending an instruction
+    pub const LAST_DISCRIMINATOR: u8 = 46;
 }
 
 pub fn process_instruction(
@@ -59,7 +176,7 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let instruction = RampInstruction::try_from_slice(instruction_data)?;
+    let instruction = RampInstruction::unpack(instruction_data)?;
 
     match instruction.instruction {
         Instruction::SetActive(args) => {
@@ -152,6 +269,244 @@ pub fn process_instruction(
                 accounts,
                 args
             )
+        },
+        Instruction::AddRegion(args) => {
+            add_region::add_region(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::AddRegionMedium(args) => {
+            add_region_medium::add_region_medium(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetRegionEnabled(args) => {
+            set_region_enabled::set_region_enabled(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetRegionFee(args) => {
+            set_region_fee::set_region_fee(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::RefundDeposit(args) => {
+            refund_deposit::refund_deposit(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::RefundDepositNative(args) => {
+            refund_deposit_native::refund_deposit_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::EscrowDeposit(args) => {
+            escrow_deposit::escrow_deposit(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::EscrowDepositNative(args) => {
+            escrow_deposit_native::escrow_deposit_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::ReleaseEscrow(args) => {
+            release_escrow::release_escrow(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::ReleaseEscrowNative(args) => {
+            release_escrow_native::release_escrow_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::ReclaimEscrow(args) => {
+            reclaim_escrow::reclaim_escrow(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::ReclaimEscrowNative(args) => {
+            reclaim_escrow_native::reclaim_escrow_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetVoucherSigner(args) => {
+            set_voucher_signer::set_voucher_signer(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::VoucherWithdraw(args) => {
+            voucher_withdraw::voucher_withdraw(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::VoucherWithdrawNative(args) => {
+            voucher_withdraw_native::voucher_withdraw_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::BatchOnRampWithdraw(args) => {
+            batch_onramp_withdraw::batch_onramp_withdraw(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetAssetPaused(args) => {
+            set_asset_paused::set_asset_paused(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::UpdateAccessList(args) => {
+            update_access_list::update_access_list(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetAllowlistEnabled(args) => {
+            set_allowlist_enabled::set_allowlist_enabled(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetFeeSchedule(args) => {
+            set_fee_schedule::set_fee_schedule(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::QuoteFee(args) => {
+            quote_fee::quote_fee(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetRevenueSplit(args) => {
+            set_revenue_split::set_revenue_split(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::WithdrawRevenue(args) => {
+            withdraw_revenue::withdraw_revenue(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::WithdrawRevenueNative(args) => {
+            withdraw_revenue_native::withdraw_revenue_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::GetAvailableLiquidity(args) => {
+            get_available_liquidity::get_available_liquidity(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetLowWaterMark(args) => {
+            set_low_water_mark::set_low_water_mark(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::FundAsset(args) => {
+            fund_asset::fund_asset(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::FundAssetNative(args) => {
+            fund_asset_native::fund_asset_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::DefundAsset(args) => {
+            defund_asset::defund_asset(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::DefundAssetNative(args) => {
+            defund_asset_native::defund_asset_native(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::SetAssetWindDown(args) => {
+            set_asset_wind_down::set_asset_wind_down(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::CloseRamp(args) => {
+            close_ramp::close_ramp(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::ClaimCredit(args) => {
+            claim_credit::claim_credit(
+                program_id,
+                accounts,
+                args
+            )
+        },
+        Instruction::MigrateRamp(args) => {
+            migrate_ramp::migrate_ramp(
+                program_id,
+                accounts,
+                args
+            )
         }
     }
 }
\ No newline at end of file
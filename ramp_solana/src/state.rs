@@ -1,12 +1,34 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use bytemuck::{Pod, Zeroable};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    errors::RampError,
+    models::{Medium, Region},
+    pod::{PodBool, PodU128, PodU16, PodU64},
+};
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
+pub const MAX_REGION_MEDIUMS: usize = 5;
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const NONCE_SEED: &[u8] = b"nonce";
+pub const ACCESS_SEED: &[u8] = b"access";
+pub const SOL_VAULT_SEED: &[u8] = b"sol_vault";
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const CREDIT_SEED: &[u8] = b"credit";
+pub const NATIVE_DECIMALS: u8 = 9;
+pub const MAX_FEE_TIERS: usize = 5;
+pub const MAX_FEE_BPS: u16 = 10_000;
+pub const MAX_BENEFICIARIES: usize = 5;
+pub const TOTAL_WEIGHT_BPS: u16 = 10_000;
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy)]
 pub struct AssetEntry {
     pub asset: Pubkey,
     pub info: AssetInfo,
 }
 
+crate::idl::idl_struct!(AssetEntry { asset: Pubkey, info: AssetInfo });
+
 impl Default for AssetEntry {
     fn default() -> Self {
         Self {
@@ -23,58 +45,373 @@ impl AssetEntry {
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeTier {
+    // smallest deposit amount the bracket applies to
+    pub threshold: PodU64,
+    pub fee_bps: PodU16,
+}
+
+crate::idl::idl_struct!(FeeTier { threshold: PodU64, fee_bps: PodU16 });
+
+impl FeeTier {
+    pub fn new(threshold: u64, fee_bps: u16) -> Self {
+        Self {
+            threshold: threshold.into(),
+            fee_bps: fee_bps.into(),
+        }
+    }
+}
+
+// Fee brackets sorted by ascending threshold, plus fee caps where 0 disables the cap
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub tier_count: u8,
+    pub tiers: [FeeTier; MAX_FEE_TIERS],
+    pub min_fee: PodU64,
+    pub max_fee: PodU64,
+}
+
+crate::idl::idl_struct!(FeeSchedule {
+    tier_count: u8,
+    tiers: [FeeTier; MAX_FEE_TIERS],
+    min_fee: PodU64,
+    max_fee: PodU64,
+});
+
+impl FeeSchedule {
+    pub fn new(tiers: &[FeeTier], min_fee: Option<u64>, max_fee: Option<u64>) -> Result<Self, &'static str> {
+        if tiers.len() > MAX_FEE_TIERS {
+            return Err("Too many fee tiers");
+        }
+        if tiers.iter().any(|tier| tier.fee_bps.get() > MAX_FEE_BPS) {
+            return Err("Fee tier above 100%");
+        }
+        if tiers.windows(2).any(|pair| pair[0].threshold.get() >= pair[1].threshold.get()) {
+            return Err("Fee tiers must have ascending thresholds");
+        }
+        if let (Some(min_fee), Some(max_fee)) = (min_fee, max_fee) {
+            if min_fee > max_fee {
+                return Err("Minimum fee above maximum fee");
+            }
+        }
+        let mut schedule = Self {
+            tier_count: tiers.len() as u8,
+            min_fee: min_fee.unwrap_or(0).into(),
+            max_fee: max_fee.unwrap_or(0).into(),
+            ..Self::default()
+        };
+        schedule.tiers[..tiers.len()].copy_from_slice(tiers);
+        Ok(schedule)
+    }
+
+    // bps of the highest bracket the amount reaches, None below the first threshold
+    pub fn tier_bps(&self, amount: u64) -> Option<u16> {
+        self.tiers[..self.tier_count as usize]
+            .iter()
+            .rev()
+            .find(|tier| amount >= tier.threshold.get())
+            .map(|tier| tier.fee_bps.get())
+    }
+
+    pub fn apply_caps(&self, fee: u128) -> u128 {
+        let fee = fee.max(self.min_fee.get() as u128);
+        if self.max_fee.get() > 0 {
+            fee.min(self.max_fee.get() as u128)
+        } else {
+            fee
+        }
+    }
+}
+
+// Fee on a deposit. A region override wins, then the schedule bracket the amount falls in,
+// then the flat percentage. The schedule caps apply on top and the fee never exceeds the amount
+pub fn compute_fee(fee_percentage: u128, schedule: &FeeSchedule, region_fee_percentage: Option<u128>, amount: u64) -> u128 {
+    let fee = match (region_fee_percentage, schedule.tier_bps(amount)) {
+        (Some(percentage), _) => (amount as u128) * percentage / 100,
+        (None, Some(bps)) => (amount as u128) * (bps as u128) / (MAX_FEE_BPS as u128),
+        (None, None) => (amount as u128) * fee_percentage / 100,
+    };
+    schedule.apply_caps(fee).min(amount as u128)
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Beneficiary {
+    pub address: Pubkey,
+    pub weight_bps: PodU16,
+}
+
+crate::idl::idl_struct!(Beneficiary { address: Pubkey, weight_bps: PodU16 });
+
+impl Beneficiary {
+    pub fn new(address: Pubkey, weight_bps: u16) -> Self {
+        Self {
+            address,
+            weight_bps: weight_bps.into(),
+        }
+    }
+}
+
+// Revenue split across beneficiaries whose weights sum to TOTAL_WEIGHT_BPS. Without
+// beneficiaries all revenue goes to the vault address
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RevenueSplit {
+    pub count: u8,
+    pub beneficiaries: [Beneficiary; MAX_BENEFICIARIES],
+}
+
+crate::idl::idl_struct!(RevenueSplit { count: u8, beneficiaries: [Beneficiary; MAX_BENEFICIARIES] });
+
+impl RevenueSplit {
+    pub fn new(beneficiaries: &[Beneficiary]) -> Result<Self, &'static str> {
+        if beneficiaries.len() > MAX_BENEFICIARIES {
+            return Err("Too many beneficiaries");
+        }
+        if beneficiaries.iter().any(|beneficiary| beneficiary.weight_bps.get() == 0 || beneficiary.address == Pubkey::default()) {
+            return Err("Invalid beneficiary");
+        }
+        if beneficiaries.iter().enumerate().any(|(i, beneficiary)| {
+            beneficiaries[..i].iter().any(|other| other.address == beneficiary.address)
+        }) {
+            return Err("Duplicate beneficiary");
+        }
+        let total_weight: u32 = beneficiaries.iter().map(|beneficiary| beneficiary.weight_bps.get() as u32).sum();
+        if !beneficiaries.is_empty() && total_weight != TOTAL_WEIGHT_BPS as u32 {
+            return Err("Weights must sum to 10000 bps");
+        }
+        let mut split = Self {
+            count: beneficiaries.len() as u8,
+            ..Self::default()
+        };
+        split.beneficiaries[..beneficiaries.len()].copy_from_slice(beneficiaries);
+        Ok(split)
+    }
+
+    // beneficiaries revenue is paid to, the vault alone when no split is configured
+    pub fn recipients(&self, vault_address: Pubkey) -> Vec<Beneficiary> {
+        if self.count == 0 {
+            return vec![Beneficiary::new(vault_address, TOTAL_WEIGHT_BPS)];
+        }
+        self.beneficiaries[..self.count as usize].to_vec()
+    }
+}
+
+// Floors each beneficiary's share and gives the rounding dust to the first one, so the shares
+// always add up to `amount`
+pub fn split_amount(beneficiaries: &[Beneficiary], amount: u64) -> Vec<u64> {
+    let mut shares: Vec<u64> = beneficiaries
+        .iter()
+        .map(|beneficiary| ((amount as u128) * (beneficiary.weight_bps.get() as u128) / (TOTAL_WEIGHT_BPS as u128)) as u64)
+        .collect();
+    let distributed: u64 = shares.iter().sum();
+    if let Some(first) = shares.first_mut() {
+        *first += amount - distributed;
+    }
+    shares
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy, Default)]
+pub struct MediumEntry {
+    pub medium: Medium,
+    pub is_enabled: PodBool,
+}
+
+crate::idl::idl_struct!(MediumEntry { medium: Medium, is_enabled: PodBool });
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy, Default)]
+pub struct RegionEntry {
+    pub region: Region,
+    pub is_enabled: PodBool,
+    pub has_fee_override: PodBool,
+    pub fee_percentage: PodU128,
+    pub mediums: [MediumEntry; MAX_REGION_MEDIUMS],
+}
+
+crate::idl::idl_struct!(RegionEntry {
+    region: Region,
+    is_enabled: PodBool,
+    has_fee_override: PodBool,
+    fee_percentage: PodU128,
+    mediums: [MediumEntry; MAX_REGION_MEDIUMS],
+});
+
+impl RegionEntry {
+    pub fn is_allowed_medium(&self, medium: &Medium) -> bool {
+        if medium.is_empty() {
+            return false;
+        }
+        self.mediums.iter().any(|entry| entry.medium == *medium && entry.is_enabled.get())
+    }
+
+    pub fn add_medium(&mut self, medium: Medium) -> Result<(), &'static str> {
+        if self.mediums.iter().any(|entry| entry.medium == medium) {
+            return Err("Medium already exists");
+        }
+        match self.mediums.iter_mut().find(|entry| entry.medium.is_empty()) {
+            Some(entry) => {
+                *entry = MediumEntry {
+                    medium,
+                    is_enabled: true.into(),
+                };
+            }
+            None => {
+                return Err("No empty slot found");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_medium_enabled(&mut self, medium: &Medium, is_enabled: bool) -> Result<(), &'static str> {
+        match self.mediums.iter_mut().find(|entry| entry.medium == *medium) {
+            Some(entry) => {
+                entry.is_enabled = is_enabled.into();
+                Ok(())
+            }
+            None => Err("Medium not found"),
+        }
+    }
+
+    pub fn fee_override(&self) -> Option<u128> {
+        if self.has_fee_override.get() {
+            Some(self.fee_percentage.get())
+        } else {
+            None
+        }
+    }
+}
+
+// Fixed layout without padding, handlers cast the account data in place with RampState::load
+// and RampState::load_mut instead of deserializing it. The bytes match the Borsh encoding
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, Pod, Zeroable, Debug, Clone, Copy)]
 pub struct RampState {
     pub owner: Pubkey,
-    pub is_active: bool,
-    pub native_fee_percentage: u128,
-    pub native_revenue: u128,
+    pub is_active: PodBool,
+    pub native_fee_percentage: PodU128,
+    pub native_revenue: PodU128,
     pub vault_address: Pubkey,
     pub asset_entries: [AssetEntry; 10],
+    pub region_entries: [RegionEntry; 10],
+    pub deposit_sequence: PodU64,
+    // key and bump the ramp PDA was derived with, used for PDA signed transfers
+    pub creator: Pubkey,
+    pub bump: u8,
+    // ed25519 key whose vouchers authorize withdrawals, default when vouchers are disabled
+    pub voucher_signer: Pubkey,
+    pub native_deposits_paused: PodBool,
+    pub native_withdrawals_paused: PodBool,
+    // when set only addresses with an Allowed access entry can deposit or be paid
+    pub allowlist_enabled: PodBool,
+    pub native_fee_schedule: FeeSchedule,
+    pub revenue_split: RevenueSplit,
+    pub native_low_water_mark: PodU64,
+    // escrowed deposits not yet released or reclaimed, the ramp cannot be closed while any is open
+    pub open_escrows: PodU64,
+    // credit entries not yet claimed by their recipient, the ramp cannot be closed while any is open
+    pub open_credits: PodU64,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy)]
+crate::idl::idl_struct!(RampState {
+    owner: Pubkey,
+    is_active: PodBool,
+    native_fee_percentage: PodU128,
+    native_revenue: PodU128,
+    vault_address: Pubkey,
+    asset_entries: [AssetEntry; 10],
+    region_entries: [RegionEntry; 10],
+    deposit_sequence: PodU64,
+    creator: Pubkey,
+    bump: u8,
+    voucher_signer: Pubkey,
+    native_deposits_paused: PodBool,
+    native_withdrawals_paused: PodBool,
+    allowlist_enabled: PodBool,
+    native_fee_schedule: FeeSchedule,
+    revenue_split: RevenueSplit,
+    native_low_water_mark: PodU64,
+    open_escrows: PodU64,
+    open_credits: PodU64,
+});
+
+#[repr(C)]
+#[derive(BorshDeserialize, BorshSerialize, Pod, Zeroable, Debug, Clone, Copy)]
 pub struct AssetInfo {
-    pub asset_fee_percentage: u128,
-    pub asset_revenue: u128,
+    pub asset_fee_percentage: PodU128,
+    pub asset_revenue: PodU128,
+    pub deposits_paused: PodBool,
+    pub withdrawals_paused: PodBool,
+    pub fee_schedule: FeeSchedule,
+    // withdrawals leaving less available liquidity than this log a RampLiquidityLow event, 0 disables it
+    pub low_water_mark: PodU64,
+    // set ahead of delisting, new deposits are rejected while payouts and escrow settlement continue
+    pub winding_down: PodBool,
 }
 
+crate::idl::idl_struct!(AssetInfo {
+    asset_fee_percentage: PodU128,
+    asset_revenue: PodU128,
+    deposits_paused: PodBool,
+    withdrawals_paused: PodBool,
+    fee_schedule: FeeSchedule,
+    low_water_mark: PodU64,
+    winding_down: PodBool,
+});
+
 impl AssetInfo {
     pub fn new(fee_percentage: u128) -> Self {
         Self {
-            asset_fee_percentage: fee_percentage,
-            asset_revenue: 0,
+            asset_fee_percentage: fee_percentage.into(),
+            ..Self::zeroed()
         }
     }
 
     pub fn add_revenue(&mut self, amount: u128) {
-        self.asset_revenue = self.asset_revenue.saturating_add(amount);
+        self.asset_revenue = self.asset_revenue.get().saturating_add(amount).into();
+    }
+
+    pub fn remove_revenue(&mut self, amount: u128) {
+        self.asset_revenue = self.asset_revenue.get().saturating_sub(amount).into();
     }
 
     pub fn get_fee_percentage(&self) -> u128 {
-        self.asset_fee_percentage
+        self.asset_fee_percentage.get()
     }
 
     #[cfg(test)]
     pub fn get_revenue(&self) -> u128 {
-        self.asset_revenue
+        self.asset_revenue.get()
     }
 }
 
 impl Default for RampState {
     fn default() -> Self {
-        Self {
-            owner: Pubkey::default(),
-            is_active: false,
-            native_fee_percentage: 0,
-            native_revenue: 0,
-            vault_address: Pubkey::default(),
-            asset_entries: [AssetEntry::default(); 10],
-        }
+        Self::zeroed()
     }
 }
 
 impl RampState {
+    pub const LEN: usize = std::mem::size_of::<RampState>();
+
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        data.get(..Self::LEN)
+            .and_then(|bytes| bytemuck::try_from_bytes(bytes).ok())
+            .ok_or_else(|| RampError::InvalidAccountState.into())
+    }
+
+    // a CPI fails while the ramp account is mutably borrowed, so handlers signing with the ramp
+    // PDA read through load and only take this borrow once their transfers are done
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        data.get_mut(..Self::LEN)
+            .and_then(|bytes| bytemuck::try_from_bytes_mut(bytes).ok())
+            .ok_or_else(|| RampError::InvalidAccountState.into())
+    }
+
     pub fn is_allowed_asset(&self, asset: &Pubkey) -> bool {
         if asset == &Pubkey::default() {
             return false;
@@ -94,13 +431,21 @@ impl RampState {
             .map(|entry| &entry.info)
     }
 
+    // Listed mints in slot order, skipping the empty slots
+    pub fn listed_assets(&self) -> Vec<Pubkey> {
+        self.asset_entries.iter()
+            .map(|entry| entry.asset)
+            .filter(|asset| *asset != Pubkey::default())
+            .collect()
+    }
+
     #[cfg(test)]
     pub fn get_assets(&self) -> Vec<Pubkey> {
         self.asset_entries.iter().map(|entry| entry.asset).collect()
     }
 
     pub fn set_active(&mut self, is_active: bool) {
-        self.is_active = is_active;
+        self.is_active = is_active.into();
     }
 
     pub fn set_new_owner(&mut self, owner: Pubkey) {
@@ -108,11 +453,80 @@ impl RampState {
     }
 
     pub fn set_native_fee_percentage(&mut self, native_fee_percentage: u128) {
-        self.native_fee_percentage = native_fee_percentage;
+        self.native_fee_percentage = native_fee_percentage.into();
     }
 
     pub fn update_native_revenue(&mut self, amount: u128) {
-        self.native_revenue = self.native_revenue.saturating_add(amount);
+        self.native_revenue = self.native_revenue.get().saturating_add(amount).into();
+    }
+
+    pub fn reduce_native_revenue(&mut self, amount: u128) {
+        self.native_revenue = self.native_revenue.get().saturating_sub(amount).into();
+    }
+
+    // the default pubkey refers to native SOL
+    pub fn is_deposit_paused(&self, asset: &Pubkey) -> bool {
+        if *asset == Pubkey::default() {
+            return self.native_deposits_paused.get();
+        }
+        self.get_asset_info_ref(asset).map(|info| info.deposits_paused.get()).unwrap_or(false)
+    }
+
+    pub fn is_withdrawal_paused(&self, asset: &Pubkey) -> bool {
+        if *asset == Pubkey::default() {
+            return self.native_withdrawals_paused.get();
+        }
+        self.get_asset_info_ref(asset).map(|info| info.withdrawals_paused.get()).unwrap_or(false)
+    }
+
+    // fee a deposit of `amount` pays, None when the asset is not listed. The default pubkey is native SOL
+    pub fn quote_fee(&self, asset: &Pubkey, amount: u64, region_fee_percentage: Option<u128>) -> Option<u128> {
+        if *asset == Pubkey::default() {
+            return Some(compute_fee(self.native_fee_percentage.get(), &self.native_fee_schedule, region_fee_percentage, amount));
+        }
+        self.get_asset_info_ref(asset)
+            .map(|info| compute_fee(info.asset_fee_percentage.get(), &info.fee_schedule, region_fee_percentage, amount))
+    }
+
+    pub fn is_winding_down(&self, asset: &Pubkey) -> bool {
+        self.get_asset_info_ref(asset).map(|info| info.winding_down.get()).unwrap_or(false)
+    }
+
+    // low-water mark of the asset's available liquidity, 0 when unset. The default pubkey is native SOL
+    pub fn low_water_mark(&self, asset: &Pubkey) -> u64 {
+        if *asset == Pubkey::default() {
+            return self.native_low_water_mark.get();
+        }
+        self.get_asset_info_ref(asset).map(|info| info.low_water_mark.get()).unwrap_or(0)
+    }
+
+    pub fn is_address_permitted(&self, status: Option<AccessStatus>) -> bool {
+        match status {
+            Some(AccessStatus::Allowed) => true,
+            Some(AccessStatus::Denied) => false,
+            None => !self.allowlist_enabled.get(),
+        }
+    }
+
+    pub fn next_deposit_sequence(&mut self) -> u64 {
+        self.deposit_sequence = self.deposit_sequence.get().saturating_add(1).into();
+        self.deposit_sequence.get()
+    }
+
+    pub fn open_escrow(&mut self) {
+        self.open_escrows = self.open_escrows.get().saturating_add(1).into();
+    }
+
+    pub fn settle_escrow(&mut self) {
+        self.open_escrows = self.open_escrows.get().saturating_sub(1).into();
+    }
+
+    pub fn open_credit(&mut self) {
+        self.open_credits = self.open_credits.get().saturating_add(1).into();
+    }
+
+    pub fn settle_credit(&mut self) {
+        self.open_credits = self.open_credits.get().saturating_sub(1).into();
     }
 
     pub fn add_asset(&mut self, asset: Pubkey, fee_percentage: u128) -> Result<(), &'static str> {
@@ -143,4 +557,162 @@ impl RampState {
         });
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn get_region_entry(&mut self, region: &Region) -> Option<&mut RegionEntry> {
+        if region.is_empty() {
+            return None;
+        }
+        self.region_entries.iter_mut().find(|entry| entry.region == *region)
+    }
+
+    pub fn is_allowed_region(&self, region: &Region, medium: &Medium) -> bool {
+        if region.is_empty() {
+            return false;
+        }
+        self.region_entries.iter()
+            .find(|entry| entry.region == *region)
+            .map(|entry| entry.is_enabled.get() && entry.is_allowed_medium(medium))
+            .unwrap_or(false)
+    }
+
+    pub fn get_region_fee_percentage(&self, region: &Region) -> Option<u128> {
+        self.region_entries.iter()
+            .find(|entry| entry.region == *region)
+            .and_then(|entry| entry.fee_override())
+    }
+
+    pub fn add_region(&mut self, region: Region, mediums: &[Medium]) -> Result<(), &'static str> {
+        if region.is_empty() || self.region_entries.iter().any(|entry| entry.region == region) {
+            return Err("Region already exists");
+        }
+        let mut region_entry = RegionEntry {
+            region,
+            is_enabled: true.into(),
+            ..RegionEntry::default()
+        };
+        for medium in mediums {
+            region_entry.add_medium(*medium)?;
+        }
+        match self.region_entries.iter_mut().find(|entry| entry.region.is_empty()) {
+            Some(entry) => {
+                *entry = region_entry;
+            }
+            None => {
+                return Err("No empty slot found");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReceiptStatus {
+    #[default]
+    Deposited,
+    Refunded,
+    // escrow deposits are held in the receipt until released to the pool or reclaimed by the sender
+    Escrowed,
+    Released,
+    Reclaimed,
+}
+
+crate::idl::idl_enum!(ReceiptStatus { Deposited, Refunded, Escrowed, Released, Reclaimed });
+
+// Stored in a PDA seeded with [b"receipt", ramp, reference], one per client order reference
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default)]
+pub struct DepositReceipt {
+    pub reference: [u8; 32],
+    pub sequence: u64,
+    pub asset: Pubkey,
+    pub sender: Pubkey,
+    pub amount: u64,
+    pub fee: u128,
+    pub status: ReceiptStatus,
+    // unix timestamp after which an escrowed deposit can be reclaimed, 0 for direct deposits
+    pub expires_at: i64,
+}
+
+crate::idl::idl_struct!(DepositReceipt {
+    reference: [u8; 32],
+    sequence: u64,
+    asset: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    fee: u128,
+    status: ReceiptStatus,
+    expires_at: i64,
+});
+
+impl DepositReceipt {
+    pub fn space() -> usize {
+        borsh::to_vec(&DepositReceipt::default()).unwrap().len()
+    }
+
+    pub fn find_address(program_id: &Pubkey, ramp: &Pubkey, reference: &[u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[RECEIPT_SEED, ramp.as_ref(), reference], program_id)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+pub fn find_nonce_address(program_id: &Pubkey, ramp: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NONCE_SEED, ramp.as_ref(), &nonce.to_le_bytes()], program_id)
+}
+
+// SOL liquidity and native revenue are held in this PDA instead of the state account
+pub fn find_sol_vault_address(program_id: &Pubkey, ramp: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SOL_VAULT_SEED, ramp.as_ref()], program_id)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccessStatus {
+    #[default]
+    Denied,
+    Allowed,
+}
+
+crate::idl::idl_enum!(AccessStatus { Denied, Allowed });
+
+// Stored in a PDA seeded with [b"access", ramp, address], removing an address closes the PDA
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default)]
+pub struct AccessEntry {
+    pub address: Pubkey,
+    pub status: AccessStatus,
+}
+
+crate::idl::idl_struct!(AccessEntry { address: Pubkey, status: AccessStatus });
+
+impl AccessEntry {
+    pub fn space() -> usize {
+        borsh::to_vec(&AccessEntry::default()).unwrap().len()
+    }
+
+    pub fn find_address(program_id: &Pubkey, ramp: &Pubkey, address: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ACCESS_SEED, ramp.as_ref(), address.as_ref()], program_id)
+    }
+}
+
+// Stored in a PDA seeded with [b"credit", ramp, recipient, mint]. Payouts to a recipient without a
+// token account for the mint are held in the PDA's associated token account until they claim them
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default)]
+pub struct CreditEntry {
+    pub recipient: Pubkey,
+    pub asset: Pubkey,
+    pub amount: u64,
+    // paid the rent of the PDA and its token account, which is returned to it on claim
+    pub payer: Pubkey,
+}
+
+crate::idl::idl_struct!(CreditEntry { recipient: Pubkey, asset: Pubkey, amount: u64, payer: Pubkey });
+
+impl CreditEntry {
+    pub fn space() -> usize {
+        borsh::to_vec(&CreditEntry::default()).unwrap().len()
+    }
+
+    pub fn find_address(program_id: &Pubkey, ramp: &Pubkey, recipient: &Pubkey, asset: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CREDIT_SEED, ramp.as_ref(), recipient.as_ref(), asset.as_ref()], program_id)
+    }
+}
@@ -0,0 +1,664 @@
+use crate::{
+    errors::RampError,
+    models::{RampCreditCreated, RampLiquidityLow, RevenueShare, WithdrawVoucher},
+    state::{
+        find_nonce_address, find_sol_vault_address, split_amount, AccessEntry, AccessStatus, CreditEntry,
+        DepositReceipt, RampState, CREDIT_SEED, METADATA_SEED, NONCE_SEED, RECEIPT_SEED, SOL_VAULT_SEED,
+    },
+};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    ed25519_program,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+        Sysvar,
+    },
+};
+use solana_system_interface::instruction::{allocate, assign, create_account, transfer};
+use mpl_token_metadata::accounts::Metadata;
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022_interface::{
+    extension::StateWithExtensions,
+    instruction as token_instruction,
+    state::{Account, Mint},
+};
+
+// Creates a program owned PDA, tolerating lamports sent to the address ahead of time
+pub fn create_pda_account<'a>(
+    program_id: &Pubkey,
+    payer_account: &AccountInfo<'a>,
+    pda_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent_required = Rent::get()
+        .map_err(|_| RampError::RentError)?
+        .minimum_balance(space);
+    let current_lamports = pda_account.lamports();
+    if current_lamports == 0 {
+        return invoke_signed(
+            &create_account(
+                payer_account.key,
+                pda_account.key,
+                rent_required,
+                space as u64,
+                program_id,
+            ),
+            &[
+                payer_account.clone(),
+                pda_account.clone(),
+                system_program.clone(),
+            ],
+            &[seeds],
+        );
+    }
+    if current_lamports < rent_required {
+        invoke(
+            &transfer(payer_account.key, pda_account.key, rent_required - current_lamports),
+            &[
+                payer_account.clone(),
+                pda_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+    invoke_signed(
+        &allocate(pda_account.key, space as u64),
+        &[pda_account.clone(), system_program.clone()],
+        &[seeds],
+    )?;
+    invoke_signed(
+        &assign(pda_account.key, program_id),
+        &[pda_account.clone(), system_program.clone()],
+        &[seeds],
+    )
+}
+
+// Creates the receipt PDA for `receipt.reference`, failing if the reference was already used
+pub fn create_deposit_receipt<'a>(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo<'a>,
+    payer_account: &AccountInfo<'a>,
+    receipt_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    receipt: &DepositReceipt,
+) -> ProgramResult {
+    let (receipt_address, receipt_bump) = DepositReceipt::find_address(
+        program_id,
+        ramp_account.key,
+        &receipt.reference,
+    );
+    if receipt_address != *receipt_account.key {
+        return Err(RampError::InvalidReceiptAccount.into());
+    }
+    if receipt_account.owner == program_id || receipt_account.data_len() > 0 {
+        return Err(RampError::DuplicateReference.into());
+    }
+    create_pda_account(
+        program_id,
+        payer_account,
+        receipt_account,
+        system_program,
+        DepositReceipt::space(),
+        &[RECEIPT_SEED, ramp_account.key.as_ref(), &receipt.reference, &[receipt_bump]],
+    )?;
+    save_deposit_receipt(receipt_account, receipt)
+}
+
+// Loads the receipt for `reference`, checking it is the program owned receipt PDA of this ramp
+pub fn load_deposit_receipt(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo,
+    receipt_account: &AccountInfo,
+    reference: &[u8; 32],
+) -> Result<DepositReceipt, ProgramError> {
+    let (receipt_address, _) = DepositReceipt::find_address(program_id, ramp_account.key, reference);
+    if receipt_address != *receipt_account.key || receipt_account.owner != program_id {
+        return Err(RampError::InvalidReceiptAccount.into());
+    }
+    let receipt_data = receipt_account.try_borrow_data()?;
+    Ok(borsh::from_slice(&receipt_data)?)
+}
+
+pub fn save_deposit_receipt(receipt_account: &AccountInfo, receipt: &DepositReceipt) -> ProgramResult {
+    let mut receipt_data = receipt_account.try_borrow_mut_data()?;
+    receipt.serialize(&mut receipt_data.as_mut())?;
+    Ok(())
+}
+
+// Moves the escrowed tokens out of the receipt's token account and closes it, signing as the receipt PDA
+pub fn settle_escrow_tokens<'a>(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo<'a>,
+    receipt_account: &AccountInfo<'a>,
+    escrow_token_account: &AccountInfo<'a>,
+    destination_token_account: &AccountInfo<'a>,
+    rent_destination: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    receipt: &DepositReceipt,
+) -> ProgramResult {
+    let (_, receipt_bump) = DepositReceipt::find_address(program_id, ramp_account.key, &receipt.reference);
+    let bump = [receipt_bump];
+    let receipt_seeds: &[&[u8]] = &[RECEIPT_SEED, ramp_account.key.as_ref(), &receipt.reference, &bump];
+
+    let transfer_result = invoke_signed(
+        &token_instruction::transfer(
+            token_program.key,
+            escrow_token_account.key,
+            destination_token_account.key,
+            receipt_account.key,
+            &[receipt_account.key],
+            receipt.amount,
+        )?,
+        &[
+            escrow_token_account.clone(),
+            destination_token_account.clone(),
+            receipt_account.clone(),
+            token_program.clone(),
+        ],
+        &[receipt_seeds],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+    invoke_signed(
+        &token_instruction::close_account(
+            token_program.key,
+            escrow_token_account.key,
+            rent_destination.key,
+            receipt_account.key,
+            &[receipt_account.key],
+        )?,
+        &[
+            escrow_token_account.clone(),
+            rent_destination.clone(),
+            receipt_account.clone(),
+            token_program.clone(),
+        ],
+        &[receipt_seeds],
+    )
+}
+
+// Loads the credit entry of `recipient` for `asset`, checking it is the program owned credit PDA of
+// this ramp. Returns None while the PDA has not been created
+pub fn load_credit_entry(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo,
+    credit_account: &AccountInfo,
+    recipient: &Pubkey,
+    asset: &Pubkey,
+) -> Result<Option<CreditEntry>, ProgramError> {
+    let (credit_address, _) = CreditEntry::find_address(program_id, ramp_account.key, recipient, asset);
+    if credit_address != *credit_account.key {
+        return Err(RampError::InvalidCreditAccount.into());
+    }
+    if credit_account.owner != program_id || credit_account.data_len() == 0 {
+        return Ok(None);
+    }
+    let credit_data = credit_account.try_borrow_data()?;
+    Ok(Some(borsh::from_slice(&credit_data)?))
+}
+
+// Moves a payout that cannot be delivered into the token account of the recipient's credit PDA,
+// creating both on first use with the owner paying the rent, and logs the credit. Returns whether
+// a new credit entry was opened, which the caller records once the ramp is no longer borrowed
+pub fn credit_tokens<'a>(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo<'a>,
+    ramp_state: &RampState,
+    payer_account: &AccountInfo<'a>,
+    recipient: &Pubkey,
+    asset_mint_account: &AccountInfo<'a>,
+    ramp_token_account: &AccountInfo<'a>,
+    credit_account: &AccountInfo<'a>,
+    credit_token_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    associated_token_program: &AccountInfo<'a>,
+    amount: u64,
+) -> Result<bool, ProgramError> {
+    let credit_token_address = get_associated_token_address_with_program_id(
+        credit_account.key,
+        asset_mint_account.key,
+        token_program.key,
+    );
+    if credit_token_address != *credit_token_account.key {
+        return Err(RampError::InvalidCreditAccount.into());
+    }
+    let mut opened_credit = false;
+    let mut entry = match load_credit_entry(
+        program_id,
+        ramp_account,
+        credit_account,
+        recipient,
+        asset_mint_account.key,
+    )? {
+        Some(entry) => entry,
+        None => {
+            let (_, credit_bump) = CreditEntry::find_address(
+                program_id,
+                ramp_account.key,
+                recipient,
+                asset_mint_account.key,
+            );
+            create_pda_account(
+                program_id,
+                payer_account,
+                credit_account,
+                system_program,
+                CreditEntry::space(),
+                &[
+                    CREDIT_SEED,
+                    ramp_account.key.as_ref(),
+                    recipient.as_ref(),
+                    asset_mint_account.key.as_ref(),
+                    &[credit_bump],
+                ],
+            )?;
+            opened_credit = true;
+            CreditEntry {
+                recipient: *recipient,
+                asset: *asset_mint_account.key,
+                amount: 0,
+                payer: *payer_account.key,
+            }
+        }
+    };
+    invoke(
+        &create_associated_token_account_idempotent(
+            payer_account.key,
+            credit_account.key,
+            asset_mint_account.key,
+            token_program.key,
+        ),
+        &[
+            payer_account.clone(),
+            credit_token_account.clone(),
+            credit_account.clone(),
+            asset_mint_account.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+
+    let bump = [ramp_state.bump];
+    let ramp_seeds: &[&[u8]] = &[b"ramp", ramp_state.creator.as_ref(), &bump];
+    let transfer_result = invoke_signed(
+        &token_instruction::transfer(
+            token_program.key,
+            ramp_token_account.key,
+            credit_token_account.key,
+            ramp_account.key,
+            &[ramp_account.key],
+            amount,
+        )?,
+        &[
+            ramp_token_account.clone(),
+            credit_token_account.clone(),
+            ramp_account.clone(),
+            token_program.clone(),
+        ],
+        &[ramp_seeds],
+    );
+    if transfer_result.is_err() {
+        return Err(RampError::TransferFailed.into());
+    }
+
+    entry.amount = entry.amount.checked_add(amount).ok_or(RampError::InvalidAmount)?;
+    entry.serialize(&mut credit_account.try_borrow_mut_data()?.as_mut())?;
+
+    msg!("RampCreditCreated:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampCreditCreated {
+            asset: *asset_mint_account.key,
+            decimals: mint_decimals(asset_mint_account)?,
+            recipient: *recipient,
+            amount,
+            credit: entry.amount,
+        }).unwrap()
+    ));
+    Ok(opened_credit)
+}
+
+// Checks the voucher targets this ramp, is unexpired and that vouchers are enabled
+pub fn validate_voucher(ramp_account: &AccountInfo, ramp_state: &RampState, voucher: &WithdrawVoucher) -> ProgramResult {
+    if ramp_state.voucher_signer == Pubkey::default() {
+        return Err(RampError::VoucherSignerNotSet.into());
+    }
+    if voucher.ramp != *ramp_account.key {
+        return Err(RampError::InvalidVoucher.into());
+    }
+    if voucher.expires_at <= Clock::get()?.unix_timestamp {
+        return Err(RampError::VoucherExpired.into());
+    }
+    Ok(())
+}
+
+// Checks the instruction before the current one is an ed25519 precompile call verifying
+// exactly one signature by `signer` over `message`, with all data inline in that instruction
+pub fn verify_voucher_signature(instructions_sysvar: &AccountInfo, signer: &Pubkey, message: &[u8]) -> ProgramResult {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        return Err(RampError::InvalidVoucherSignature.into());
+    }
+    let ed25519_instruction = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    if ed25519_instruction.program_id != ed25519_program::ID {
+        return Err(RampError::InvalidVoucherSignature.into());
+    }
+    // [num_signatures, padding, Ed25519SignatureOffsets (7 x u16)]
+    let data = &ed25519_instruction.data;
+    if data.len() < 16 || data[0] != 1 {
+        return Err(RampError::InvalidVoucherSignature.into());
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_instruction_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_instruction_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_instruction_index = read_u16(14);
+    if signature_instruction_index != u16::MAX
+        || public_key_instruction_index != u16::MAX
+        || message_instruction_index != u16::MAX
+    {
+        return Err(RampError::InvalidVoucherSignature.into());
+    }
+    let signed_key = data.get(public_key_offset..public_key_offset + 32);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+    if signed_key != Some(signer.as_ref()) || signed_message != Some(message) {
+        return Err(RampError::InvalidVoucherSignature.into());
+    }
+    Ok(())
+}
+
+// Marks `nonce` as used by creating its empty PDA, failing if it already exists
+pub fn consume_voucher_nonce<'a>(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo<'a>,
+    payer_account: &AccountInfo<'a>,
+    nonce_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    nonce: u64,
+) -> ProgramResult {
+    let (nonce_address, nonce_bump) = find_nonce_address(program_id, ramp_account.key, nonce);
+    if nonce_address != *nonce_account.key {
+        return Err(RampError::InvalidVoucher.into());
+    }
+    if nonce_account.owner == program_id {
+        return Err(RampError::NonceAlreadyUsed.into());
+    }
+    create_pda_account(
+        program_id,
+        payer_account,
+        nonce_account,
+        system_program,
+        0,
+        &[NONCE_SEED, ramp_account.key.as_ref(), &nonce.to_le_bytes(), &[nonce_bump]],
+    )
+}
+
+// Reads the access list status of `address`, None when the address has no entry
+pub fn load_access_status(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo,
+    access_account: &AccountInfo,
+    address: &Pubkey,
+) -> Result<Option<AccessStatus>, ProgramError> {
+    let (access_address, _) = AccessEntry::find_address(program_id, ramp_account.key, address);
+    if access_address != *access_account.key {
+        return Err(RampError::InvalidAccessEntry.into());
+    }
+    if access_account.owner != program_id || access_account.data_len() == 0 {
+        return Ok(None);
+    }
+    let access_data = access_account.try_borrow_data()?;
+    let entry: AccessEntry = borsh::from_slice(&access_data)?;
+    Ok(Some(entry.status))
+}
+
+// Denied addresses are always rejected, in allowlist mode addresses without an entry are too
+pub fn check_address_access(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo,
+    ramp_state: &RampState,
+    access_account: &AccountInfo,
+    address: &Pubkey,
+) -> ProgramResult {
+    match load_access_status(program_id, ramp_account, access_account, address)? {
+        Some(AccessStatus::Denied) => Err(RampError::AddressDenied.into()),
+        status if !ramp_state.is_address_permitted(status) => Err(RampError::AddressNotAllowed.into()),
+        _ => Ok(()),
+    }
+}
+
+// Access entries are keyed by wallet, so token payouts are checked against the token account owner
+pub fn token_account_owner(token_account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    let token_data = token_account.try_borrow_data()?;
+    StateWithExtensions::<Account>::unpack(&token_data)
+        .map(|account| account.base.owner)
+        .map_err(|_| RampError::InvalidRecipient.into())
+}
+
+// Creates the ramp's SOL vault on first use. It is a program owned PDA without data, so the
+// program can move its lamports directly
+pub fn ensure_sol_vault<'a>(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo<'a>,
+    payer_account: &AccountInfo<'a>,
+    sol_vault_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (vault_address, vault_bump) = find_sol_vault_address(program_id, ramp_account.key);
+    if vault_address != *sol_vault_account.key {
+        return Err(RampError::InvalidSolVault.into());
+    }
+    if sol_vault_account.owner == program_id {
+        return Ok(());
+    }
+    create_pda_account(
+        program_id,
+        payer_account,
+        sol_vault_account,
+        system_program,
+        0,
+        &[SOL_VAULT_SEED, ramp_account.key.as_ref(), &[vault_bump]],
+    )
+}
+
+// SOL in the vault that can be paid out, without its rent reserve and the unwithdrawn native
+// revenue. A vault that was never created holds nothing
+pub fn sol_vault_liquidity(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo,
+    ramp_state: &RampState,
+    sol_vault_account: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let (vault_address, _) = find_sol_vault_address(program_id, ramp_account.key);
+    if vault_address != *sol_vault_account.key {
+        return Err(RampError::InvalidSolVault.into());
+    }
+    if sol_vault_account.owner != program_id {
+        return Ok(0);
+    }
+    let rent_required = Rent::get()
+        .map_err(|_| RampError::RentError)?
+        .minimum_balance(sol_vault_account.data_len());
+    let available = (sol_vault_account.lamports() as u128)
+        .saturating_sub(rent_required as u128)
+        .saturating_sub(ramp_state.native_revenue.get());
+    Ok(available.min(u64::MAX as u128) as u64)
+}
+
+// Tokens in the ramp's associated token account that can be paid out, without the unwithdrawn revenue
+pub fn token_liquidity(
+    ramp_account: &AccountInfo,
+    asset_mint_account: &AccountInfo,
+    ramp_token_account: &AccountInfo,
+    token_program: &AccountInfo,
+    revenue: u128,
+) -> Result<u64, ProgramError> {
+    let ramp_token_address = get_associated_token_address_with_program_id(
+        ramp_account.key,
+        asset_mint_account.key,
+        token_program.key,
+    );
+    if ramp_token_address != *ramp_token_account.key {
+        return Err(RampError::InvalidAccountState.into());
+    }
+    let ramp_token_data = ramp_token_account.try_borrow_data()?;
+    let balance = StateWithExtensions::<Account>::unpack(&ramp_token_data)?.base.amount;
+    Ok((balance as u128).saturating_sub(revenue) as u64)
+}
+
+// Pays SOL out of the vault, keeping its rent reserve and the unwithdrawn native revenue
+pub fn pay_from_sol_vault(
+    program_id: &Pubkey,
+    ramp_account: &AccountInfo,
+    ramp_state: &RampState,
+    sol_vault_account: &AccountInfo,
+    recipient_account: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let available = sol_vault_liquidity(program_id, ramp_account, ramp_state, sol_vault_account)?;
+    if available < amount {
+        return Err(RampError::InsufficientFunds.into());
+    }
+    **sol_vault_account.try_borrow_mut_lamports()? -= amount;
+    **recipient_account.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+// Pays `revenue` of a mint out of the ramp's token account following the revenue split. The
+// beneficiary token accounts are passed in split order, or the vault's token account without a split
+pub fn distribute_token_revenue<'a>(
+    ramp_account: &AccountInfo<'a>,
+    ramp_state: &RampState,
+    asset_mint_account: &AccountInfo<'a>,
+    ramp_token_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    beneficiary_accounts: &[AccountInfo<'a>],
+    revenue: u64,
+) -> Result<Vec<RevenueShare>, ProgramError> {
+    let beneficiaries = ramp_state.revenue_split.recipients(ramp_state.vault_address);
+    if beneficiaries.len() != beneficiary_accounts.len() {
+        return Err(RampError::InvalidRevenueSplit.into());
+    }
+    for (beneficiary, beneficiary_account) in beneficiaries.iter().zip(beneficiary_accounts) {
+        let beneficiary_data = beneficiary_account.try_borrow_data()?;
+        let token_account = StateWithExtensions::<Account>::unpack(&beneficiary_data)
+            .map_err(|_| RampError::InvalidRecipient)?;
+        if token_account.base.owner != beneficiary.address || token_account.base.mint != *asset_mint_account.key {
+            return Err(RampError::InvalidRecipient.into());
+        }
+    }
+
+    let amounts = split_amount(&beneficiaries, revenue);
+    let bump = [ramp_state.bump];
+    let ramp_seeds: &[&[u8]] = &[b"ramp", ramp_state.creator.as_ref(), &bump];
+    let mut shares = Vec::with_capacity(beneficiaries.len());
+    for ((beneficiary, beneficiary_account), amount) in beneficiaries.iter().zip(beneficiary_accounts).zip(amounts) {
+        if amount > 0 {
+            let transfer_result = invoke_signed(
+                &token_instruction::transfer(
+                    token_program.key,
+                    ramp_token_account.key,
+                    beneficiary_account.key,
+                    ramp_account.key,
+                    &[ramp_account.key],
+                    amount,
+                )?,
+                &[
+                    ramp_token_account.clone(),
+                    beneficiary_account.clone(),
+                    ramp_account.clone(),
+                    token_program.clone(),
+                ],
+                &[ramp_seeds],
+            );
+            if transfer_result.is_err() {
+                return Err(RampError::TransferFailed.into());
+            }
+        }
+        shares.push(RevenueShare {
+            beneficiary: beneficiary.address,
+            amount,
+        });
+    }
+    Ok(shares)
+}
+
+// Logs a RampLiquidityLow event when a withdrawal takes the available liquidity below the
+// asset's low-water mark. Only the crossing is logged, not every withdrawal below it
+pub fn check_low_water_mark(ramp_state: &RampState, asset: &Pubkey, decimals: u8, available_before: u64, available_after: u64) {
+    let low_water_mark = ramp_state.low_water_mark(asset);
+    if low_water_mark == 0 || available_before < low_water_mark || available_after >= low_water_mark {
+        return;
+    }
+    msg!("RampLiquidityLow:{}", general_purpose::STANDARD.encode(
+        borsh::to_vec(&RampLiquidityLow {
+            asset: *asset,
+            decimals,
+            available: available_after,
+            low_water_mark,
+        }).unwrap()
+    ));
+}
+
+// mpl-token-metadata is built against an older solana-program, so its ids are converted through bytes
+pub fn metadata_program_id() -> Pubkey {
+    Pubkey::new_from_array(mpl_token_metadata::ID.to_bytes())
+}
+
+pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    let metadata_program = metadata_program_id();
+    Pubkey::find_program_address(
+        &[METADATA_SEED, metadata_program.as_ref(), mint.as_ref()],
+        &metadata_program,
+    )
+}
+
+// Reads the name and symbol of a classic SPL mint from its Metaplex metadata PDA, with the
+// fixed width padding trimmed
+pub fn load_metaplex_metadata(
+    mint_account: &AccountInfo,
+    metadata_account: &AccountInfo,
+) -> Result<(String, String), ProgramError> {
+    let (metadata_address, _) = find_metadata_address(mint_account.key);
+    if metadata_address != *metadata_account.key || *metadata_account.owner != metadata_program_id() {
+        return Err(RampError::InvalidMetadataAccount.into());
+    }
+    let metadata_data = metadata_account.try_borrow_data()?;
+    let metadata = Metadata::safe_deserialize(&metadata_data)
+        .map_err(|_| RampError::InvalidMetadataAccount)?;
+    if metadata.mint.to_bytes() != mint_account.key.to_bytes() {
+        return Err(RampError::InvalidMetadataAccount.into());
+    }
+    Ok((
+        metadata.name.trim_end_matches('\0').to_string(),
+        metadata.symbol.trim_end_matches('\0').to_string(),
+    ))
+}
+
+pub fn mint_decimals(mint_account: &AccountInfo) -> Result<u8, ProgramError> {
+    let mint_data = mint_account.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    Ok(mint.base.decimals)
+}
+
+// Amount left for the ramp after the fee, as reported in deposit events
+pub fn net_amount(gross_amount: u64, fee: u128) -> u64 {
+    (gross_amount as u128).saturating_sub(fee) as u64
+}
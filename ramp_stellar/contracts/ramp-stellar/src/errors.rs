@@ -25,4 +25,118 @@ pub enum RampContractError {
     //Indicates the vault address is not found
     VaultAddressNotFound = 6,
 
+    //Indicates the region is already registered
+    RegionAlreadyExists = 7,
+
+    //Indicates the region is not registered
+    RegionNotFound = 8,
+
+    //Indicates the medium is already registered for the region
+    MediumAlreadyExists = 9,
+
+    //Indicates the medium is not registered for the region
+    MediumNotFound = 10,
+
+    //Indicates the region or medium is not registered or is disabled
+    RegionNotAllowed = 11,
+
+    //Indicates the deposit reference has already been used
+    DuplicateReference = 12,
+
+    //Indicates no deposit receipt exists for the reference
+    ReceiptNotFound = 13,
+
+    //Indicates the deposit has already been refunded
+    DepositAlreadyRefunded = 14,
+
+    //Indicates the escrow expiry is not in the future
+    InvalidExpiry = 15,
+
+    //Indicates the escrow expired and can only be reclaimed by the sender
+    EscrowExpired = 16,
+
+    //Indicates the escrow has not expired and cannot be reclaimed yet
+    EscrowNotExpired = 17,
+
+    //Indicates the deposit is not in the escrow state the action requires
+    InvalidEscrowState = 18,
+
+    //Indicates no voucher signer has been configured
+    VoucherSignerNotSet = 19,
+
+    //Indicates the voucher is past its expiry
+    VoucherExpired = 20,
+
+    //Indicates the voucher nonce has already been redeemed
+    NonceAlreadyUsed = 21,
+
+    //Indicates the voucher was issued for another contract
+    InvalidVoucher = 22,
+
+    //Indicates the batch is empty or has a non positive amount
+    InvalidBatch = 23,
+
+    //Indicates the contract already lists the maximum number of assets
+    MaxAssetsReached = 24,
+
+    //Indicates deposits of the asset are paused
+    AssetDepositsPaused = 25,
+
+    //Indicates withdrawals of the asset are paused
+    AssetWithdrawalsPaused = 26,
+
+    //Indicates the address is on the deny list
+    AddressDenied = 27,
+
+    //Indicates allowlist mode is on and the address is not allowed
+    AddressNotAllowed = 28,
+
+    //Indicates the fee schedule has unsorted or out of range tiers or caps
+    InvalidFeeSchedule = 29,
+
+    //Indicates the revenue split weights do not sum to 10000 bps or repeat a beneficiary
+    InvalidRevenueSplit = 30,
+
+    //Indicates the low-water mark is negative
+    InvalidLowWaterMark = 31,
+
+    //Indicates the amount is zero or negative
+    InvalidAmount = 32,
+
+    //Indicates the asset is winding down and takes no new deposits
+    AssetWindingDown = 33,
+
+    //Indicates the asset still has escrowed deposits to release or reclaim
+    PendingEscrows = 34,
+
+    //Indicates the storage already has the current layout version
+    AlreadyMigrated = 35,
+
+    //Indicates the caller is neither the owner nor the guardian
+    Unauthorized = 36,
+
+    //Indicates there is no pending proposal of that kind
+    ProposalNotFound = 37,
+
+    //Indicates a proposal of that kind is already pending
+    ProposalAlreadyPending = 38,
+
+    //Indicates the pending proposal is for another operation
+    ProposalMismatch = 39,
+
+    //Indicates the timelock delay of the proposal has not passed yet
+    TimelockNotElapsed = 40,
+
+    //Indicates the timelock delay is above the maximum
+    InvalidTimelockDelay = 41,
+
+    //Indicates the recipient has no credit to claim for the asset
+    NoCredit = 42,
+
+    //Indicates the asset still has unclaimed credits
+    PendingCredits = 43,
+
+    //Indicates the native asset was passed where only listed assets are accepted
+    NativeAssetNotAllowed = 44,
+
 }
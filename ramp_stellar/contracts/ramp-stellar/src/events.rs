@@ -1,6 +1,5 @@
-use soroban_sdk::{contracttype, Address, Bytes, Env, Symbol};
-
-use crate::{OnrampMedium, Region};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, Symbol, Vec};
+use crate::{AccessStatus, DepositParams, DepositReceipt, FeeSchedule, ReceiptStatus, TimelockKind, TimelockProposal};
 
 // ######### EVENTS #########
 
@@ -11,6 +10,7 @@ use crate::{OnrampMedium, Region};
 pub struct AssetAddedEvent{
     pub funder: Address,
     pub fee_percentage: i128,
+    pub decimals: u32,
     pub initial_balance: i128,
 } 
 
@@ -21,17 +21,20 @@ pub struct AssetAddedEvent{
 /// * `env` - The environment in which the event is emitted
 /// * `asset` - The address of the asset that was added
 /// * `fee_percentage` - The fee percentage for the asset
+/// * `decimals` - The decimals of the asset
+/// * `initial_balance` - The liquidity pulled from the funder
 /// 
 /// # Events
 /// 
 /// * topics - [ASSET_ADDED, asset]
 /// * data - [AssetAddedEvent]
-pub fn emit_asset_added(env: &Env, asset: Address, funder: Address, fee_percentage: i128, initial_balance: i128) {
+pub fn emit_asset_added(env: &Env, asset: Address, funder: Address, fee_percentage: i128, decimals: u32, initial_balance: i128) {
     let topics = (Symbol::new(env, "ASSET_ADDED"), asset);
 
     env.events().publish(topics, AssetAddedEvent {
         funder: funder,
         fee_percentage: fee_percentage,
+        decimals,
         initial_balance: initial_balance,
     });
 }
@@ -41,6 +44,7 @@ pub fn emit_asset_added(env: &Env, asset: Address, funder: Address, fee_percenta
 #[derive(Debug)]
 pub struct AssetRemovedEvent {
     pub balance_receipient: Address,
+    pub decimals: u32,
     pub amount: i128
 }
 /// Emits an event indicating that an asset has been removed from the contract
@@ -50,17 +54,19 @@ pub struct AssetRemovedEvent {
 /// * `env` - The environment in which the event is emitted
 /// * `asset` - The address of the asset that was removed
 /// * `balance_recipient` - The address of the recipient of the asset balance
+/// * `decimals` - The decimals of the asset
 /// * `amount` - The amount of the asset that was removed
 /// 
 /// # Events
 /// 
 /// * topics - [ASSET_REMOVED, asset]
 /// * data - [balance_recipient, amount]
-pub fn emit_asset_removed(env: &Env, asset: Address, balance_recipient: Address, amount: i128) {
+pub fn emit_asset_removed(env: &Env, asset: Address, balance_recipient: Address, decimals: u32, amount: i128) {
     let topics = (Symbol::new(env, "ASSET_REMOVED"), asset);
 
     env.events().publish(topics, AssetRemovedEvent {
         balance_receipient: balance_recipient,
+        decimals,
         amount: amount
     });
 }
@@ -116,6 +122,7 @@ pub fn emit_asset_fee_percentage_changed(env: &Env, asset: Address, old_fee_perc
 #[derive(Debug)]
 pub struct RevenueWithdrawnEvent {
     pub recipient: Address,
+    pub decimals: u32,
     pub amount: i128
 }
 
@@ -127,16 +134,18 @@ pub struct RevenueWithdrawnEvent {
 /// * `asset` - The address of the asset that was changed
 /// * `old_revenue` - The old revenue
 /// * `new_revenue` - The new revenue
+/// * `decimals` - The decimals of the asset
 /// 
 /// # Events
 /// 
 /// * topics - [ASSET_REVENUE_WITHDRAWN, asset]
 /// * data - [RevenueWithdrawnEvent]
-pub fn emit_asset_revenue_withdrawn(env: &Env, asset: Address, receiver: Address, revenue: i128) {
+pub fn emit_asset_revenue_withdrawn(env: &Env, asset: Address, receiver: Address, decimals: u32, revenue: i128) {
     let topics = (Symbol::new(env, "ASSET_REVENUE_WITHDRAWN"), asset);
 
     env.events().publish(topics, RevenueWithdrawnEvent {
         recipient: receiver,
+        decimals,
         amount: revenue
     });
 }
@@ -145,10 +154,15 @@ pub fn emit_asset_revenue_withdrawn(env: &Env, asset: Address, receiver: Address
 #[contracttype]
 #[derive(Debug)]
 pub struct OnRampDepositEvent {
-    pub amount: i128,
-    pub medium: OnrampMedium,
-    pub region: Region,
-    pub data: Bytes
+    pub decimals: u32,
+    pub gross_amount: i128,
+    pub fee: i128,
+    pub net_amount: i128,
+    pub medium: Symbol,
+    pub region: Symbol,
+    pub data: Bytes,
+    pub reference: BytesN<32>,
+    pub sequence: u64
 }
 
 /// Emits an event indicating that an onramp has occured
@@ -156,40 +170,35 @@ pub struct OnRampDepositEvent {
 /// # Arguments
 /// 
 /// * `env` - The environment in which the function gets run
-/// * `asset` - The address of the asset whose fee is getting changed
-/// * `amount` - amount deposited
-/// * `sender` - The user making the deposit
-/// * `medium` - The medium to recieve fiat
-/// * `region` - The region where the transaction originated
-/// * `data` - User data
+/// * `receipt` - The receipt recorded for the deposit
+/// * `decimals` - The decimals of the asset
+/// * `params` - The medium, region, user data and order reference of the deposit
 /// 
 /// # Event emitted
 /// 
 /// * topics - [ONRAMP, asset, sender]
 /// * data - [OnRampDepositEvent]
 ///
-pub fn emit_onramp_deposit_event(
-    env: &Env,
-    asset: Address,
-    sender: Address,
-    amount: i128,
-    medium: OnrampMedium,
-    region: Region,
-    data: Bytes
-) {
-    let topics = (Symbol::new(env, "ONRAMP"), asset, sender);
+pub fn emit_onramp_deposit_event(env: &Env, receipt: DepositReceipt, decimals: u32, params: DepositParams) {
+    let topics = (Symbol::new(env, "ONRAMP"), receipt.asset, receipt.sender);
 
     env.events().publish(topics, OnRampDepositEvent {
-        amount: amount,
-        region: region,
-        medium: medium,
-        data: data
+        decimals,
+        gross_amount: receipt.amount,
+        fee: receipt.fee,
+        net_amount: receipt.amount - receipt.fee,
+        region: params.region,
+        medium: params.medium,
+        data: params.data,
+        reference: params.reference,
+        sequence: receipt.sequence
     });
 }
 
 #[contracttype]
 #[derive(Debug)]
 pub struct OffRampWithdrawEvent {
+    pub decimals: u32,
     pub amount: i128,
 }
 
@@ -200,6 +209,7 @@ pub struct OffRampWithdrawEvent {
 /// * `env` - The environment in which the function gets run
 /// * `recipient` - The user making the deposit
 /// * `asset` - The address of the asset whose fee is getting changed
+/// * `decimals` - The decimals of the asset
 /// * `amount` - amount deposited
 /// 
 /// # Event emitted
@@ -211,11 +221,612 @@ pub fn emit_off_ramp_event(
     env: &Env,
     asset: Address,
     recipient: Address,
+    decimals: u32,
     amount: i128
 ) {
     let topics = (Symbol::new(env, "OFFRAMP"), asset, recipient);
     env.events().publish(topics, OffRampWithdrawEvent {
+        decimals,
         amount: amount
     });
 
-}
\ No newline at end of file
+}
+
+/// Emits an event indicating that a region has been registered
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `region` - The region code
+/// * `mediums` - The mediums allowed in the region
+/// 
+/// # Events
+/// 
+/// * topics - [REGION_ADDED, region]
+/// * data - [mediums]
+pub fn emit_region_added(env: &Env, region: Symbol, mediums: Vec<Symbol>) {
+    let topics = (Symbol::new(env, "REGION_ADDED"), region);
+
+    env.events().publish(topics, mediums);
+}
+
+/// Emits an event indicating that a medium has been added to a region
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `region` - The region code
+/// * `medium` - The medium that was added
+/// 
+/// # Events
+/// 
+/// * topics - [REGION_MEDIUM_ADDED, region]
+/// * data - [medium]
+pub fn emit_region_medium_added(env: &Env, region: Symbol, medium: Symbol) {
+    let topics = (Symbol::new(env, "REGION_MEDIUM_ADDED"), region);
+
+    env.events().publish(topics, medium);
+}
+
+/// Emits an event indicating that a region has been enabled or disabled
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `region` - The region code
+/// * `is_enabled` - The new region status
+/// 
+/// # Events
+/// 
+/// * topics - [REGION_STATUS_CHANGED, region]
+/// * data - [is_enabled]
+pub fn emit_region_status_changed(env: &Env, region: Symbol, is_enabled: bool) {
+    let topics = (Symbol::new(env, "REGION_STATUS_CHANGED"), region);
+
+    env.events().publish(topics, is_enabled);
+}
+
+/// Emits an event indicating that a medium has been enabled or disabled
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `region` - The region code
+/// * `medium` - The medium whose status changed
+/// * `is_enabled` - The new medium status
+/// 
+/// # Events
+/// 
+/// * topics - [MEDIUM_STATUS_CHANGED, region, medium]
+/// * data - [is_enabled]
+pub fn emit_medium_status_changed(env: &Env, region: Symbol, medium: Symbol, is_enabled: bool) {
+    let topics = (Symbol::new(env, "MEDIUM_STATUS_CHANGED"), region, medium);
+
+    env.events().publish(topics, is_enabled);
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct RegionFeeChangedEvent {
+    pub old_fee: Option<i128>,
+    pub new_fee: Option<i128>,
+}
+
+/// Emits an event indicating that a region fee override has been changed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `region` - The region code
+/// * `old_fee_percentage` - The old fee override
+/// * `new_fee_percentage` - The new fee override
+/// 
+/// # Events
+/// 
+/// * topics - [REGION_FEE_CHANGED, region]
+/// * data - [RegionFeeChangedEvent]
+pub fn emit_region_fee_changed(env: &Env, region: Symbol, old_fee_percentage: Option<i128>, new_fee_percentage: Option<i128>) {
+    let topics = (Symbol::new(env, "REGION_FEE_CHANGED"), region);
+
+    env.events().publish(topics, RegionFeeChangedEvent {
+        old_fee: old_fee_percentage,
+        new_fee: new_fee_percentage
+    });
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct DepositRefundedEvent {
+    pub decimals: u32,
+    pub gross_amount: i128,
+    pub fee: i128,
+    pub amount: i128,
+    pub fee_refunded: bool,
+    pub reference: BytesN<32>,
+    pub sequence: u64,
+}
+
+/// Emits an event indicating that a deposit has been refunded to its sender
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `reference` - The order reference of the deposit
+/// * `receipt` - The receipt of the refunded deposit
+/// * `decimals` - The decimals of the asset
+/// * `amount` - The amount returned
+/// * `fee_refunded` - Whether the deposit fee was returned as well
+/// 
+/// # Events
+/// 
+/// * topics - [REFUND, asset, sender]
+/// * data - [DepositRefundedEvent]
+pub fn emit_deposit_refunded(
+    env: &Env,
+    reference: BytesN<32>,
+    receipt: DepositReceipt,
+    decimals: u32,
+    amount: i128,
+    fee_refunded: bool
+) {
+    let topics = (Symbol::new(env, "REFUND"), receipt.asset, receipt.sender);
+
+    env.events().publish(topics, DepositRefundedEvent {
+        decimals,
+        gross_amount: receipt.amount,
+        fee: receipt.fee,
+        amount,
+        fee_refunded,
+        reference,
+        sequence: receipt.sequence
+    });
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct EscrowEvent {
+    pub reference: BytesN<32>,
+    pub sequence: u64,
+    pub decimals: u32,
+    pub gross_amount: i128,
+    pub fee: i128,
+    pub net_amount: i128,
+    pub status: ReceiptStatus,
+    pub expires_at: u64,
+}
+
+/// Emits an event indicating that an escrowed deposit was created, released or reclaimed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `reference` - The order reference of the deposit
+/// * `receipt` - The deposit receipt after the change
+/// * `decimals` - The decimals of the asset
+/// 
+/// # Events
+/// 
+/// * topics - [ESCROW, asset, sender]
+/// * data - [EscrowEvent]
+pub fn emit_escrow_status_changed(env: &Env, reference: BytesN<32>, receipt: DepositReceipt, decimals: u32) {
+    let topics = (Symbol::new(env, "ESCROW"), receipt.asset, receipt.sender);
+
+    env.events().publish(topics, EscrowEvent {
+        reference,
+        sequence: receipt.sequence,
+        decimals,
+        gross_amount: receipt.amount,
+        fee: receipt.fee,
+        net_amount: receipt.amount - receipt.fee,
+        status: receipt.status,
+        expires_at: receipt.expires_at
+    });
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct VoucherSignerChangedEvent {
+    pub old_signer: Option<BytesN<32>>,
+    pub new_signer: BytesN<32>,
+}
+
+/// Emits an event indicating that the voucher signer has been rotated
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `old_signer` - The previous signer, if any
+/// * `new_signer` - The new signer
+/// 
+/// # Events
+/// 
+/// * topics - [VOUCHER_SIGNER_CHANGED]
+/// * data - [VoucherSignerChangedEvent]
+pub fn emit_voucher_signer_changed(env: &Env, old_signer: Option<BytesN<32>>, new_signer: BytesN<32>) {
+    let topics = (Symbol::new(env, "VOUCHER_SIGNER_CHANGED"),);
+
+    env.events().publish(topics, VoucherSignerChangedEvent {
+        old_signer,
+        new_signer
+    });
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct AssetPauseChangedEvent {
+    pub deposits_paused: bool,
+    pub withdrawals_paused: bool,
+}
+
+/// Emits an event indicating that an asset's pause switches have changed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `deposits_paused` - Whether deposits of the asset are paused
+/// * `withdrawals_paused` - Whether withdrawals of the asset are paused
+/// 
+/// # Events
+/// 
+/// * topics - [ASSET_PAUSE_CHANGED, asset]
+/// * data - [AssetPauseChangedEvent]
+pub fn emit_asset_pause_changed(env: &Env, asset: Address, deposits_paused: bool, withdrawals_paused: bool) {
+    let topics = (Symbol::new(env, "ASSET_PAUSE_CHANGED"), asset);
+
+    env.events().publish(topics, AssetPauseChangedEvent {
+        deposits_paused,
+        withdrawals_paused
+    });
+}
+
+/// Emits an event indicating that an asset entered or left wind-down
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `winding_down` - Whether the asset is winding down
+/// 
+/// # Events
+/// 
+/// * topics - [ASSET_WIND_DOWN_CHANGED, asset]
+/// * data - [winding_down]
+pub fn emit_asset_wind_down_changed(env: &Env, asset: Address, winding_down: bool) {
+    let topics = (Symbol::new(env, "ASSET_WIND_DOWN_CHANGED"), asset);
+
+    env.events().publish(topics, winding_down);
+}
+
+/// Emits an event indicating that a withdrawal voucher has been redeemed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `nonce` - The voucher nonce
+/// * `recipient` - The address that received the payout
+/// 
+/// # Events
+/// 
+/// * topics - [VOUCHER_REDEEMED, nonce]
+/// * data - [recipient]
+pub fn emit_voucher_redeemed(env: &Env, nonce: u64, recipient: Address) {
+    let topics = (Symbol::new(env, "VOUCHER_REDEEMED"), nonce);
+
+    env.events().publish(topics, recipient);
+}
+
+/// Emits an event indicating that addresses were added to or removed from the access lists
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `addresses` - The updated addresses
+/// * `status` - The list the addresses were put on, None when they were removed
+/// 
+/// # Events
+/// 
+/// * topics - [ACCESS_LIST_UPDATED, status]
+/// * data - [addresses]
+pub fn emit_access_list_updated(env: &Env, addresses: Vec<Address>, status: Option<AccessStatus>) {
+    let topics = (Symbol::new(env, "ACCESS_LIST_UPDATED"), status);
+
+    env.events().publish(topics, addresses);
+}
+
+/// Emits an event indicating that allowlist-only mode was switched
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `allowlist_enabled` - Whether allowlist-only mode is on
+/// 
+/// # Events
+/// 
+/// * topics - [ALLOWLIST_MODE_CHANGED]
+/// * data - [allowlist_enabled]
+pub fn emit_allowlist_mode_changed(env: &Env, allowlist_enabled: bool) {
+    let topics = (Symbol::new(env, "ALLOWLIST_MODE_CHANGED"),);
+
+    env.events().publish(topics, allowlist_enabled);
+}
+
+/// Emits an event indicating that the fee schedule of an asset has been changed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `schedule` - The new schedule, None when it was cleared
+/// 
+/// # Events
+/// 
+/// * topics - [FEE_SCHEDULE_CHANGED, asset]
+/// * data - [Option<FeeSchedule>]
+pub fn emit_fee_schedule_changed(env: &Env, asset: Address, schedule: Option<FeeSchedule>) {
+    let topics = (Symbol::new(env, "FEE_SCHEDULE_CHANGED"), asset);
+
+    env.events().publish(topics, schedule);
+}
+
+/// Emits an event indicating that the revenue split has been changed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `split` - The (beneficiary, weight in bps) entries, empty when the split was cleared
+/// 
+/// # Events
+/// 
+/// * topics - [REVENUE_SPLIT_CHANGED]
+/// * data - [split]
+pub fn emit_revenue_split_changed(env: &Env, split: Vec<(Address, u32)>) {
+    let topics = (Symbol::new(env, "REVENUE_SPLIT_CHANGED"),);
+
+    env.events().publish(topics, split);
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct LiquidityLowEvent {
+    pub decimals: u32,
+    pub available: i128,
+    pub low_water_mark: i128,
+}
+
+/// Emits an event indicating that the low-water mark of an asset has been changed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `low_water_mark` - The new low-water mark, None when it was cleared
+/// 
+/// # Events
+/// 
+/// * topics - [LOW_WATER_MARK_CHANGED, asset]
+/// * data - [Option<i128>]
+pub fn emit_low_water_mark_changed(env: &Env, asset: Address, low_water_mark: Option<i128>) {
+    let topics = (Symbol::new(env, "LOW_WATER_MARK_CHANGED"), asset);
+
+    env.events().publish(topics, low_water_mark);
+}
+
+/// Emits an event indicating that a withdrawal took the available liquidity of an asset
+/// below its low-water mark
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `decimals` - The decimals of the asset
+/// * `available` - The liquidity left after the withdrawal
+/// * `low_water_mark` - The low-water mark that was crossed
+/// 
+/// # Events
+/// 
+/// * topics - [LIQUIDITY_LOW, asset]
+/// * data - [LiquidityLowEvent]
+pub fn emit_liquidity_low(env: &Env, asset: Address, decimals: u32, available: i128, low_water_mark: i128) {
+    let topics = (Symbol::new(env, "LIQUIDITY_LOW"), asset);
+
+    env.events().publish(topics, LiquidityLowEvent {
+        decimals,
+        available,
+        low_water_mark,
+    });
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct LiquidityFundedEvent {
+    pub funder: Address,
+    pub decimals: u32,
+    pub amount: i128,
+}
+
+/// Emits an event indicating that liquidity of an asset has been topped up
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `funder` - The address providing the liquidity
+/// * `decimals` - The decimals of the asset
+/// * `amount` - The amount added
+/// 
+/// # Events
+/// 
+/// * topics - [LIQUIDITY_FUNDED, asset]
+/// * data - [LiquidityFundedEvent]
+pub fn emit_liquidity_funded(env: &Env, asset: Address, funder: Address, decimals: u32, amount: i128) {
+    let topics = (Symbol::new(env, "LIQUIDITY_FUNDED"), asset);
+
+    env.events().publish(topics, LiquidityFundedEvent {
+        funder,
+        decimals,
+        amount,
+    });
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct LiquidityDefundedEvent {
+    pub recipient: Address,
+    pub decimals: u32,
+    pub amount: i128,
+}
+
+/// Emits an event indicating that liquidity of an asset has been drained
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `recipient` - The address receiving the liquidity
+/// * `decimals` - The decimals of the asset
+/// * `amount` - The amount drained
+/// 
+/// # Events
+/// 
+/// * topics - [LIQUIDITY_DEFUNDED, asset]
+/// * data - [LiquidityDefundedEvent]
+pub fn emit_liquidity_defunded(env: &Env, asset: Address, recipient: Address, decimals: u32, amount: i128) {
+    let topics = (Symbol::new(env, "LIQUIDITY_DEFUNDED"), asset);
+
+    env.events().publish(topics, LiquidityDefundedEvent {
+        recipient,
+        decimals,
+        amount,
+    });
+}
+
+/// Emits an event indicating that the storage was migrated to a new layout version
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `from_version` - The storage version before the migration
+/// * `to_version` - The storage version after the migration
+/// 
+/// # Events
+/// 
+/// * topics - [CONTRACT_MIGRATED, from_version]
+/// * data - [to_version]
+pub fn emit_contract_migrated(env: &Env, from_version: u32, to_version: u32) {
+    let topics = (Symbol::new(env, "CONTRACT_MIGRATED"), from_version);
+
+    env.events().publish(topics, to_version);
+}
+
+/// Emits an event indicating that a timelocked operation was scheduled
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `kind` - The kind of the operation
+/// * `proposal` - The proposed operation and the timestamp it becomes executable at
+/// 
+/// # Events
+/// 
+/// * topics - [TIMELOCK_PROPOSED, kind]
+/// * data - [TimelockProposal]
+pub fn emit_timelock_proposed(env: &Env, kind: TimelockKind, proposal: TimelockProposal) {
+    let topics = (Symbol::new(env, "TIMELOCK_PROPOSED"), kind);
+
+    env.events().publish(topics, proposal);
+}
+
+/// Emits an event indicating that a timelocked operation was executed
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `kind` - The kind of the operation
+/// * `proposal` - The proposed operation and the timestamp it becomes executable at
+/// 
+/// # Events
+/// 
+/// * topics - [TIMELOCK_EXECUTED, kind]
+/// * data - [TimelockProposal]
+pub fn emit_timelock_executed(env: &Env, kind: TimelockKind, proposal: TimelockProposal) {
+    let topics = (Symbol::new(env, "TIMELOCK_EXECUTED"), kind);
+
+    env.events().publish(topics, proposal);
+}
+
+/// Emits an event indicating that a pending timelocked operation was cancelled
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `kind` - The kind of the operation
+/// * `proposal` - The proposed operation and the timestamp it becomes executable at
+/// 
+/// # Events
+/// 
+/// * topics - [TIMELOCK_CANCELLED, kind]
+/// * data - [TimelockProposal]
+pub fn emit_timelock_cancelled(env: &Env, kind: TimelockKind, proposal: TimelockProposal) {
+    let topics = (Symbol::new(env, "TIMELOCK_CANCELLED"), kind);
+
+    env.events().publish(topics, proposal);
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct CreditEvent {
+    pub decimals: u32,
+    pub amount: i128,
+    pub credit: i128,
+}
+
+/// Emits an event indicating that a payout could not be delivered and was credited to the
+/// recipient instead
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `recipient` - The credited recipient
+/// * `decimals` - The decimals of the asset
+/// * `amount` - The amount credited
+/// * `credit` - The recipient's credit for the asset including the amount
+/// 
+/// # Events
+/// 
+/// * topics - [CREDIT_CREATED, asset, recipient]
+/// * data - [CreditEvent]
+pub fn emit_credit_created(env: &Env, asset: Address, recipient: Address, decimals: u32, amount: i128, credit: i128) {
+    let topics = (Symbol::new(env, "CREDIT_CREATED"), asset, recipient);
+
+    env.events().publish(topics, CreditEvent {
+        decimals,
+        amount,
+        credit,
+    });
+}
+
+/// Emits an event indicating that a recipient claimed their credit
+/// 
+/// # Arguments
+/// 
+/// * `env` - The environment in which the event is emitted
+/// * `asset` - The address of the asset
+/// * `recipient` - The recipient that claimed
+/// * `decimals` - The decimals of the asset
+/// * `amount` - The amount paid out
+/// 
+/// # Events
+/// 
+/// * topics - [CREDIT_CLAIMED, asset, recipient]
+/// * data - [CreditEvent]
+pub fn emit_credit_claimed(env: &Env, asset: Address, recipient: Address, decimals: u32, amount: i128) {
+    let topics = (Symbol::new(env, "CREDIT_CLAIMED"), asset, recipient);
+
+    env.events().publish(topics, CreditEvent {
+        decimals,
+        amount,
+        credit: 0,
+    });
+}
@@ -1,9 +1,9 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, panic_with_error, token, Address, Bytes, Env};
+use soroban_sdk::{contract, contractimpl, contracttype, panic_with_error, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, Symbol, TryFromVal, Val, Vec};
 use stellar_access::ownable::{self as ownable, Ownable};
 use stellar_contract_utils::pausable::{self as pausable, Pausable};
-use stellar_contract_utils::upgradeable::UpgradeableInternal;
-use stellar_macros::{default_impl, only_owner, when_not_paused, when_paused, Upgradeable};
+use stellar_contract_utils::upgradeable::{self as upgradeable, UpgradeableMigratable, UpgradeableMigratableInternal};
+use stellar_macros::{default_impl, only_owner, when_not_paused, when_paused};
 
 mod events;
 mod errors;
@@ -34,30 +34,74 @@ pub enum RampContractState {
     MaxAssets,
     // Key for the assets entries of the contract
     AssetsInfo(Address),
+    // Key for the list of listed assets, bounded by MaxAssets
+    AssetList,
+    // Key for the registered regions of the contract
+    RegionInfo(Symbol),
+    // Key for the last deposit sequence number
+    DepositSequence,
+    // Key for the deposit receipts, kept in persistent storage
+    DepositReceipt(BytesN<32>),
+    // Key for the ed25519 public key that signs withdrawal vouchers
+    VoucherSigner,
+    // Key for the used voucher nonces, kept in persistent storage
+    VoucherNonce(u64),
+    // Key for the deny/allow list entry of an address, kept in persistent storage
+    AccessList(Address),
+    // Key for the allowlist-only mode flag
+    AllowlistEnabled,
+    // Key for the tiered fee schedule of an asset
+    FeeSchedule(Address),
+    // Key for the (beneficiary, weight in bps) entries revenue is split across
+    RevenueSplit,
+    // Key for the low-water mark of an asset's available liquidity
+    LowWaterMark(Address),
+    // Key for the storage layout version, missing on contracts deployed before versioning
+    StorageVersion,
+    // Key for the delay in seconds between proposing and executing a timelocked operation
+    TimelockDelay,
+    // Key for the address that can cancel timelocked proposals besides the owner
+    Guardian,
+    // Key for the pending proposal of each kind of timelocked operation
+    TimelockProposal(TimelockKind),
+    // Key for the fee percentage of native XLM deposits
+    NativeFeePercentage,
+    // Key for the revenue generated by native XLM deposits
+    NativeRevenue,
+    // Key for the (deposits, withdrawals) pause flags of native XLM, which has no asset entry
+    NativePaused,
+    // Key for the undeliverable payouts a recipient can claim, per (recipient, asset), kept in persistent storage
+    Credit(Address, Address),
+    // Key for the outstanding claimable credits of an asset, excluded from its liquidity
+    CreditedTotal(Address),
 }
 
-// Onramp Medium
-// Defines where ofchain funds should be sent
-//ie medium: Safaricom, Airtel, and PayStack
-#[contracttype]
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum OnrampMedium {
-    Primary,
-    Secondary,
-    Tertiary
-}
+// Storage layout version written by the constructor and by migrate. Bump it whenever a stored
+// type changes, and rewrite the older entries in `_migrate`
+const STORAGE_VERSION: u32 = 1;
 
-// Region
-#[contracttype]
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Region {
-    KEN,
-    RWN,
-    NGA,
-    SA,
-    EGY,
-    GHN
-}
+// Deposit receipt TTL, roughly 30 days with 5s ledgers
+const RECEIPT_TTL_THRESHOLD: u32 = 518_400;
+const RECEIPT_TTL_EXTEND_TO: u32 = 1_036_800;
+
+// Access list entry TTL, extended on every write and check
+const ACCESS_TTL_THRESHOLD: u32 = 518_400;
+const ACCESS_TTL_EXTEND_TO: u32 = 1_036_800;
+
+// Fee schedule bounds, tiers are capped at the same 60% as asset fees
+const MAX_FEE_TIERS: u32 = 5;
+const MAX_FEE_BPS: u32 = 6_000;
+
+// Revenue split bounds, weights are in bps and must add up to the whole revenue
+const MAX_BENEFICIARIES: u32 = 10;
+const TOTAL_WEIGHT_BPS: u32 = 10_000;
+
+// Timelock delays in seconds, 2 days until the owner schedules another one, at most 30 days
+const DEFAULT_TIMELOCK_DELAY: u64 = 172_800;
+const MAX_TIMELOCK_DELAY: u64 = 2_592_000;
+
+// XDR of `Asset::Native`, the native Stellar Asset Contract address is derived from it
+const NATIVE_ASSET_XDR: [u8; 4] = [0; 4];
 
 /**
  * @dev Contract DataStructures
@@ -70,6 +114,32 @@ struct AssetInfo {
     is_added: bool,
     asset_fee_percentage: i128,
     asset_revenue: i128,
+    // deposits held in escrow, not available for withdrawals until released
+    asset_escrowed: i128,
+    deposits_paused: bool,
+    withdrawals_paused: bool,
+    // set ahead of delisting, new deposits are rejected while payouts and escrow settlement continue
+    winding_down: bool,
+}
+
+//AssetInfo as stored by version 0 contracts, before escrow, pause and wind-down tracking
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct AssetInfoV0 {
+    is_added: bool,
+    asset_fee_percentage: i128,
+    asset_revenue: i128,
+}
+
+impl From<AssetInfoV0> for AssetInfo {
+    fn from(legacy_info: AssetInfoV0) -> Self {
+        AssetInfo {
+            is_added: legacy_info.is_added,
+            asset_fee_percentage: legacy_info.asset_fee_percentage,
+            asset_revenue: legacy_info.asset_revenue,
+            ..AssetInfo::default()
+        }
+    }
 }
 
 impl Default for AssetInfo {
@@ -77,11 +147,147 @@ impl Default for AssetInfo {
         AssetInfo {
             is_added: false,
             asset_fee_percentage: 0,
-            asset_revenue: 0
+            asset_revenue: 0,
+            asset_escrowed: 0,
+            deposits_paused: false,
+            withdrawals_paused: false,
+            winding_down: false
+        }
+    }
+}
+
+//registered region (ie KEN) and the mediums fiat can be sent through
+//ie mediums: MPESA, AIRTEL, and PAYSTACK
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegionInfo {
+    pub is_enabled: bool,
+    pub fee_percentage: Option<i128>,
+    pub mediums: Map<Symbol, bool>,
+}
+
+//fee bracket, deposits of at least `threshold` pay `fee_bps` basis points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTier {
+    pub threshold: i128,
+    pub fee_bps: u32,
+}
+
+//tiered fee schedule of an asset, tiers are sorted by ascending threshold
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    pub tiers: Vec<FeeTier>,
+    pub min_fee: Option<i128>,
+    pub max_fee: Option<i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReceiptStatus {
+    Deposited,
+    Refunded,
+    Escrowed,
+    Released,
+    Reclaimed,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccessStatus {
+    Denied,
+    Allowed,
+}
+
+//on-chain record of a deposit, keyed by the client supplied order reference
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositReceipt {
+    pub sequence: u64,
+    pub asset: Address,
+    pub sender: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub status: ReceiptStatus,
+    // ledger timestamp from which an escrowed deposit can be reclaimed, 0 for direct deposits
+    pub expires_at: u64,
+}
+
+//payment details of a deposit, the order reference must not have been used before
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositParams {
+    pub medium: Symbol,
+    pub region: Symbol,
+    pub data: Bytes,
+    pub reference: BytesN<32>,
+}
+
+//payout authorization signed off-chain by the voucher signer, the signed
+//message is the XDR encoding of this struct
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawVoucher {
+    pub contract: Address,
+    pub recipient: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub nonce: u64,
+    pub expires_at: u64,
+}
+
+//owner operations that only take effect once the timelock delay has passed since their proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelockAction {
+    Upgrade(BytesN<32>),
+    ChangeVaultAddress(Address),
+    SetTimelockDelay(u64),
+    SetGuardian(Option<Address>),
+    SetRevenueSplit(Vec<(Address, u32)>),
+    RemoveAsset(Address, Address),
+    ChangeOwner(Address),
+    SetVoucherSigner(BytesN<32>),
+}
+
+//one proposal of each kind can be pending at a time
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimelockKind {
+    Upgrade,
+    ChangeVaultAddress,
+    SetTimelockDelay,
+    SetGuardian,
+    SetRevenueSplit,
+    RemoveAsset,
+    ChangeOwner,
+    SetVoucherSigner,
+}
+
+impl TimelockAction {
+    pub fn kind(&self) -> TimelockKind {
+        match self {
+            TimelockAction::Upgrade(_) => TimelockKind::Upgrade,
+            TimelockAction::ChangeVaultAddress(_) => TimelockKind::ChangeVaultAddress,
+            TimelockAction::SetTimelockDelay(_) => TimelockKind::SetTimelockDelay,
+            TimelockAction::SetGuardian(_) => TimelockKind::SetGuardian,
+            TimelockAction::SetRevenueSplit(_) => TimelockKind::SetRevenueSplit,
+            TimelockAction::RemoveAsset(..) => TimelockKind::RemoveAsset,
+            TimelockAction::ChangeOwner(_) => TimelockKind::ChangeOwner,
+            TimelockAction::SetVoucherSigner(_) => TimelockKind::SetVoucherSigner,
         }
     }
 }
 
+//scheduled operation, executable from the `eta` ledger timestamp onward
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelockProposal {
+    pub action: TimelockAction,
+    pub eta: u64,
+}
+
 #[contract]
 pub struct RampContract;
 
@@ -91,6 +297,13 @@ impl RampContract {
         ownable::set_owner(&env, &admin);
         env.storage().instance().set(&RampContractState::MaxAssets, &max_assets);
         env.storage().instance().set(&RampContractState::VaultAddress, &vault_address);
+        env.storage().instance().set(&RampContractState::StorageVersion, &STORAGE_VERSION);
+    }
+
+    /// function version
+    /// returns the storage layout version, 0 for contracts deployed before versioning
+    pub fn version(env: &Env) -> u32 {
+        env.storage().instance().get(&RampContractState::StorageVersion).unwrap_or(0)
     }
 
     /// function add_asset 
@@ -113,11 +326,22 @@ impl RampContract {
         if fee_percentage < 0 || fee_percentage > 60 {
             return Err(RampContractError::InvalidFeePercentage);
         }
+        // XLM is served by the native entry points, listing it would count its balance twice
+        if asset == Self::get_native_asset(env) {
+            return Err(RampContractError::NativeAssetNotAllowed);
+        }
         let asset_key = RampContractState::AssetsInfo(asset.clone());
 
-        let mut current_asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
 
         if !current_asset_info.is_added {
+            let mut assets = Self::get_assets(env);
+            let max_assets: u32 = env.storage().instance().get(&RampContractState::MaxAssets).unwrap_or(0);
+            if assets.len() >= max_assets {
+                return Err(RampContractError::MaxAssetsReached);
+            }
+            assets.push_back(asset.clone());
+            env.storage().instance().set(&RampContractState::AssetList, &assets);
 
             let token = token::Client::new(env, &asset);
             let current_address =  env.current_contract_address();
@@ -132,7 +356,7 @@ impl RampContract {
 
             env.storage().instance().set(&asset_key, &current_asset_info);
 
-            emit_asset_added(env, asset, funder, fee_percentage, initial_balance);
+            emit_asset_added(env, asset, funder, fee_percentage, token.decimals(), initial_balance);
             Ok(())
         } else {
             Err(RampContractError::AssetAlreadyExists)
@@ -140,7 +364,9 @@ impl RampContract {
     }
 
     /// function remove_asset 
-    /// removes an existing asset to the contract
+    /// removes an existing asset to the contract, sweeping its revenue to the vault
+    /// (or the revenue split) before returning the remaining liquidity. Needs an elapsed
+    /// `RemoveAsset` proposal
     /// 
     /// # Arguments
     /// 
@@ -150,40 +376,130 @@ impl RampContract {
     /// 
     /// # Event emitted
     /// 
+    /// * topics - [TIMELOCK_EXECUTED, kind]
+    /// * data - [TimelockProposal]
+    /// * topics - [ASSET_REVENUE_WITHDRAWN, asset] for each beneficiary paid
+    /// * data - [RevenueWithdrawnEvent]
     /// * topics - [ASSET_REMOVED, asset]
     /// * data - [AssetRemovedEvent]
     #[only_owner]
     #[when_not_paused]
     pub fn remove_asset(env: &Env, asset: Address, balance_recipient: Address) -> Result<(), RampContractError> {
+        Self::execute_proposal(env, TimelockAction::RemoveAsset(asset.clone(), balance_recipient.clone()))?;
         let asset_key = RampContractState::AssetsInfo(asset.clone());
 
-        let asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
+        let asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
 
         if asset_info.is_added {
+            // escrowed deposits could no longer be released or reclaimed once delisted
+            if asset_info.asset_escrowed > 0 {
+                return Err(RampContractError::PendingEscrows);
+            }
+            // nor could credits be claimed
+            if Self::get_credited_total(env, asset.clone()) > 0 {
+                return Err(RampContractError::PendingCredits);
+            }
             let token = token::Client::new(env, &asset);
 
-            let balance = token.balance(&env.current_contract_address()) - asset_info.asset_revenue;
+            // revenue is settled first so it is not orphaned by the reset below
+            if asset_info.asset_revenue > 0 {
+                Self::distribute_revenue(env, &asset, &token, asset_info.asset_revenue)?;
+            }
+            let balance = token.balance(&env.current_contract_address());
 
             if balance > 0 {
                 token.transfer(&env.current_contract_address(), &balance_recipient, &balance);
             }
             env.storage().instance().set(&asset_key, &AssetInfo::default());
-            emit_asset_removed(env, asset, balance_recipient, balance);
+            env.storage().instance().remove(&RampContractState::FeeSchedule(asset.clone()));
+            env.storage().instance().remove(&RampContractState::LowWaterMark(asset.clone()));
+
+            let mut assets = Self::get_assets(env);
+            if let Some(index) = assets.first_index_of(&asset) {
+                assets.remove(index);
+                env.storage().instance().set(&RampContractState::AssetList, &assets);
+            }
+            emit_asset_removed(env, asset, balance_recipient, token.decimals(), balance);
             Ok(())
         } else {
             Err(RampContractError::AssetNotFound)
         }
     }
 
-    #[only_owner]
+    /// function fund_asset
+    /// tops up the liquidity of a listed asset without relisting it
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset
+    /// * `funder` - The address providing the liquidity, has to authorize the transfer
+    /// * `amount` - The amount added
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [LIQUIDITY_FUNDED, asset]
+    /// * data - [LiquidityFundedEvent]
     #[when_not_paused]
-    pub fn change_owner(env: &Env, new_owner: Address) {
-        ownable::set_owner(env, &new_owner);
+    pub fn fund_asset(env: &Env, asset: Address, funder: Address, amount: i128) -> Result<(), RampContractError> {
+        funder.require_auth();
+        if amount <= 0 {
+            return Err(RampContractError::InvalidAmount);
+        }
+        if asset == Self::get_native_asset(env) {
+            return Err(RampContractError::NativeAssetNotAllowed);
+        }
+        let asset_info: AssetInfo = Self::load_asset_info(env, &RampContractState::AssetsInfo(asset.clone()));
+        if !asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+
+        let token = token::Client::new(env, &asset);
+        token.transfer(&funder, &env.current_contract_address(), &amount);
+        emit_liquidity_funded(env, asset, funder, token.decimals(), amount);
+        Ok(())
     }
 
+    /// function defund_asset
+    /// drains liquidity of a listed asset without delisting it, revenue and escrowed
+    /// deposits stay in the contract
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset
+    /// * `recipient` - The address receiving the liquidity
+    /// * `amount` - The amount drained, at most the available liquidity
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [LIQUIDITY_DEFUNDED, asset]
+    /// * data - [LiquidityDefundedEvent]
+    #[only_owner]
+    #[when_not_paused]
+    pub fn defund_asset(env: &Env, asset: Address, recipient: Address, amount: i128) -> Result<(), RampContractError> {
+        if amount <= 0 {
+            return Err(RampContractError::InvalidAmount);
+        }
+        let asset_info: AssetInfo = Self::load_asset_info(env, &RampContractState::AssetsInfo(asset.clone()));
+        if !asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+
+        let token = token::Client::new(env, &asset);
+        let available = Self::available_liquidity(env, &token, &asset_info);
+        if available < amount {
+            return Err(RampContractError::InsufficientFunds);
+        }
+        token.transfer(&env.current_contract_address(), &recipient, &amount);
+        let decimals = token.decimals();
+        emit_liquidity_defunded(env, asset.clone(), recipient, decimals, amount);
+        Self::check_low_water_mark(env, asset, decimals, available, available - amount);
+        Ok(())
+    }
 
     /// function change_vault_address 
-    /// changesthe current vault address
+    /// changes the current vault address to the one of an elapsed `ChangeVaultAddress` proposal
     /// 
     /// # Arguments
     /// 
@@ -192,11 +508,15 @@ impl RampContract {
     /// 
     /// # Event emitted
     /// 
-    /// * topics - [VAULT_ADDRESS_CHANGED, asset]
-    /// * data - [old_vault_address, new_vault_address]
+    /// * topics - [TIMELOCK_EXECUTED, kind]
+    /// * data - [TimelockProposal]
+    /// * topics - [VAULT_ADDRESS_CHANGED, old_vault_address]
+    /// * data - [new_vault_address]
     #[only_owner]
     #[when_not_paused]
-    pub fn change_vault_address(env: &Env, new_vault_address: Address) {
+    pub fn change_vault_address(env: &Env, new_vault_address: Address) -> Result<(), RampContractError> {
+        Self::execute_proposal(env, TimelockAction::ChangeVaultAddress(new_vault_address.clone()))?;
+
         let vault_address_key = RampContractState::VaultAddress;
         let mut current_vault: Address = env
         .storage()
@@ -209,6 +529,127 @@ impl RampContract {
         env.storage().instance().extend_ttl(50, 100);
 
         emit_vault_address_changed(env, old_vault_address, new_vault_address);
+        Ok(())
+    }
+
+    /// function propose
+    /// schedules a timelocked operation, which can be executed once the current delay has passed
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `action` - The operation to schedule
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [TIMELOCK_PROPOSED, kind]
+    /// * data - [TimelockProposal]
+    #[only_owner]
+    pub fn propose(env: &Env, action: TimelockAction) -> Result<u64, RampContractError> {
+        match &action {
+            TimelockAction::SetTimelockDelay(delay) if *delay > MAX_TIMELOCK_DELAY => {
+                return Err(RampContractError::InvalidTimelockDelay);
+            },
+            TimelockAction::SetRevenueSplit(split) => Self::check_revenue_split(split)?,
+            _ => {},
+        }
+        let kind = action.kind();
+        let proposal_key = RampContractState::TimelockProposal(kind);
+        if env.storage().instance().has(&proposal_key) {
+            return Err(RampContractError::ProposalAlreadyPending);
+        }
+        let proposal = TimelockProposal {
+            action,
+            eta: env.ledger().timestamp() + Self::get_timelock_delay(env),
+        };
+        env.storage().instance().set(&proposal_key, &proposal);
+
+        emit_timelock_proposed(env, kind, proposal.clone());
+        Ok(proposal.eta)
+    }
+
+    /// function cancel_proposal
+    /// drops a pending proposal, callable by the owner or the guardian
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `caller` - The owner or the guardian
+    /// * `kind` - The kind of the pending proposal
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [TIMELOCK_CANCELLED, kind]
+    /// * data - [TimelockProposal]
+    pub fn cancel_proposal(env: &Env, caller: Address, kind: TimelockKind) -> Result<(), RampContractError> {
+        caller.require_auth();
+        let caller = Some(caller);
+        if caller != ownable::get_owner(env) && caller != Self::get_guardian(env) {
+            return Err(RampContractError::Unauthorized);
+        }
+        let proposal_key = RampContractState::TimelockProposal(kind);
+        let proposal: TimelockProposal = env
+            .storage()
+            .instance()
+            .get(&proposal_key)
+            .ok_or(RampContractError::ProposalNotFound)?;
+        env.storage().instance().remove(&proposal_key);
+
+        emit_timelock_cancelled(env, kind, proposal);
+        Ok(())
+    }
+
+    pub fn get_proposal(env: &Env, kind: TimelockKind) -> Option<TimelockProposal> {
+        env.storage().instance().get(&RampContractState::TimelockProposal(kind))
+    }
+
+    /// function set_timelock_delay
+    /// applies the delay of an elapsed `SetTimelockDelay` proposal to proposals made afterwards
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `delay` - The new delay in seconds
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [TIMELOCK_EXECUTED, kind]
+    /// * data - [TimelockProposal]
+    #[only_owner]
+    pub fn set_timelock_delay(env: &Env, delay: u64) -> Result<(), RampContractError> {
+        Self::execute_proposal(env, TimelockAction::SetTimelockDelay(delay))?;
+        env.storage().instance().set(&RampContractState::TimelockDelay, &delay);
+        Ok(())
+    }
+
+    pub fn get_timelock_delay(env: &Env) -> u64 {
+        env.storage().instance().get(&RampContractState::TimelockDelay).unwrap_or(DEFAULT_TIMELOCK_DELAY)
+    }
+
+    /// function set_guardian
+    /// sets or clears (`None`) the guardian of an elapsed `SetGuardian` proposal
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `guardian` - The new guardian
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [TIMELOCK_EXECUTED, kind]
+    /// * data - [TimelockProposal]
+    #[only_owner]
+    pub fn set_guardian(env: &Env, guardian: Option<Address>) -> Result<(), RampContractError> {
+        Self::execute_proposal(env, TimelockAction::SetGuardian(guardian.clone()))?;
+        match guardian {
+            Some(guardian) => env.storage().instance().set(&RampContractState::Guardian, &guardian),
+            None => env.storage().instance().remove(&RampContractState::Guardian),
+        }
+        Ok(())
+    }
+
+    pub fn get_guardian(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&RampContractState::Guardian)
     }
 
     pub fn get_vault_address(env: &Env) -> Address {
@@ -222,16 +663,104 @@ impl RampContract {
 
     pub fn get_asset_fee_percentage(env: &Env, asset: Address) -> i128 {
         let asset_key = RampContractState::AssetsInfo(asset.clone());
-        let asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
+        let asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
         asset_info.asset_fee_percentage
     }
 
     pub fn get_asset_revenue(env: &Env, asset: Address) -> i128 {
         let asset_key = RampContractState::AssetsInfo(asset.clone());
-        let asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
+        let asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
         asset_info.asset_revenue
     }
 
+    pub fn get_assets(env: &Env) -> Vec<Address> {
+        env.storage().instance().get(&RampContractState::AssetList).unwrap_or(Vec::new(env))
+    }
+
+    pub fn is_asset_deposit_paused(env: &Env, asset: Address) -> bool {
+        if asset == Self::get_native_asset(env) {
+            return Self::get_native_paused(env).0;
+        }
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        asset_info.deposits_paused
+    }
+
+    pub fn is_asset_withdrawal_paused(env: &Env, asset: Address) -> bool {
+        if asset == Self::get_native_asset(env) {
+            return Self::get_native_paused(env).1;
+        }
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        asset_info.withdrawals_paused
+    }
+
+    /// function set_asset_paused
+    /// pauses deposits and/or withdrawals of a single asset, on top of the global pause
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset, or of the native asset to pause XLM
+    /// * `deposits_paused` - Whether deposits of the asset are rejected
+    /// * `withdrawals_paused` - Whether withdrawals of the asset are rejected
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ASSET_PAUSE_CHANGED, asset]
+    /// * data - [AssetPauseChangedEvent]
+    #[only_owner]
+    pub fn set_asset_paused(env: &Env, asset: Address, deposits_paused: bool, withdrawals_paused: bool) -> Result<(), RampContractError> {
+        if asset == Self::get_native_asset(env) {
+            env.storage().instance().set(&RampContractState::NativePaused, &(deposits_paused, withdrawals_paused));
+            emit_asset_pause_changed(env, asset, deposits_paused, withdrawals_paused);
+            return Ok(());
+        }
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        if !current_asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+        current_asset_info.deposits_paused = deposits_paused;
+        current_asset_info.withdrawals_paused = withdrawals_paused;
+        env.storage().instance().set(&asset_key, &current_asset_info);
+        emit_asset_pause_changed(env, asset, deposits_paused, withdrawals_paused);
+        Ok(())
+    }
+
+    pub fn is_asset_winding_down(env: &Env, asset: Address) -> bool {
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        asset_info.winding_down
+    }
+
+    /// function set_asset_winding_down
+    /// puts an asset into wind-down ahead of delisting. New deposits are rejected while
+    /// payouts, refunds and escrow release or reclaim keep working
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset
+    /// * `winding_down` - Whether the asset is winding down
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ASSET_WIND_DOWN_CHANGED, asset]
+    /// * data - [winding_down]
+    #[only_owner]
+    pub fn set_asset_winding_down(env: &Env, asset: Address, winding_down: bool) -> Result<(), RampContractError> {
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        if !current_asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+        current_asset_info.winding_down = winding_down;
+        env.storage().instance().set(&asset_key, &current_asset_info);
+        emit_asset_wind_down_changed(env, asset, winding_down);
+        Ok(())
+    }
+
     /// function withdraw_asset_revenue 
     /// sends an asset's generated revenue to the vault
     /// 
@@ -242,33 +771,24 @@ impl RampContract {
     /// 
     /// # Event emitted
     /// 
-    /// * topics - [ASSET_FEE_PERCENTAGE_CHANGED, asset]
+    /// * topics - [ASSET_REVENUE_WITHDRAWN, asset] for each beneficiary paid
     /// * data - [RevenueWithdrawnEvent]
     #[only_owner]
     #[when_not_paused]
     pub fn withdraw_asset_revenue(env: &Env, asset: Address) -> Result<(), RampContractError> {
         let asset_key = RampContractState::AssetsInfo(asset.clone());
 
-        let mut current_asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
 
         if current_asset_info.is_added {
-            let vault_address_key = RampContractState::VaultAddress;
-
-            let current_vault = env
-                .storage()
-                .instance()
-                .get(&vault_address_key)
-                .unwrap();
-
             let token = token::Client::new(env, &asset);
 
             let balance = token.balance(&env.current_contract_address());
             let current_revenue = current_asset_info.asset_revenue;
             if balance > current_revenue {
-                token.transfer(&env.current_contract_address(), &current_vault, &current_revenue);
+                Self::distribute_revenue(env, &asset, &token, current_revenue)?;
                 current_asset_info.asset_revenue = 0;
                 env.storage().instance().set(&asset_key, &current_asset_info);
-                emit_asset_revenue_withdrawn(env, asset, current_vault, current_revenue);
                 Ok(())
             } else {
                 Err(RampContractError::InsufficientFunds)
@@ -279,137 +799,1434 @@ impl RampContract {
         }
     }
 
-    /// function change_asset_fee_percentage 
-    /// changes the fee percentage charged on the asset
+    /// function withdraw_all_revenue 
+    /// sends the generated revenue of every listed asset to the vault, assets
+    /// without revenue are skipped
     /// 
     /// # Arguments
     /// 
     /// * `env` - The environment in which the function gets run
-    /// * `asset` - The address of the asset whose fee is getting changed
-    /// * `new_fee_percentage` - the new fee on the asset
     /// 
     /// # Event emitted
     /// 
-    /// * topics - [ASSET_FEE_PERCENTAGE_CHANGED, asset]
-    /// * data - [AssetFeeChangedEvent]
+    /// * topics - [ASSET_REVENUE_WITHDRAWN, asset] for each swept asset and beneficiary
+    /// * data - [RevenueWithdrawnEvent]
     #[only_owner]
     #[when_not_paused]
-    pub fn change_asset_fee_percentage(env: &Env, asset: Address, new_fee_percentage: i128) -> Result<(), RampContractError> {
-        let asset_key = RampContractState::AssetsInfo(asset.clone());
+    pub fn withdraw_all_revenue(env: &Env) -> Result<(), RampContractError> {
+        let current_address = env.current_contract_address();
 
-        let mut current_asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
-        if new_fee_percentage < 0 || new_fee_percentage > 60 {
-            return Err(RampContractError::InvalidFeePercentage);
-        }
-        if current_asset_info.is_added {
-            let old_fee = current_asset_info.asset_fee_percentage;
-            current_asset_info.asset_fee_percentage = new_fee_percentage;
+        for asset in Self::get_assets(env).iter() {
+            let asset_key = RampContractState::AssetsInfo(asset.clone());
+            let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+            let current_revenue = current_asset_info.asset_revenue;
+            if current_revenue <= 0 {
+                continue;
+            }
+
+            let token = token::Client::new(env, &asset);
+            if token.balance(&current_address) < current_revenue {
+                return Err(RampContractError::InsufficientFunds);
+            }
+            Self::distribute_revenue(env, &asset, &token, current_revenue)?;
+            current_asset_info.asset_revenue = 0;
             env.storage().instance().set(&asset_key, &current_asset_info);
-            emit_asset_fee_percentage_changed(env, asset, old_fee, new_fee_percentage);
-            Ok(())
-        } else {
-            Err(RampContractError::AssetNotFound)
         }
+        Ok(())
     }
 
-    /// function onramp_deposit 
-    /// deposits funds into the contract from the sender 
+    /// function set_revenue_split
+    /// splits withdrawn revenue across weighted beneficiaries instead of sending it
+    /// all to the vault, applying the split of an elapsed `SetRevenueSplit` proposal
     /// 
     /// # Arguments
     /// 
     /// * `env` - The environment in which the function gets run
-    /// * `asset` - The address of the asset whose fee is getting changed
-    /// * `amount` - amount deposited
-    /// * `sender` - The user making the deposit
-    /// * `medium` - The medium to recieve fiat
-    /// * `region` - The region where the transaction originated
-    /// * `data` - User data
+    /// * `split` - (beneficiary, weight in bps) entries summing to 10000, empty clears the split
     /// 
     /// # Event emitted
     /// 
-    /// * topics - [ONRAMP, asset, sender]
-    /// * data - [OnRampDepositEvent]
-    #[when_not_paused]
-    pub fn onramp_deposit(
-        env: &Env,
-        asset: Address,
-        amount: i128,
-        sender: Address,
-        medium: OnrampMedium,
-        region: Region,
-        data: Bytes
-    ) -> Result<(), RampContractError>{
-        let asset_key = RampContractState::AssetsInfo(asset.clone());
-
-        let mut current_asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
+    /// * topics - [TIMELOCK_EXECUTED, kind]
+    /// * data - [TimelockProposal]
+    /// * topics - [REVENUE_SPLIT_CHANGED]
+    /// * data - [split]
+    #[only_owner]
+    pub fn set_revenue_split(env: &Env, split: Vec<(Address, u32)>) -> Result<(), RampContractError> {
+        Self::execute_proposal(env, TimelockAction::SetRevenueSplit(split.clone()))?;
+        Self::check_revenue_split(&split)?;
 
-        if !current_asset_info.is_added {
-            Err(RampContractError::AssetNotAllowed)
+        if split.is_empty() {
+            env.storage().instance().remove(&RampContractState::RevenueSplit);
         } else {
-            let token = token::Client::new(env, &asset);
-            let current_address =  env.current_contract_address();
-
-            let fee = (current_asset_info.asset_fee_percentage * amount) / 100;
-
-            let amount_min_fee = amount - fee;
-            token.transfer_from(&current_address, &sender, &current_address, &amount);
-
-            current_asset_info.asset_revenue += fee;
-
-            env.storage().instance().set(&asset_key, &current_asset_info);
-            emit_onramp_deposit_event(env, asset, sender, amount_min_fee, medium, region, data);
-            Ok(())
+            env.storage().instance().set(&RampContractState::RevenueSplit, &split);
         }
+        emit_revenue_split_changed(env, split);
+        Ok(())
     }
 
-    /// function off_ramp_withdraw
-    /// 
+    pub fn get_revenue_split(env: &Env) -> Vec<(Address, u32)> {
+        env.storage().instance().get(&RampContractState::RevenueSplit).unwrap_or(Vec::new(env))
+    }
+
+    /// function get_available_liquidity
+    /// returns the balance of an asset that can be paid out, excluding revenue, escrowed
+    /// deposits and claimable credits
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset
+    pub fn get_available_liquidity(env: &Env, asset: Address) -> Result<i128, RampContractError> {
+        let asset_info: AssetInfo = Self::load_asset_info(env, &RampContractState::AssetsInfo(asset.clone()));
+        if !asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+        Ok(Self::available_liquidity(env, &token::Client::new(env, &asset), &asset_info))
+    }
+
+    /// function set_low_water_mark
+    /// sets the available liquidity below which a withdrawal emits LIQUIDITY_LOW
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset
+    /// * `low_water_mark` - The new low-water mark, None clears it
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [LOW_WATER_MARK_CHANGED, asset]
+    /// * data - [Option<i128>]
+    #[only_owner]
+    pub fn set_low_water_mark(env: &Env, asset: Address, low_water_mark: Option<i128>) -> Result<(), RampContractError> {
+        let asset_info: AssetInfo = Self::load_asset_info(env, &RampContractState::AssetsInfo(asset.clone()));
+        if !asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+
+        let mark_key = RampContractState::LowWaterMark(asset.clone());
+        match low_water_mark {
+            Some(mark) if mark < 0 => return Err(RampContractError::InvalidLowWaterMark),
+            Some(mark) => env.storage().instance().set(&mark_key, &mark),
+            None => env.storage().instance().remove(&mark_key),
+        }
+        emit_low_water_mark_changed(env, asset, low_water_mark);
+        Ok(())
+    }
+
+    pub fn get_low_water_mark(env: &Env, asset: Address) -> Option<i128> {
+        env.storage().instance().get(&RampContractState::LowWaterMark(asset))
+    }
+
+    /// function change_asset_fee_percentage 
+    /// changes the fee percentage charged on the asset
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset whose fee is getting changed
+    /// * `new_fee_percentage` - the new fee on the asset
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ASSET_FEE_PERCENTAGE_CHANGED, asset]
+    /// * data - [AssetFeeChangedEvent]
+    #[only_owner]
+    #[when_not_paused]
+    pub fn change_asset_fee_percentage(env: &Env, asset: Address, new_fee_percentage: i128) -> Result<(), RampContractError> {
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        if new_fee_percentage < 0 || new_fee_percentage > 60 {
+            return Err(RampContractError::InvalidFeePercentage);
+        }
+        if current_asset_info.is_added {
+            let old_fee = current_asset_info.asset_fee_percentage;
+            current_asset_info.asset_fee_percentage = new_fee_percentage;
+            env.storage().instance().set(&asset_key, &current_asset_info);
+            emit_asset_fee_percentage_changed(env, asset, old_fee, new_fee_percentage);
+            Ok(())
+        } else {
+            Err(RampContractError::AssetNotFound)
+        }
+    }
+
+    /// function set_fee_schedule
+    /// sets the tiered fee schedule of an asset, replacing the flat fee percentage for
+    /// amounts that reach the first tier
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset
+    /// * `schedule` - The new schedule, None clears it
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [FEE_SCHEDULE_CHANGED, asset]
+    /// * data - [Option<FeeSchedule>]
+    #[only_owner]
+    pub fn set_fee_schedule(env: &Env, asset: Address, schedule: Option<FeeSchedule>) -> Result<(), RampContractError> {
+        let asset_info: AssetInfo = Self::load_asset_info(env, &RampContractState::AssetsInfo(asset.clone()));
+        if !asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+
+        let schedule_key = RampContractState::FeeSchedule(asset.clone());
+        match &schedule {
+            Some(schedule) => {
+                Self::validate_fee_schedule(schedule)?;
+                env.storage().instance().set(&schedule_key, schedule);
+            },
+            None => env.storage().instance().remove(&schedule_key),
+        }
+        emit_fee_schedule_changed(env, asset, schedule);
+        Ok(())
+    }
+
+    pub fn get_fee_schedule(env: &Env, asset: Address) -> Option<FeeSchedule> {
+        env.storage().instance().get(&RampContractState::FeeSchedule(asset))
+    }
+
+    /// function quote_fee
+    /// returns the fee a deposit of `amount` would pay, computed exactly as on deposit
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset
+    /// * `amount` - The deposit amount
+    /// * `region` - The deposit region, so a region fee override is reflected in the quote
+    pub fn quote_fee(env: &Env, asset: Address, amount: i128, region: Option<Symbol>) -> Result<i128, RampContractError> {
+        let asset_info: AssetInfo = Self::load_asset_info(env, &RampContractState::AssetsInfo(asset.clone()));
+        if !asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+        let region_fee_percentage = region
+            .and_then(|region| env.storage().instance().get::<_, RegionInfo>(&RampContractState::RegionInfo(region)))
+            .and_then(|info| info.fee_percentage);
+        Ok(Self::compute_fee(env, &asset, &asset_info, amount, region_fee_percentage))
+    }
+
+    /// function add_region 
+    /// registers a region and the mediums it accepts
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `region` - The region code ie KEN
+    /// * `mediums` - The mediums allowed in the region ie MPESA, AIRTEL
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [REGION_ADDED, region]
+    /// * data - [mediums]
+    #[only_owner]
+    pub fn add_region(env: &Env, region: Symbol, mediums: Vec<Symbol>) -> Result<(), RampContractError> {
+        let region_key = RampContractState::RegionInfo(region.clone());
+
+        if env.storage().instance().has(&region_key) {
+            return Err(RampContractError::RegionAlreadyExists);
+        }
+        let mut region_mediums = Map::new(env);
+        for medium in mediums.iter() {
+            region_mediums.set(medium, true);
+        }
+        env.storage().instance().set(&region_key, &RegionInfo {
+            is_enabled: true,
+            fee_percentage: None,
+            mediums: region_mediums,
+        });
+        emit_region_added(env, region, mediums);
+        Ok(())
+    }
+
+    /// function add_region_medium 
+    /// adds a medium to an already registered region
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `region` - The region code
+    /// * `medium` - The medium being added
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [REGION_MEDIUM_ADDED, region]
+    /// * data - [medium]
+    #[only_owner]
+    pub fn add_region_medium(env: &Env, region: Symbol, medium: Symbol) -> Result<(), RampContractError> {
+        let region_key = RampContractState::RegionInfo(region.clone());
+
+        let mut region_info: RegionInfo = env
+            .storage()
+            .instance()
+            .get(&region_key)
+            .ok_or(RampContractError::RegionNotFound)?;
+
+        if region_info.mediums.contains_key(medium.clone()) {
+            return Err(RampContractError::MediumAlreadyExists);
+        }
+        region_info.mediums.set(medium.clone(), true);
+        env.storage().instance().set(&region_key, &region_info);
+        emit_region_medium_added(env, region, medium);
+        Ok(())
+    }
+
+    /// function set_region_enabled 
+    /// enables or disables deposits for a region
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `region` - The region code
+    /// * `is_enabled` - The new region status
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [REGION_STATUS_CHANGED, region]
+    /// * data - [is_enabled]
+    #[only_owner]
+    pub fn set_region_enabled(env: &Env, region: Symbol, is_enabled: bool) -> Result<(), RampContractError> {
+        let region_key = RampContractState::RegionInfo(region.clone());
+
+        let mut region_info: RegionInfo = env
+            .storage()
+            .instance()
+            .get(&region_key)
+            .ok_or(RampContractError::RegionNotFound)?;
+
+        region_info.is_enabled = is_enabled;
+        env.storage().instance().set(&region_key, &region_info);
+        emit_region_status_changed(env, region, is_enabled);
+        Ok(())
+    }
+
+    /// function set_medium_enabled 
+    /// enables or disables a single medium within a region
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `region` - The region code
+    /// * `medium` - The medium whose status changes
+    /// * `is_enabled` - The new medium status
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [MEDIUM_STATUS_CHANGED, region, medium]
+    /// * data - [is_enabled]
+    #[only_owner]
+    pub fn set_medium_enabled(env: &Env, region: Symbol, medium: Symbol, is_enabled: bool) -> Result<(), RampContractError> {
+        let region_key = RampContractState::RegionInfo(region.clone());
+
+        let mut region_info: RegionInfo = env
+            .storage()
+            .instance()
+            .get(&region_key)
+            .ok_or(RampContractError::RegionNotFound)?;
+
+        if !region_info.mediums.contains_key(medium.clone()) {
+            return Err(RampContractError::MediumNotFound);
+        }
+        region_info.mediums.set(medium.clone(), is_enabled);
+        env.storage().instance().set(&region_key, &region_info);
+        emit_medium_status_changed(env, region, medium, is_enabled);
+        Ok(())
+    }
+
+    /// function set_region_fee_percentage 
+    /// overrides the asset fee for deposits originating in a region
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `region` - The region code
+    /// * `fee_percentage` - The override fee, None falls back to the asset fee
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [REGION_FEE_CHANGED, region]
+    /// * data - [RegionFeeChangedEvent]
+    #[only_owner]
+    pub fn set_region_fee_percentage(env: &Env, region: Symbol, fee_percentage: Option<i128>) -> Result<(), RampContractError> {
+        if let Some(fee) = fee_percentage {
+            if !(0..=60).contains(&fee) {
+                return Err(RampContractError::InvalidFeePercentage);
+            }
+        }
+        let region_key = RampContractState::RegionInfo(region.clone());
+
+        let mut region_info: RegionInfo = env
+            .storage()
+            .instance()
+            .get(&region_key)
+            .ok_or(RampContractError::RegionNotFound)?;
+
+        let old_fee = region_info.fee_percentage;
+        region_info.fee_percentage = fee_percentage;
+        env.storage().instance().set(&region_key, &region_info);
+        emit_region_fee_changed(env, region, old_fee, fee_percentage);
+        Ok(())
+    }
+
+    pub fn get_region(env: &Env, region: Symbol) -> Option<RegionInfo> {
+        env.storage().instance().get(&RampContractState::RegionInfo(region))
+    }
+
+    pub fn get_deposit_receipt(env: &Env, reference: BytesN<32>) -> Option<DepositReceipt> {
+        env.storage().persistent().get(&RampContractState::DepositReceipt(reference))
+    }
+
+    pub fn get_deposit_sequence(env: &Env) -> u64 {
+        env.storage().instance().get(&RampContractState::DepositSequence).unwrap_or(0)
+    }
+
+    /// function onramp_deposit 
+    /// deposits funds into the contract from the sender 
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset whose fee is getting changed
+    /// * `amount` - amount deposited
+    /// * `sender` - The user making the deposit
+    /// * `params` - The medium, region, user data and unique order reference of the deposit
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ONRAMP, asset, sender]
+    /// * data - [OnRampDepositEvent]
+    #[when_not_paused]
+    pub fn onramp_deposit(
+        env: &Env,
+        asset: Address,
+        amount: i128,
+        sender: Address,
+        params: DepositParams
+    ) -> Result<(), RampContractError>{
+        if amount <= 0 {
+            return Err(RampContractError::InvalidAmount);
+        }
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let receipt_key = RampContractState::DepositReceipt(params.reference.clone());
+
+        if env.storage().persistent().has(&receipt_key) {
+            return Err(RampContractError::DuplicateReference);
+        }
+
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+
+        let region_fee_percentage = Self::region_fee_percentage(env, &params.region, &params.medium)?;
+        Self::check_access(env, &sender)?;
+
+        if !current_asset_info.is_added {
+            Err(RampContractError::AssetNotAllowed)
+        } else if current_asset_info.deposits_paused {
+            Err(RampContractError::AssetDepositsPaused)
+        } else if current_asset_info.winding_down {
+            Err(RampContractError::AssetWindingDown)
+        } else {
+            let token = token::Client::new(env, &asset);
+            let current_address =  env.current_contract_address();
+
+            let fee = Self::compute_fee(env, &asset, &current_asset_info, amount, region_fee_percentage);
+
+            token.transfer_from(&current_address, &sender, &current_address, &amount);
+
+            current_asset_info.asset_revenue += fee;
+
+            env.storage().instance().set(&asset_key, &current_asset_info);
+
+            let sequence = Self::get_deposit_sequence(env) + 1;
+            env.storage().instance().set(&RampContractState::DepositSequence, &sequence);
+
+            let receipt = DepositReceipt {
+                sequence,
+                asset,
+                sender,
+                amount,
+                fee,
+                status: ReceiptStatus::Deposited,
+                expires_at: 0,
+            };
+            env.storage().persistent().set(&receipt_key, &receipt);
+            env.storage().persistent().extend_ttl(&receipt_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+            emit_onramp_deposit_event(env, receipt, token.decimals(), params);
+            Ok(())
+        }
+    }
+
+    /// function escrow_deposit 
+    /// deposits funds into an escrow held against the deposit receipt until the operator
+    /// releases it or the sender reclaims it after expiry
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the deposited asset
+    /// * `amount` - amount deposited
+    /// * `sender` - The user making the deposit
+    /// * `params` - The medium, region, user data and unique order reference of the deposit
+    /// * `expires_at` - Ledger timestamp from which the sender can reclaim the deposit
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ONRAMP, asset, sender]
+    /// * data - [OnRampDepositEvent]
+    /// * topics - [ESCROW, asset, sender]
+    /// * data - [EscrowEvent]
+    #[when_not_paused]
+    pub fn escrow_deposit(
+        env: &Env,
+        asset: Address,
+        amount: i128,
+        sender: Address,
+        params: DepositParams,
+        expires_at: u64
+    ) -> Result<(), RampContractError> {
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let receipt_key = RampContractState::DepositReceipt(params.reference.clone());
+
+        if env.storage().persistent().has(&receipt_key) {
+            return Err(RampContractError::DuplicateReference);
+        }
+        if amount <= 0 {
+            return Err(RampContractError::InvalidAmount);
+        }
+        if expires_at <= env.ledger().timestamp() {
+            return Err(RampContractError::InvalidExpiry);
+        }
+
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+
+        let region_fee_percentage = Self::region_fee_percentage(env, &params.region, &params.medium)?;
+        Self::check_access(env, &sender)?;
+
+        if !current_asset_info.is_added {
+            return Err(RampContractError::AssetNotAllowed);
+        }
+        if current_asset_info.deposits_paused {
+            return Err(RampContractError::AssetDepositsPaused);
+        }
+        if current_asset_info.winding_down {
+            return Err(RampContractError::AssetWindingDown);
+        }
+        let token = token::Client::new(env, &asset);
+        let current_address =  env.current_contract_address();
+
+        // revenue is only booked once the escrow is released
+        let fee = Self::compute_fee(env, &asset, &current_asset_info, amount, region_fee_percentage);
+
+        token.transfer_from(&current_address, &sender, &current_address, &amount);
+
+        current_asset_info.asset_escrowed += amount;
+        env.storage().instance().set(&asset_key, &current_asset_info);
+
+        let sequence = Self::get_deposit_sequence(env) + 1;
+        env.storage().instance().set(&RampContractState::DepositSequence, &sequence);
+
+        let receipt = DepositReceipt {
+            sequence,
+            asset: asset.clone(),
+            sender: sender.clone(),
+            amount,
+            fee,
+            status: ReceiptStatus::Escrowed,
+            expires_at,
+        };
+        env.storage().persistent().set(&receipt_key, &receipt);
+        env.storage().persistent().extend_ttl(&receipt_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+        let decimals = token.decimals();
+        let reference = params.reference.clone();
+        emit_onramp_deposit_event(env, receipt.clone(), decimals, params);
+        emit_escrow_status_changed(env, reference, receipt, decimals);
+        Ok(())
+    }
+
+    /// function release_escrow 
+    /// confirms the fiat payout of an escrowed deposit before it expires, moving the
+    /// funds into the pool and booking the fee as revenue
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `reference` - The order reference of the escrowed deposit
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ESCROW, asset, sender]
+    /// * data - [EscrowEvent]
+    #[only_owner]
+    #[when_not_paused]
+    pub fn release_escrow(env: &Env, reference: BytesN<32>) -> Result<(), RampContractError> {
+        let receipt_key = RampContractState::DepositReceipt(reference.clone());
+
+        let mut receipt: DepositReceipt = env
+            .storage()
+            .persistent()
+            .get(&receipt_key)
+            .ok_or(RampContractError::ReceiptNotFound)?;
+
+        if receipt.status != ReceiptStatus::Escrowed {
+            return Err(RampContractError::InvalidEscrowState);
+        }
+        if env.ledger().timestamp() >= receipt.expires_at {
+            return Err(RampContractError::EscrowExpired);
+        }
+
+        let asset_key = RampContractState::AssetsInfo(receipt.asset.clone());
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        current_asset_info.asset_escrowed -= receipt.amount;
+        current_asset_info.asset_revenue += receipt.fee;
+        env.storage().instance().set(&asset_key, &current_asset_info);
+
+        receipt.status = ReceiptStatus::Released;
+        env.storage().persistent().set(&receipt_key, &receipt);
+        env.storage().persistent().extend_ttl(&receipt_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+        let decimals = token::Client::new(env, &receipt.asset).decimals();
+        emit_escrow_status_changed(env, reference, receipt, decimals);
+        Ok(())
+    }
+
+    /// function reclaim_escrow 
+    /// returns an expired escrowed deposit to its sender, works without the operator
+    /// and while the contract is paused
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `reference` - The order reference of the escrowed deposit
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ESCROW, asset, sender]
+    /// * data - [EscrowEvent]
+    pub fn reclaim_escrow(env: &Env, reference: BytesN<32>) -> Result<(), RampContractError> {
+        let receipt_key = RampContractState::DepositReceipt(reference.clone());
+
+        let mut receipt: DepositReceipt = env
+            .storage()
+            .persistent()
+            .get(&receipt_key)
+            .ok_or(RampContractError::ReceiptNotFound)?;
+
+        receipt.sender.require_auth();
+
+        if receipt.status != ReceiptStatus::Escrowed {
+            return Err(RampContractError::InvalidEscrowState);
+        }
+        if env.ledger().timestamp() < receipt.expires_at {
+            return Err(RampContractError::EscrowNotExpired);
+        }
+
+        let asset_key = RampContractState::AssetsInfo(receipt.asset.clone());
+        let mut current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        current_asset_info.asset_escrowed -= receipt.amount;
+        env.storage().instance().set(&asset_key, &current_asset_info);
+
+        let token = token::Client::new(env, &receipt.asset);
+        token.transfer(&env.current_contract_address(), &receipt.sender, &receipt.amount);
+
+        receipt.status = ReceiptStatus::Reclaimed;
+        env.storage().persistent().set(&receipt_key, &receipt);
+        env.storage().persistent().extend_ttl(&receipt_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+        emit_escrow_status_changed(env, reference, receipt, token.decimals());
+        Ok(())
+    }
+
+    /// function refund_deposit 
+    /// returns a receipted deposit to its original sender
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `reference` - The order reference of the deposit
+    /// * `refund_fee` - Whether the fee is returned too, removing it from the asset revenue
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [REFUND, asset, sender]
+    /// * data - [DepositRefundedEvent]
+    #[only_owner]
+    #[when_not_paused]
+    pub fn refund_deposit(env: &Env, reference: BytesN<32>, refund_fee: bool) -> Result<(), RampContractError> {
+        let receipt_key = RampContractState::DepositReceipt(reference.clone());
+
+        let mut receipt: DepositReceipt = env
+            .storage()
+            .persistent()
+            .get(&receipt_key)
+            .ok_or(RampContractError::ReceiptNotFound)?;
+
+        match receipt.status {
+            ReceiptStatus::Deposited | ReceiptStatus::Released => {},
+            ReceiptStatus::Refunded => return Err(RampContractError::DepositAlreadyRefunded),
+            _ => return Err(RampContractError::InvalidEscrowState),
+        }
+        Self::check_access(env, &receipt.sender)?;
+
+        let is_native = receipt.asset == Self::get_native_asset(env);
+        let asset_key = RampContractState::AssetsInfo(receipt.asset.clone());
+        let mut current_asset_info: AssetInfo = if is_native {
+            // native deposits have no asset entry, only their revenue and pause flags are tracked
+            AssetInfo {
+                is_added: true,
+                asset_revenue: Self::get_native_revenue(env),
+                withdrawals_paused: Self::get_native_paused(env).1,
+                ..Default::default()
+            }
+        } else {
+            Self::load_asset_info(env, &asset_key)
+        };
+        if !current_asset_info.is_added {
+            return Err(RampContractError::AssetNotFound);
+        }
+        if current_asset_info.withdrawals_paused {
+            return Err(RampContractError::AssetWithdrawalsPaused);
+        }
+
+        let token = token::Client::new(env, &receipt.asset);
+        let current_address = env.current_contract_address();
+        let liquidity = if is_native {
+            Self::native_liquidity(env, &token)
+        } else {
+            Self::available_liquidity(env, &token, &current_asset_info)
+        };
+
+        // a refunded fee leaves the revenue, so it can be paid out on top of the liquidity
+        let reversed_fee = if refund_fee { receipt.fee.min(current_asset_info.asset_revenue) } else { 0 };
+        let refund_amount = if refund_fee { receipt.amount } else { receipt.amount - receipt.fee };
+        if liquidity + reversed_fee < refund_amount {
+            return Err(RampContractError::InsufficientFunds);
+        }
+        current_asset_info.asset_revenue -= reversed_fee;
+        token.transfer(&current_address, &receipt.sender, &refund_amount);
+
+        if is_native {
+            env.storage().instance().set(&RampContractState::NativeRevenue, &current_asset_info.asset_revenue);
+        } else {
+            env.storage().instance().set(&asset_key, &current_asset_info);
+        }
+
+        receipt.status = ReceiptStatus::Refunded;
+        env.storage().persistent().set(&receipt_key, &receipt);
+        env.storage().persistent().extend_ttl(&receipt_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+        emit_deposit_refunded(env, reference, receipt, token.decimals(), refund_amount, refund_fee);
+        Ok(())
+    }
+
+    /// function off_ramp_withdraw
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `recipient` - The user making the deposit
+    /// * `asset` - The address of the asset whose fee is getting changed
+    /// * `amount` - amount deposited
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [OFFRAMP, asset, recipient]
+    /// * data - [OffRampWithdrawEvent]
+    /// * topics - [CREDIT_CREATED, asset, recipient] instead, when the transfer fails
+    /// * data - [CreditEvent]
+    ///
+    #[only_owner]
+    #[when_not_paused]
+    pub fn off_ramp_withdraw(
+        env: &Env,
+        asset: Address,
+        recipient: Address,
+        amount: i128
+    ) -> Result<(), RampContractError> {
+        Self::payout(env, asset, recipient, amount)
+    }
+
+    /// function batch_off_ramp_withdraw
+    /// pays several recipients of one asset, checking liquidity once for the batch total
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `asset` - The address of the asset paid out
+    /// * `payouts` - (recipient, amount) pairs
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [OFFRAMP, asset, recipient] for each payout
+    /// * data - [OffRampWithdrawEvent]
+    /// * topics - [CREDIT_CREATED, asset, recipient] instead, for each payout whose transfer fails
+    /// * data - [CreditEvent]
+    #[only_owner]
+    #[when_not_paused]
+    pub fn batch_off_ramp_withdraw(env: &Env, asset: Address, payouts: Vec<(Address, i128)>) -> Result<(), RampContractError> {
+        if payouts.is_empty() {
+            return Err(RampContractError::InvalidBatch);
+        }
+        let asset_key = RampContractState::AssetsInfo(asset.clone());
+        let current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
+        if !current_asset_info.is_added {
+            return Err(RampContractError::AssetNotAllowed);
+        }
+        if current_asset_info.withdrawals_paused {
+            return Err(RampContractError::AssetWithdrawalsPaused);
+        }
+
+        let mut total: i128 = 0;
+        for (recipient, amount) in payouts.iter() {
+            if amount <= 0 {
+                return Err(RampContractError::InvalidBatch);
+            }
+            Self::check_access(env, &recipient)?;
+            total += amount;
+        }
+
+        let token = token::Client::new(env, &asset);
+        let available = Self::available_liquidity(env, &token, &current_asset_info);
+        if available < total {
+            return Err(RampContractError::InsufficientFunds);
+        }
+
+        let decimals = token.decimals();
+        for (recipient, amount) in payouts.iter() {
+            Self::deliver(env, &token, recipient, decimals, amount);
+        }
+        Self::check_low_water_mark(env, asset, decimals, available, available - total);
+        Ok(())
+    }
+
+    /// function get_native_asset
+    /// returns the address of the native XLM Stellar Asset Contract on the current network
+    pub fn get_native_asset(env: &Env) -> Address {
+        env.deployer()
+            .with_stellar_asset(Bytes::from_array(env, &NATIVE_ASSET_XDR))
+            .deployed_address()
+    }
+
+    pub fn get_native_fee_percentage(env: &Env) -> i128 {
+        env.storage().instance().get(&RampContractState::NativeFeePercentage).unwrap_or(0)
+    }
+
+    pub fn get_native_revenue(env: &Env) -> i128 {
+        env.storage().instance().get(&RampContractState::NativeRevenue).unwrap_or(0)
+    }
+
+    fn get_native_paused(env: &Env) -> (bool, bool) {
+        env.storage().instance().get(&RampContractState::NativePaused).unwrap_or((false, false))
+    }
+
+    /// function get_native_liquidity
+    /// returns the XLM balance that can be paid out, excluding native revenue and credits
+    pub fn get_native_liquidity(env: &Env) -> i128 {
+        Self::native_liquidity(env, &token::Client::new(env, &Self::get_native_asset(env)))
+    }
+
+    /// function set_native_fee_percentage 
+    /// changes the fee charged on native XLM deposits
+    /// 
     /// # Arguments
     /// 
     /// * `env` - The environment in which the function gets run
-    /// * `recipient` - The user making the deposit
-    /// * `asset` - The address of the asset whose fee is getting changed
-    /// * `amount` - amount deposited
+    /// * `new_fee_percentage` - the new fee on XLM
     /// 
     /// # Event emitted
     /// 
-    /// * topics - [OFFRAMP, asset, recipient]
-    /// * data - [OffRampWithdrawEvent]
-    ///
+    /// * topics - [ASSET_FEE_PERCENTAGE_CHANGED, native_asset]
+    /// * data - [AssetFeeChangedEvent]
+    #[only_owner]
     #[when_not_paused]
-    pub fn off_ramp_withdraw(
+    pub fn set_native_fee_percentage(env: &Env, new_fee_percentage: i128) -> Result<(), RampContractError> {
+        if !(0..=60).contains(&new_fee_percentage) {
+            return Err(RampContractError::InvalidFeePercentage);
+        }
+        let old_fee = Self::get_native_fee_percentage(env);
+        env.storage().instance().set(&RampContractState::NativeFeePercentage, &new_fee_percentage);
+        emit_asset_fee_percentage_changed(env, Self::get_native_asset(env), old_fee, new_fee_percentage);
+        Ok(())
+    }
+
+    /// function native_deposit 
+    /// deposits XLM into the contract from the sender, like onramp_deposit with the
+    /// native fee percentage
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `amount` - amount of stroops deposited, pulled from the sender's allowance
+    /// * `sender` - The user making the deposit
+    /// * `params` - The medium, region, user data and unique order reference of the deposit
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ONRAMP, native_asset, sender]
+    /// * data - [OnRampDepositEvent]
+    #[when_not_paused]
+    pub fn native_deposit(
         env: &Env,
-        asset: Address,
-        recipient: Address,
-        amount: i128
+        amount: i128,
+        sender: Address,
+        params: DepositParams
     ) -> Result<(), RampContractError> {
+        let receipt_key = RampContractState::DepositReceipt(params.reference.clone());
+        if env.storage().persistent().has(&receipt_key) {
+            return Err(RampContractError::DuplicateReference);
+        }
+        if amount <= 0 {
+            return Err(RampContractError::InvalidAmount);
+        }
+
+        let region_fee_percentage = Self::region_fee_percentage(env, &params.region, &params.medium)?;
+        Self::check_access(env, &sender)?;
+
+        let asset = Self::get_native_asset(env);
+        if Self::is_asset_deposit_paused(env, asset.clone()) {
+            return Err(RampContractError::AssetDepositsPaused);
+        }
+        let token = token::Client::new(env, &asset);
+        let native_info = AssetInfo {
+            is_added: true,
+            asset_fee_percentage: Self::get_native_fee_percentage(env),
+            ..Default::default()
+        };
+        let fee = Self::compute_fee(env, &asset, &native_info, amount, region_fee_percentage);
+
+        let current_address = env.current_contract_address();
+        token.transfer_from(&current_address, &sender, &current_address, &amount);
+
+        let revenue = Self::get_native_revenue(env) + fee;
+        env.storage().instance().set(&RampContractState::NativeRevenue, &revenue);
+
+        let sequence = Self::get_deposit_sequence(env) + 1;
+        env.storage().instance().set(&RampContractState::DepositSequence, &sequence);
+
+        let receipt = DepositReceipt {
+            sequence,
+            asset,
+            sender,
+            amount,
+            fee,
+            status: ReceiptStatus::Deposited,
+            expires_at: 0,
+        };
+        env.storage().persistent().set(&receipt_key, &receipt);
+        env.storage().persistent().extend_ttl(&receipt_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+        emit_onramp_deposit_event(env, receipt, token.decimals(), params);
+        Ok(())
+    }
+
+    /// function native_withdraw
+    /// pays XLM liquidity out to the recipient
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `recipient` - The user receiving the XLM
+    /// * `amount` - amount of stroops paid out
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [OFFRAMP, native_asset, recipient]
+    /// * data - [OffRampWithdrawEvent]
+    /// * topics - [CREDIT_CREATED, native_asset, recipient] instead, when the transfer fails
+    /// * data - [CreditEvent]
+    #[only_owner]
+    #[when_not_paused]
+    pub fn native_withdraw(env: &Env, recipient: Address, amount: i128) -> Result<(), RampContractError> {
+        if amount <= 0 {
+            return Err(RampContractError::InvalidAmount);
+        }
+        Self::check_access(env, &recipient)?;
+
+        let token = token::Client::new(env, &Self::get_native_asset(env));
+        if Self::is_asset_withdrawal_paused(env, token.address.clone()) {
+            return Err(RampContractError::AssetWithdrawalsPaused);
+        }
+        if Self::native_liquidity(env, &token) < amount {
+            return Err(RampContractError::InsufficientFunds);
+        }
+        Self::deliver(env, &token, recipient, token.decimals(), amount);
+        Ok(())
+    }
+
+    /// function withdraw_native_revenue 
+    /// sends the revenue generated by XLM deposits to the vault, or across the revenue split
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ASSET_REVENUE_WITHDRAWN, native_asset] for each beneficiary paid
+    /// * data - [RevenueWithdrawnEvent]
+    #[only_owner]
+    #[when_not_paused]
+    pub fn withdraw_native_revenue(env: &Env) -> Result<(), RampContractError> {
+        let asset = Self::get_native_asset(env);
+        let token = token::Client::new(env, &asset);
+        let revenue = Self::get_native_revenue(env);
+        if token.balance(&env.current_contract_address()) < revenue {
+            return Err(RampContractError::InsufficientFunds);
+        }
+        Self::distribute_revenue(env, &asset, &token, revenue)?;
+        env.storage().instance().set(&RampContractState::NativeRevenue, &0i128);
+        Ok(())
+    }
+
+    /// function claim
+    /// pays out the credit of a recipient whose payouts could not be delivered, callable
+    /// once the recipient can receive the asset and its withdrawals are not paused
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `recipient` - The credited recipient, has to authorize the claim
+    /// * `asset` - The address of the credited asset
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [CREDIT_CLAIMED, asset, recipient]
+    /// * data - [CreditEvent]
+    #[when_not_paused]
+    pub fn claim(env: &Env, recipient: Address, asset: Address) -> Result<(), RampContractError> {
+        recipient.require_auth();
+        Self::check_access(env, &recipient)?;
+        if Self::is_asset_withdrawal_paused(env, asset.clone()) {
+            return Err(RampContractError::AssetWithdrawalsPaused);
+        }
+
+        let amount = Self::get_credit(env, recipient.clone(), asset.clone());
+        if amount <= 0 {
+            return Err(RampContractError::NoCredit);
+        }
+        env.storage().persistent().remove(&RampContractState::Credit(recipient.clone(), asset.clone()));
+        let credited_total = Self::get_credited_total(env, asset.clone()) - amount;
+        env.storage().instance().set(&RampContractState::CreditedTotal(asset.clone()), &credited_total);
+
+        let token = token::Client::new(env, &asset);
+        token.transfer(&env.current_contract_address(), &recipient, &amount);
+
+        emit_credit_claimed(env, asset, recipient, token.decimals(), amount);
+        Ok(())
+    }
+
+    pub fn get_credit(env: &Env, recipient: Address, asset: Address) -> i128 {
+        env.storage().persistent().get(&RampContractState::Credit(recipient, asset)).unwrap_or(0)
+    }
+
+    pub fn get_credited_total(env: &Env, asset: Address) -> i128 {
+        env.storage().instance().get(&RampContractState::CreditedTotal(asset)).unwrap_or(0)
+    }
+
+    /// function set_voucher_signer
+    /// sets or rotates the ed25519 key whose vouchers authorize withdrawals to the key of an
+    /// elapsed `SetVoucherSigner` proposal
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `signer` - The new ed25519 public key
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [TIMELOCK_EXECUTED, kind]
+    /// * data - [TimelockProposal]
+    /// * topics - [VOUCHER_SIGNER_CHANGED]
+    /// * data - [VoucherSignerChangedEvent]
+    #[only_owner]
+    pub fn set_voucher_signer(env: &Env, signer: BytesN<32>) -> Result<(), RampContractError> {
+        Self::execute_proposal(env, TimelockAction::SetVoucherSigner(signer.clone()))?;
+        let old_signer = Self::get_voucher_signer(env);
+        env.storage().instance().set(&RampContractState::VoucherSigner, &signer);
+        emit_voucher_signer_changed(env, old_signer, signer);
+        Ok(())
+    }
+
+    pub fn get_voucher_signer(env: &Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&RampContractState::VoucherSigner)
+    }
+
+    pub fn is_voucher_nonce_used(env: &Env, nonce: u64) -> bool {
+        env.storage().persistent().has(&RampContractState::VoucherNonce(nonce))
+    }
+
+    /// function voucher_withdraw
+    /// pays out a voucher signed by the voucher signer, callable by anyone
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `voucher` - The signed payout
+    /// * `signature` - ed25519 signature over the XDR encoded voucher
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [OFFRAMP, asset, recipient]
+    /// * data - [OffRampWithdrawEvent]
+    /// * topics - [VOUCHER_REDEEMED, nonce]
+    /// * data - [recipient]
+    #[when_not_paused]
+    pub fn voucher_withdraw(env: &Env, voucher: WithdrawVoucher, signature: BytesN<64>) -> Result<(), RampContractError> {
+        let signer = Self::get_voucher_signer(env).ok_or(RampContractError::VoucherSignerNotSet)?;
+
+        if voucher.contract != env.current_contract_address() {
+            return Err(RampContractError::InvalidVoucher);
+        }
+        if env.ledger().timestamp() >= voucher.expires_at {
+            return Err(RampContractError::VoucherExpired);
+        }
+        let nonce_key = RampContractState::VoucherNonce(voucher.nonce);
+        if env.storage().persistent().has(&nonce_key) {
+            return Err(RampContractError::NonceAlreadyUsed);
+        }
+
+        // traps the invocation if the signature does not match
+        env.crypto().ed25519_verify(&signer, &voucher.clone().to_xdr(env), &signature);
+
+        env.storage().persistent().set(&nonce_key, &true);
+        env.storage().persistent().extend_ttl(&nonce_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+        Self::payout(env, voucher.asset, voucher.recipient.clone(), voucher.amount)?;
+        emit_voucher_redeemed(env, voucher.nonce, voucher.recipient);
+        Ok(())
+    }
+
+    /// function update_access_list
+    /// puts addresses on the deny list or allowlist, or removes them, so the lists can be
+    /// synced with the compliance provider in batches
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `addresses` - The addresses to update
+    /// * `status` - The list to put the addresses on, None removes them
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ACCESS_LIST_UPDATED, status]
+    /// * data - [addresses]
+    #[only_owner]
+    pub fn update_access_list(env: &Env, addresses: Vec<Address>, status: Option<AccessStatus>) -> Result<(), RampContractError> {
+        if addresses.is_empty() {
+            return Err(RampContractError::InvalidBatch);
+        }
+        for address in addresses.iter() {
+            let access_key = RampContractState::AccessList(address);
+            match status {
+                Some(status) => {
+                    env.storage().persistent().set(&access_key, &status);
+                    env.storage().persistent().extend_ttl(&access_key, ACCESS_TTL_THRESHOLD, ACCESS_TTL_EXTEND_TO);
+                },
+                None => env.storage().persistent().remove(&access_key),
+            }
+        }
+        emit_access_list_updated(env, addresses, status);
+        Ok(())
+    }
+
+    /// function set_allowlist_enabled
+    /// switches allowlist-only mode, where only allowed addresses can deposit or be paid
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `allowlist_enabled` - Whether allowlist-only mode is on
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [ALLOWLIST_MODE_CHANGED]
+    /// * data - [allowlist_enabled]
+    #[only_owner]
+    pub fn set_allowlist_enabled(env: &Env, allowlist_enabled: bool) {
+        env.storage().instance().set(&RampContractState::AllowlistEnabled, &allowlist_enabled);
+        emit_allowlist_mode_changed(env, allowlist_enabled);
+    }
+
+    pub fn is_allowlist_enabled(env: &Env) -> bool {
+        env.storage().instance().get(&RampContractState::AllowlistEnabled).unwrap_or(false)
+    }
+
+    pub fn get_access_status(env: &Env, address: Address) -> Option<AccessStatus> {
+        env.storage().persistent().get(&RampContractState::AccessList(address))
+    }
+}
+impl RampContract {
+    // denied addresses are always rejected, in allowlist mode addresses without an entry are too
+    fn check_access(env: &Env, address: &Address) -> Result<(), RampContractError> {
+        let access_key = RampContractState::AccessList(address.clone());
+        match env.storage().persistent().get::<_, AccessStatus>(&access_key) {
+            Some(AccessStatus::Denied) => Err(RampContractError::AddressDenied),
+            Some(AccessStatus::Allowed) => {
+                env.storage().persistent().extend_ttl(&access_key, ACCESS_TTL_THRESHOLD, ACCESS_TTL_EXTEND_TO);
+                Ok(())
+            },
+            None if Self::is_allowlist_enabled(env) => Err(RampContractError::AddressNotAllowed),
+            None => Ok(()),
+        }
+    }
+
+    // balance that can be paid out, revenue and escrowed deposits are not liquidity
+    fn available_liquidity(env: &Env, token: &token::Client, asset_info: &AssetInfo) -> i128 {
+        token.balance(&env.current_contract_address())
+            - asset_info.asset_revenue
+            - asset_info.asset_escrowed
+            - Self::get_credited_total(env, token.address.clone())
+    }
+
+    // XLM balance that can be paid out, native revenue and credits are not liquidity
+    fn native_liquidity(env: &Env, token: &token::Client) -> i128 {
+        token.balance(&env.current_contract_address())
+            - Self::get_native_revenue(env)
+            - Self::get_credited_total(env, token.address.clone())
+    }
+
+    // transfers a payout to the recipient. When the transfer fails, e.g. for a missing trustline
+    // or a deauthorized balance, the amount is credited to the recipient to claim later instead
+    fn deliver(env: &Env, token: &token::Client, recipient: Address, decimals: u32, amount: i128) {
+        let asset = token.address.clone();
+        if let Ok(Ok(())) = token.try_transfer(&env.current_contract_address(), &recipient, &amount) {
+            emit_off_ramp_event(env, asset, recipient, decimals, amount);
+            return;
+        }
+
+        let credit_key = RampContractState::Credit(recipient.clone(), asset.clone());
+        let credit = Self::get_credit(env, recipient.clone(), asset.clone()) + amount;
+        env.storage().persistent().set(&credit_key, &credit);
+        env.storage().persistent().extend_ttl(&credit_key, RECEIPT_TTL_THRESHOLD, RECEIPT_TTL_EXTEND_TO);
+
+        let credited_total = Self::get_credited_total(env, asset.clone()) + amount;
+        env.storage().instance().set(&RampContractState::CreditedTotal(asset.clone()), &credited_total);
+
+        emit_credit_created(env, asset, recipient, decimals, amount, credit);
+    }
+
+    // emits LIQUIDITY_LOW when a withdrawal crosses the asset's low-water mark, staying
+    // below it does not emit again
+    fn check_low_water_mark(env: &Env, asset: Address, decimals: u32, available_before: i128, available_after: i128) {
+        if let Some(low_water_mark) = Self::get_low_water_mark(env, asset.clone()) {
+            if available_before >= low_water_mark && available_after < low_water_mark {
+                emit_liquidity_low(env, asset, decimals, available_after, low_water_mark);
+            }
+        }
+    }
+
+    fn payout(env: &Env, asset: Address, recipient: Address, amount: i128) -> Result<(), RampContractError> {
         let asset_key = RampContractState::AssetsInfo(asset.clone());
+        Self::check_access(env, &recipient)?;
 
-        let current_asset_info: AssetInfo = env.storage().instance().get(&asset_key).unwrap_or_default();
+        let current_asset_info: AssetInfo = Self::load_asset_info(env, &asset_key);
         if !current_asset_info.is_added {
             Err(RampContractError::AssetNotAllowed)
+        } else if current_asset_info.withdrawals_paused {
+            Err(RampContractError::AssetWithdrawalsPaused)
         } else {
             let token = token::Client::new(env, &asset);
-            let current_address =  env.current_contract_address();
 
-            let token_balance = token.balance(&current_address);
+            let available = Self::available_liquidity(env, &token, &current_asset_info);
 
-            if !(token_balance - current_asset_info.asset_revenue) > amount {
+            if available < amount {
                 Err(RampContractError::InsufficientFunds)
             }else {
-                token.transfer(&current_address, &recipient, &amount);
-                emit_off_ramp_event(env, asset, recipient, amount);
+                let decimals = token.decimals();
+                Self::deliver(env, &token, recipient, decimals, amount);
+                Self::check_low_water_mark(env, asset, decimals, available, available - amount);
                 Ok(())
             }
         }
+    }
+
+    // pays `revenue` out to the split beneficiaries, or all of it to the vault without a
+    // split. Shares are floored and the rounding dust goes to the first beneficiary
+    fn distribute_revenue(env: &Env, asset: &Address, token: &token::Client, revenue: i128) -> Result<(), RampContractError> {
+        let mut split = Self::get_revenue_split(env);
+        if split.is_empty() {
+            let current_vault: Address = env
+                .storage()
+                .instance()
+                .get(&RampContractState::VaultAddress)
+                .ok_or(RampContractError::VaultAddressNotFound)?;
+            split.push_back((current_vault, TOTAL_WEIGHT_BPS));
+        }
+
+        let mut shares: Vec<i128> = Vec::new(env);
+        let mut distributed: i128 = 0;
+        for (_, weight) in split.iter() {
+            let share = revenue * weight as i128 / TOTAL_WEIGHT_BPS as i128;
+            distributed += share;
+            shares.push_back(share);
+        }
+        shares.set(0, shares.get_unchecked(0) + revenue - distributed);
+
+        let decimals = token.decimals();
+        let current_address = env.current_contract_address();
+        for ((beneficiary, _), share) in split.iter().zip(shares.iter()) {
+            if share <= 0 {
+                continue;
+            }
+            token.transfer(&current_address, &beneficiary, &share);
+            emit_asset_revenue_withdrawn(env, asset.clone(), beneficiary, decimals, share);
+        }
+        Ok(())
+    }
+
+    // a region override wins, then the schedule tier the amount reaches, then the flat
+    // percentage. The schedule caps apply on top and the fee never exceeds the amount
+    fn compute_fee(env: &Env, asset: &Address, asset_info: &AssetInfo, amount: i128, region_fee_percentage: Option<i128>) -> i128 {
+        let schedule = Self::get_fee_schedule(env, asset.clone());
+        let mut tier_bps = None;
+        if let Some(schedule) = &schedule {
+            for tier in schedule.tiers.iter() {
+                if amount >= tier.threshold {
+                    tier_bps = Some(tier.fee_bps);
+                }
+            }
+        }
+        let mut fee = match (region_fee_percentage, tier_bps) {
+            (Some(percentage), _) => (percentage * amount) / 100,
+            (None, Some(bps)) => (bps as i128 * amount) / 10_000,
+            (None, None) => (asset_info.asset_fee_percentage * amount) / 100,
+        };
+        if let Some(schedule) = schedule {
+            if let Some(min_fee) = schedule.min_fee {
+                fee = fee.max(min_fee);
+            }
+            if let Some(max_fee) = schedule.max_fee {
+                fee = fee.min(max_fee);
+            }
+        }
+        fee.min(amount)
+    }
+
+    fn validate_fee_schedule(schedule: &FeeSchedule) -> Result<(), RampContractError> {
+        if schedule.tiers.len() > MAX_FEE_TIERS {
+            return Err(RampContractError::InvalidFeeSchedule);
+        }
+        let mut previous_threshold: Option<i128> = None;
+        for tier in schedule.tiers.iter() {
+            if tier.threshold < 0 || tier.fee_bps > MAX_FEE_BPS || previous_threshold.is_some_and(|previous| tier.threshold <= previous) {
+                return Err(RampContractError::InvalidFeeSchedule);
+            }
+            previous_threshold = Some(tier.threshold);
+        }
+        match (schedule.min_fee, schedule.max_fee) {
+            (Some(min_fee), _) if min_fee < 0 => Err(RampContractError::InvalidFeeSchedule),
+            (_, Some(max_fee)) if max_fee < 0 => Err(RampContractError::InvalidFeeSchedule),
+            (Some(min_fee), Some(max_fee)) if min_fee > max_fee => Err(RampContractError::InvalidFeeSchedule),
+            _ => Ok(()),
+        }
+    }
+
+    // checks the region and medium are enabled, returning the region fee override if any
+    fn region_fee_percentage(env: &Env, region: &Symbol, medium: &Symbol) -> Result<Option<i128>, RampContractError> {
+        let region_info: Option<RegionInfo> = env
+            .storage()
+            .instance()
+            .get(&RampContractState::RegionInfo(region.clone()));
+
+        match region_info {
+            Some(info) if info.is_enabled && info.mediums.get(medium.clone()).unwrap_or(false) => Ok(info.fee_percentage),
+            _ => Err(RampContractError::RegionNotAllowed),
+        }
+    }
+
+    // a split has at most MAX_BENEFICIARIES distinct beneficiaries with non-zero weights summing
+    // to TOTAL_WEIGHT_BPS, or is empty
+    fn check_revenue_split(split: &Vec<(Address, u32)>) -> Result<(), RampContractError> {
+        if split.len() > MAX_BENEFICIARIES {
+            return Err(RampContractError::InvalidRevenueSplit);
+        }
+        let mut total_weight: u32 = 0;
+        for (i, (beneficiary, weight)) in split.iter().enumerate() {
+            if weight == 0 || split.iter().take(i).any(|(other, _)| other == beneficiary) {
+                return Err(RampContractError::InvalidRevenueSplit);
+            }
+            total_weight = total_weight.saturating_add(weight);
+        }
+        if !split.is_empty() && total_weight != TOTAL_WEIGHT_BPS {
+            return Err(RampContractError::InvalidRevenueSplit);
+        }
+        Ok(())
+    }
+
+    // consumes the pending proposal of the action's kind, which has to match the action and be due
+    fn execute_proposal(env: &Env, action: TimelockAction) -> Result<(), RampContractError> {
+        let kind = action.kind();
+        let proposal_key = RampContractState::TimelockProposal(kind);
+        let proposal: TimelockProposal = env
+            .storage()
+            .instance()
+            .get(&proposal_key)
+            .ok_or(RampContractError::ProposalNotFound)?;
+        if proposal.action != action {
+            return Err(RampContractError::ProposalMismatch);
+        }
+        if env.ledger().timestamp() < proposal.eta {
+            return Err(RampContractError::TimelockNotElapsed);
+        }
+        env.storage().instance().remove(&proposal_key);
+
+        emit_timelock_executed(env, kind, proposal);
+        Ok(())
+    }
+
+    // reads an asset entry, an entry still in the version 0 layout is rewritten with the new
+    // fields defaulted, so assets missing from the migration's `legacy_assets` stay readable
+    fn load_asset_info(env: &Env, asset_key: &RampContractState) -> AssetInfo {
+        // struct entries are stored as maps keyed by field name, the legacy one has three fields
+        let stored: Map<Symbol, Val> = match env.storage().instance().get(asset_key) {
+            Some(stored) => stored,
+            None => return AssetInfo::default(),
+        };
+        if stored.len() != 3 {
+            return AssetInfo::try_from_val(env, &stored.to_val())
+                .unwrap_or_else(|_| panic_with_error!(env, RampContractError::AssetNotFound));
+        }
+        let legacy_info = AssetInfoV0::try_from_val(env, &stored.to_val())
+            .unwrap_or_else(|_| panic_with_error!(env, RampContractError::AssetNotFound));
+        let asset_info = AssetInfo::from(legacy_info);
+        env.storage().instance().set(asset_key, &asset_info);
+        asset_info
+    }
 
+    // version 0 stored AssetInfoV0 and no asset list, so the listed assets are rewritten with the
+    // new fields defaulted and collected into the list
+    fn migrate_asset_info_v0(env: &Env, legacy_assets: &Vec<Address>) {
+        let mut assets = Self::get_assets(env);
+        for asset in legacy_assets.iter() {
+            let asset_key = RampContractState::AssetsInfo(asset.clone());
+            if !env.storage().instance().has(&asset_key) {
+                panic_with_error!(env, RampContractError::AssetNotFound);
+            }
+            let asset_info = Self::load_asset_info(env, &asset_key);
+            if asset_info.is_added && !assets.contains(&asset) {
+                assets.push_back(asset);
+            }
+        }
+        env.storage().instance().set(&RampContractState::AssetList, &assets);
     }
 }
+
 //Ownable implimatation for the contract    
+//a transfer started with `transfer_ownership` needs an elapsed `ChangeOwner` proposal of the new
+//owner, cancelling one with a `live_until_ledger` of 0 does not
 #[default_impl]
 #[contractimpl]
 impl Ownable for RampContract {
-    
+    fn transfer_ownership(e: &Env, new_owner: Address, live_until_ledger: u32) {
+        if live_until_ledger != 0 {
+            if let Err(error) = Self::execute_proposal(e, TimelockAction::ChangeOwner(new_owner.clone())) {
+                panic_with_error!(e, error);
+            }
+        }
+        ownable::transfer_ownership(e, &new_owner, live_until_ledger);
+    }
 }
 
 //Pausable implimatation for the contract
@@ -432,12 +2249,66 @@ impl Pausable for RampContract {
 }
 
 
-//Upgradeable implimatation for the contract
-impl UpgradeableInternal for RampContract {
+//Upgradeable implimatation for the contract. It follows `#[derive(UpgradeableMigratable)]`, except
+//that upgrade executes the timelocked `Upgrade` proposal of the new wasm hash
+#[contractimpl]
+impl UpgradeableMigratable for RampContract {
+    fn upgrade(env: &Env, new_wasm_hash: BytesN<32>, operator: Address) {
+        Self::_require_auth(env, &operator);
+        if let Err(error) = Self::execute_proposal(env, TimelockAction::Upgrade(new_wasm_hash.clone())) {
+            panic_with_error!(env, error);
+        }
+
+        upgradeable::enable_migration(env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    fn migrate(env: &Env, legacy_assets: Vec<Address>, operator: Address) {
+        Self::_require_auth(env, &operator);
+
+        upgradeable::ensure_can_complete_migration(env);
+
+        Self::_migrate(env, &legacy_assets);
+
+        upgradeable::complete_migration(env);
+    }
+}
+
+//the owner upgrades and then migrates while paused
+impl UpgradeableMigratableInternal for RampContract {
+    // assets listed by a version 0 contract, which kept no asset list
+    type MigrationData = Vec<Address>;
+
     #[when_paused]
     fn _require_auth(env: &Env, _operator: &Address) {
         ownable::enforce_owner_auth(env);
     }
+
+    /// function migrate
+    /// rewrites the entries stored by older versions to the current layout, once per version
+    /// 
+    /// # Arguments
+    /// 
+    /// * `env` - The environment in which the function gets run
+    /// * `legacy_assets` - The assets listed by a version 0 contract, ignored for later versions
+    /// 
+    /// # Event emitted
+    /// 
+    /// * topics - [CONTRACT_MIGRATED, from_version]
+    /// * data - [to_version]
+    fn _migrate(env: &Env, legacy_assets: &Vec<Address>) {
+        let from_version = Self::version(env);
+        if from_version >= STORAGE_VERSION {
+            panic_with_error!(env, RampContractError::AlreadyMigrated);
+        }
+        if from_version < 1 {
+            Self::migrate_asset_info_v0(env, legacy_assets);
+        }
+        env.storage().instance().set(&RampContractState::StorageVersion, &STORAGE_VERSION);
+
+        emit_contract_migrated(env, from_version, STORAGE_VERSION);
+    }
 }
 
 
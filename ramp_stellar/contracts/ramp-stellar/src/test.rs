@@ -1,8 +1,15 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Events}, Address, Env, IntoVal, Symbol};
+use soroban_sdk::{symbol_short, testutils::{Address as _, Events, IssuerFlags, Ledger, MockAuth, MockAuthInvoke}, Address, BytesN, Env, IntoVal, Symbol, Val};
 use soroban_sdk::vec;
+use soroban_sdk::xdr::{
+    AccountEntry, AccountEntryExt, AccountId, Hash, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey,
+    LedgerKeyAccount, PublicKey, ScAddress, SequenceNumber, Thresholds, Uint256,
+};
+use soroban_sdk::TryFromVal;
+extern crate std;
+use std::rc::Rc;
 
 mod ramp_token {
     // Compatible with OpenZeppelin Stellar Soroban Contracts ^0.4.1
@@ -51,6 +58,63 @@ mod ramp_token {
     impl Ownable for RampToken {}
 }
 
+// the last published event, as a one element Vec since events only compare inside a soroban Vec
+// the native SAC has no admin to mint with, so test XLM is held by classic accounts written
+// straight into the ledger
+fn register_native_asset(env: &Env) -> Address {
+    env.deployer().with_stellar_asset(Bytes::from_array(env, &[0u8; 4])).deploy()
+}
+
+fn create_native_account(env: &Env, balance: i64) -> Address {
+    let ScAddress::Contract(Hash(key)) = ScAddress::from(&Address::generate(env)) else {
+        unreachable!()
+    };
+    let account_id = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)));
+    let ledger_key = Rc::new(LedgerKey::Account(LedgerKeyAccount { account_id: account_id.clone() }));
+    let ledger_entry = Rc::new(LedgerEntry {
+        data: LedgerEntryData::Account(AccountEntry {
+            account_id: account_id.clone(),
+            balance,
+            flags: 0,
+            home_domain: Default::default(),
+            inflation_dest: None,
+            num_sub_entries: 0,
+            seq_num: SequenceNumber(0),
+            thresholds: Thresholds([1; 4]),
+            signers: Default::default(),
+            ext: AccountEntryExt::V0,
+        }),
+        last_modified_ledger_seq: 0,
+        ext: LedgerEntryExt::V0,
+    });
+    let budget = env.host().budget_cloned();
+    env.host()
+        .with_mut_storage(|storage| storage.put(&ledger_key, &ledger_entry, None, &budget))
+        .unwrap();
+    Address::try_from_val(env, &ScAddress::Account(account_id)).unwrap()
+}
+
+fn last_event(env: &Env) -> soroban_sdk::Vec<(Address, soroban_sdk::Vec<Val>, Val)> {
+    let events = env.events().all();
+    events.slice(events.len() - 1..)
+}
+
+// payment details of a test deposit paid with MPESA in KEN
+fn deposit_params(env: &Env, reference: BytesN<32>) -> DepositParams {
+    DepositParams {
+        medium: symbol_short!("MPESA"),
+        region: symbol_short!("KEN"),
+        data: Bytes::from_slice(env, b"test"),
+        reference,
+    }
+}
+
+// proposes a timelocked action and moves the ledger to the time it can be executed
+fn elapse_proposal(env: &Env, client: &RampContractClient, action: TimelockAction) {
+    let eta = client.propose(&action);
+    env.ledger().set_timestamp(eta);
+}
+
 #[test]
 fn test_add_asset() {
     let env = Env::default();
@@ -71,6 +135,7 @@ fn test_add_asset() {
     let data = AssetAddedEvent {
         funder: test_admin.clone(),
         fee_percentage: 1i128,
+        decimals: 18,
         initial_balance: 1000i128,
     }.into_val(&env);
     assert_eq!(
@@ -111,17 +176,27 @@ fn test_remove_asset() {
 
     assert_eq!(token_client.balance(&contract_id), 1000i128);
 
+    // delisting returns all liquidity, so it waits for the timelock
+    assert_eq!(client.try_remove_asset(&token_id, &test_admin), Err(Ok(RampContractError::ProposalNotFound)));
+    let action = TimelockAction::RemoveAsset(token_id.clone(), test_admin.clone());
+    elapse_proposal(&env, &client, action.clone());
     client.remove_asset(&token_id, &test_admin);
 
     let topics = (Symbol::new(&env, "ASSET_REMOVED"), token_id.clone()).into_val(&env);
     let data = AssetRemovedEvent {
         balance_receipient: test_admin.clone(),
+        decimals: 18,
         amount: 1000i128,
     }.into_val(&env);
     assert_eq!(
         env.events().all(),
         vec![
             &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "TIMELOCK_EXECUTED"), TimelockKind::RemoveAsset).into_val(&env),
+                TimelockProposal { action, eta: client.get_timelock_delay() }.into_val(&env),
+            ),
             (
                 token_id.clone(),
                 (Symbol::new(&env, "transfer"), contract_id.clone(), test_admin.clone()).into_val(&env),
@@ -145,8 +220,15 @@ fn test_change_owner() {
     let vault_address = Address::generate(&env);
     let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
     let client = RampContractClient::new(&env, &contract_id);
-    assert_eq!(client.get_owner(), Some(test_admin));
+    assert_eq!(client.get_owner(), Some(test_admin.clone()));
     let new_owner = Address::generate(&env);
+
+    // the transfer waits for a ChangeOwner proposal of the new owner
+    assert!(client.try_transfer_ownership(&new_owner, &(env.ledger().sequence() + 100)).is_err());
+    elapse_proposal(&env, &client, TimelockAction::ChangeOwner(test_admin.clone()));
+    assert!(client.try_transfer_ownership(&new_owner, &(env.ledger().sequence() + 100)).is_err());
+    client.cancel_proposal(&test_admin, &TimelockKind::ChangeOwner);
+    elapse_proposal(&env, &client, TimelockAction::ChangeOwner(new_owner.clone()));
     client.transfer_ownership(&new_owner, &(env.ledger().sequence() + 100));
     client.accept_ownership();
     assert_eq!(client.get_owner(), Some(new_owner));
@@ -170,6 +252,8 @@ fn test_change_vault_address() {
     let client = RampContractClient::new(&env, &contract_id);
     assert_eq!(client.get_vault_address(), vault_address);
     let new_vault_address = Address::generate(&env);
+    client.propose(&TimelockAction::ChangeVaultAddress(new_vault_address.clone()));
+    env.ledger().set_timestamp(client.get_timelock_delay());
     client.change_vault_address(&new_vault_address);
     assert_eq!(client.get_vault_address(), new_vault_address);
 }
@@ -247,19 +331,26 @@ fn test_onramp_deposit() {
     client.add_asset(&token_id, &test_admin, &1i128);
     assert_eq!(token_client.balance(&contract_id), user_init_balance);
 
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
     let onramp_amount = 1000i128;
     let fee = client.get_asset_fee_percentage(&token_id);
 
     let fee_amount = onramp_amount * fee / 100;
     token_client.approve(&test_user, &contract_id, &onramp_amount, &(env.ledger().sequence() + 100));
-    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &OnrampMedium::Primary, &Region::KEN, &Bytes::from_slice(&env, b"test"));
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
 
     let topics = (Symbol::new(&env, "ONRAMP"), token_id.clone(), test_user.clone()).into_val(&env);
     let data = OnRampDepositEvent {
-        amount: onramp_amount - fee_amount,
-        medium: OnrampMedium::Primary,
-        region: Region::KEN,
-        data: Bytes::from_slice(&env, b"test")
+        decimals: 18,
+        gross_amount: onramp_amount,
+        fee: fee_amount,
+        net_amount: onramp_amount - fee_amount,
+        medium: symbol_short!("MPESA"),
+        region: symbol_short!("KEN"),
+        data: Bytes::from_slice(&env, b"test"),
+        reference: BytesN::from_array(&env, &[1u8; 32]),
+        sequence: 1
     }.into_val(&env);
     assert_eq!(
         env.events().all(),
@@ -306,15 +397,15 @@ fn test_withdraw_asset_revenue() {
     client.add_asset(&token_id, &test_admin, &1i128);
     assert_eq!(token_client.balance(&contract_id), user_init_balance);
 
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
     let onramp_amount = 1000i128;
     token_client.approve(&test_user, &contract_id, &onramp_amount, &(env.ledger().sequence() + 100));
     client.onramp_deposit(
         &token_id,
         &onramp_amount,
         &test_user,
-        &OnrampMedium::Primary,
-        &Region::KEN,
-        &Bytes::from_slice(&env, b"test")
+        &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32]))
     );
 
     assert_eq!(token_client.balance(&contract_id), user_init_balance + onramp_amount);
@@ -324,6 +415,7 @@ fn test_withdraw_asset_revenue() {
     let topics = (Symbol::new(&env, "ASSET_REVENUE_WITHDRAWN"), token_id.clone()).into_val(&env);
     let data = RevenueWithdrawnEvent {
         recipient: client.get_vault_address(),
+        decimals: 18,
         amount: asset_revenue
     }.into_val(&env);
     client.withdraw_asset_revenue(&token_id);
@@ -374,9 +466,11 @@ fn test_offramp_withdraw() {
     client.add_asset(&token_id, &test_admin, &1i128);
     assert_eq!(token_client.balance(&contract_id), user_init_balance);
 
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
     let onramp_amount = 1000i128;
     token_client.approve(&test_user, &contract_id, &onramp_amount, &(env.ledger().sequence() + 100));
-    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &OnrampMedium::Primary, &Region::KEN, &Bytes::from_slice(&env, b"test"));
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
 
     assert_eq!(token_client.balance(&contract_id), user_init_balance + onramp_amount);
     assert_eq!(token_client.balance(&test_user), user_init_balance - onramp_amount);
@@ -385,6 +479,7 @@ fn test_offramp_withdraw() {
 
     let topics = (Symbol::new(&env, "OFFRAMP"), token_id.clone(), recipient.clone()).into_val(&env);
     let data = OffRampWithdrawEvent {
+        decimals: 18,
         amount: onramp_amount,
     }.into_val(&env);
     assert_eq!(
@@ -403,4 +498,1386 @@ fn test_offramp_withdraw() {
             ),
         ]
     );
-}
\ No newline at end of file
+
+    // only the owner can pay out liquidity
+    env.mock_auths(&[MockAuth {
+        address: &test_user,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "off_ramp_withdraw",
+            args: (token_id.clone(), test_user.clone(), onramp_amount).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_off_ramp_withdraw(&token_id, &test_user, &onramp_amount).is_err());
+    assert_eq!(token_client.balance(&test_user), user_init_balance - onramp_amount);
+}
+
+#[test]
+fn test_region_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let client = RampContractClient::new(&env, &contract_id);
+
+    let region = symbol_short!("KEN");
+    client.add_region(&region, &vec![&env, symbol_short!("MPESA")]);
+    assert_eq!(client.try_add_region(&region, &vec![&env]), Err(Ok(RampContractError::RegionAlreadyExists)));
+
+    client.add_region_medium(&region, &symbol_short!("AIRTEL"));
+    assert_eq!(
+        client.try_add_region_medium(&region, &symbol_short!("AIRTEL")),
+        Err(Ok(RampContractError::MediumAlreadyExists))
+    );
+
+    client.set_medium_enabled(&region, &symbol_short!("AIRTEL"), &false);
+    client.set_region_fee_percentage(&region, &Some(2i128));
+
+    let region_info = client.get_region(&region).unwrap();
+    assert!(region_info.is_enabled);
+    assert_eq!(region_info.fee_percentage, Some(2i128));
+    assert_eq!(region_info.mediums.get(symbol_short!("MPESA")), Some(true));
+    assert_eq!(region_info.mediums.get(symbol_short!("AIRTEL")), Some(false));
+
+    client.set_region_enabled(&region, &false);
+    assert!(!client.get_region(&region).unwrap().is_enabled);
+    assert_eq!(client.get_region(&symbol_short!("NGA")), None);
+    assert_eq!(
+        client.try_set_region_enabled(&symbol_short!("NGA"), &true),
+        Err(Ok(RampContractError::RegionNotFound))
+    );
+}
+
+#[test]
+fn test_onramp_deposit_region_not_allowed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_user, &10000i128);
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA"), symbol_short!("AIRTEL")]);
+    client.set_medium_enabled(&symbol_short!("KEN"), &symbol_short!("AIRTEL"), &false);
+    client.set_region_fee_percentage(&symbol_short!("KEN"), &Some(5i128));
+
+    let onramp_amount = 1000i128;
+    token_client.approve(&test_user, &contract_id, &onramp_amount, &(env.ledger().sequence() + 100));
+
+    // unregistered region
+    assert_eq!(
+        client.try_onramp_deposit(&token_id, &onramp_amount, &test_user, &DepositParams { medium: symbol_short!("MPESA"), region: symbol_short!("NGA"), data: Bytes::from_slice(&env, b"test"), reference: BytesN::from_array(&env, &[1u8; 32]) }),
+        Err(Ok(RampContractError::RegionNotAllowed))
+    );
+    // disabled medium
+    assert_eq!(
+        client.try_onramp_deposit(&token_id, &onramp_amount, &test_user, &DepositParams { medium: symbol_short!("AIRTEL"), region: symbol_short!("KEN"), data: Bytes::from_slice(&env, b"test"), reference: BytesN::from_array(&env, &[1u8; 32]) }),
+        Err(Ok(RampContractError::RegionNotAllowed))
+    );
+
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+    // region override replaces the 1% asset fee
+    assert_eq!(client.get_asset_revenue(&token_id), onramp_amount * 5 / 100);
+}
+
+#[test]
+fn test_onramp_deposit_receipt() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_user, &10000i128);
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
+    let onramp_amount = 1000i128;
+    let first_reference = BytesN::from_array(&env, &[1u8; 32]);
+    let second_reference = BytesN::from_array(&env, &[2u8; 32]);
+    token_client.approve(&test_user, &contract_id, &(onramp_amount * 3), &(env.ledger().sequence() + 100));
+
+    assert_eq!(client.get_deposit_receipt(&first_reference), None);
+    // zero and negative deposits do not use up the reference
+    assert_eq!(
+        client.try_onramp_deposit(&token_id, &0i128, &test_user, &deposit_params(&env, first_reference.clone())),
+        Err(Ok(RampContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_onramp_deposit(&token_id, &-1i128, &test_user, &deposit_params(&env, first_reference.clone())),
+        Err(Ok(RampContractError::InvalidAmount))
+    );
+    assert_eq!(client.get_deposit_receipt(&first_reference), None);
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, first_reference.clone()));
+
+    // the same order cannot be deposited twice
+    assert_eq!(
+        client.try_onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, first_reference.clone())),
+        Err(Ok(RampContractError::DuplicateReference))
+    );
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, second_reference.clone()));
+
+    assert_eq!(
+        client.get_deposit_receipt(&first_reference),
+        Some(DepositReceipt {
+            sequence: 1,
+            asset: token_id.clone(),
+            sender: test_user.clone(),
+            amount: onramp_amount,
+            fee: onramp_amount / 100,
+            status: ReceiptStatus::Deposited,
+            expires_at: 0,
+        })
+    );
+    assert_eq!(client.get_deposit_receipt(&second_reference).unwrap().sequence, 2);
+    assert_eq!(client.get_deposit_sequence(), 2);
+    assert_eq!(token_client.balance(&contract_id), onramp_amount * 2);
+}
+
+#[test]
+fn test_refund_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_user, &10000i128);
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
+    let onramp_amount = 1000i128;
+    let fee = onramp_amount / 100;
+    let first_reference = BytesN::from_array(&env, &[1u8; 32]);
+    let second_reference = BytesN::from_array(&env, &[2u8; 32]);
+    token_client.approve(&test_user, &contract_id, &(onramp_amount * 2), &(env.ledger().sequence() + 100));
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, first_reference.clone()));
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, second_reference.clone()));
+    assert_eq!(client.get_asset_revenue(&token_id), fee * 2);
+
+    assert_eq!(
+        client.try_refund_deposit(&BytesN::from_array(&env, &[9u8; 32]), &true),
+        Err(Ok(RampContractError::ReceiptNotFound))
+    );
+
+    // denied senders cannot receive refunds
+    client.update_access_list(&vec![&env, test_user.clone()], &Some(AccessStatus::Denied));
+    assert_eq!(
+        client.try_refund_deposit(&first_reference, &true),
+        Err(Ok(RampContractError::AddressDenied))
+    );
+    client.update_access_list(&vec![&env, test_user.clone()], &None);
+
+    // full refund returns the fee and removes it from revenue
+    client.refund_deposit(&first_reference, &true);
+    assert_eq!(token_client.balance(&test_user), 10000 - onramp_amount);
+    assert_eq!(client.get_asset_revenue(&token_id), fee);
+    assert_eq!(client.get_deposit_receipt(&first_reference).unwrap().status, ReceiptStatus::Refunded);
+
+    // net refund keeps the fee as revenue
+    client.refund_deposit(&second_reference, &false);
+    assert_eq!(token_client.balance(&test_user), 10000 - fee);
+    assert_eq!(client.get_asset_revenue(&token_id), fee);
+    assert_eq!(token_client.balance(&contract_id), fee);
+
+    assert_eq!(
+        client.try_refund_deposit(&first_reference, &true),
+        Err(Ok(RampContractError::DepositAlreadyRefunded))
+    );
+
+    // refunds are paid from liquidity, the revenue of other deposits is not touched
+    let third_reference = BytesN::from_array(&env, &[3u8; 32]);
+    token_client.approve(&test_user, &contract_id, &onramp_amount, &(env.ledger().sequence() + 100));
+    client.onramp_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, third_reference.clone()));
+    client.off_ramp_withdraw(&token_id, &Address::generate(&env), &fee);
+    assert_eq!(token_client.balance(&contract_id), onramp_amount);
+    assert_eq!(
+        client.try_refund_deposit(&third_reference, &false),
+        Err(Ok(RampContractError::InsufficientFunds))
+    );
+    assert_eq!(
+        client.try_refund_deposit(&third_reference, &true),
+        Err(Ok(RampContractError::InsufficientFunds))
+    );
+}
+
+#[test]
+fn test_escrow_deposit_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_user, &10000i128);
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
+    let onramp_amount = 1000i128;
+    let fee = onramp_amount / 100;
+    let released_reference = BytesN::from_array(&env, &[1u8; 32]);
+    let reclaimed_reference = BytesN::from_array(&env, &[2u8; 32]);
+    token_client.approve(&test_user, &contract_id, &(onramp_amount * 2), &(env.ledger().sequence() + 100));
+
+    // expiry has to be strictly in the future
+    assert_eq!(
+        client.try_escrow_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, released_reference.clone()), &1000u64),
+        Err(Ok(RampContractError::InvalidExpiry))
+    );
+    // empty or negative escrows are rejected
+    assert_eq!(
+        client.try_escrow_deposit(&token_id, &0i128, &test_user, &deposit_params(&env, released_reference.clone()), &1100u64),
+        Err(Ok(RampContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_escrow_deposit(&token_id, &-1i128, &test_user, &deposit_params(&env, released_reference.clone()), &1100u64),
+        Err(Ok(RampContractError::InvalidAmount))
+    );
+    assert!(client.get_deposit_receipt(&released_reference).is_none());
+    client.escrow_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, released_reference.clone()), &1100u64);
+    client.escrow_deposit(&token_id, &onramp_amount, &test_user, &deposit_params(&env, reclaimed_reference.clone()), &1100u64);
+    assert_eq!(client.get_asset_revenue(&token_id), 0);
+    assert_eq!(client.get_deposit_receipt(&released_reference).unwrap().status, ReceiptStatus::Escrowed);
+
+    // escrowed funds are not available for payouts
+    assert_eq!(
+        client.try_off_ramp_withdraw(&token_id, &Address::generate(&env), &1i128),
+        Err(Ok(RampContractError::InsufficientFunds))
+    );
+
+    // one second before expiry the operator can still release, the sender cannot reclaim
+    env.ledger().set_timestamp(1099);
+    assert_eq!(
+        client.try_reclaim_escrow(&reclaimed_reference),
+        Err(Ok(RampContractError::EscrowNotExpired))
+    );
+    client.release_escrow(&released_reference);
+    assert_eq!(client.get_asset_revenue(&token_id), fee);
+    assert_eq!(client.get_deposit_receipt(&released_reference).unwrap().status, ReceiptStatus::Released);
+    assert_eq!(
+        client.try_reclaim_escrow(&released_reference),
+        Err(Ok(RampContractError::InvalidEscrowState))
+    );
+
+    // at the expiry timestamp the escrow belongs back to the sender
+    env.ledger().set_timestamp(1100);
+    assert_eq!(
+        client.try_release_escrow(&reclaimed_reference),
+        Err(Ok(RampContractError::EscrowExpired))
+    );
+    client.reclaim_escrow(&reclaimed_reference);
+    assert_eq!(token_client.balance(&test_user), 10000 - onramp_amount);
+    assert_eq!(token_client.balance(&contract_id), onramp_amount);
+    assert_eq!(client.get_deposit_receipt(&reclaimed_reference).unwrap().status, ReceiptStatus::Reclaimed);
+    assert_eq!(
+        client.try_reclaim_escrow(&reclaimed_reference),
+        Err(Ok(RampContractError::InvalidEscrowState))
+    );
+}
+
+#[test]
+fn test_voucher_withdraw() {
+    extern crate std;
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let test_admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_admin, &10000i128);
+    token_client.approve(&test_admin, &contract_id, &10000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+
+    // the signer only takes effect once its proposal has elapsed
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let signer = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    let eta = client.propose(&TimelockAction::SetVoucherSigner(signer.clone()));
+    assert_eq!(client.try_set_voucher_signer(&signer), Err(Ok(RampContractError::TimelockNotElapsed)));
+    env.ledger().set_timestamp(eta);
+
+    let sign = |key: &SigningKey, voucher: &WithdrawVoucher| {
+        let message: std::vec::Vec<u8> = voucher.clone().to_xdr(&env).iter().collect();
+        BytesN::from_array(&env, &key.sign(&message).to_bytes())
+    };
+    let voucher = WithdrawVoucher {
+        contract: contract_id.clone(),
+        recipient: recipient.clone(),
+        asset: token_id.clone(),
+        amount: 1000,
+        nonce: 1,
+        expires_at: eta + 100,
+    };
+
+    assert_eq!(
+        client.try_voucher_withdraw(&voucher, &sign(&signing_key, &voucher)),
+        Err(Ok(RampContractError::VoucherSignerNotSet))
+    );
+    client.set_voucher_signer(&signer);
+
+    client.voucher_withdraw(&voucher, &sign(&signing_key, &voucher));
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert!(client.is_voucher_nonce_used(&1));
+
+    // a nonce can only be redeemed once
+    assert_eq!(
+        client.try_voucher_withdraw(&voucher, &sign(&signing_key, &voucher)),
+        Err(Ok(RampContractError::NonceAlreadyUsed))
+    );
+
+    let other_contract = WithdrawVoucher { contract: Address::generate(&env), nonce: 2, ..voucher.clone() };
+    assert_eq!(
+        client.try_voucher_withdraw(&other_contract, &sign(&signing_key, &other_contract)),
+        Err(Ok(RampContractError::InvalidVoucher))
+    );
+
+    env.ledger().set_timestamp(eta + 100);
+    let expired = WithdrawVoucher { nonce: 3, ..voucher.clone() };
+    assert_eq!(
+        client.try_voucher_withdraw(&expired, &sign(&signing_key, &expired)),
+        Err(Ok(RampContractError::VoucherExpired))
+    );
+
+    // a tampered voucher does not verify
+    let signed = WithdrawVoucher { nonce: 4, expires_at: eta + 1_000_000, ..voucher.clone() };
+    let tampered = WithdrawVoucher { amount: 5000, ..signed.clone() };
+    assert!(client.try_voucher_withdraw(&tampered, &sign(&signing_key, &signed)).is_err());
+
+    // after rotation vouchers from the old key are rejected
+    let rotated_key = SigningKey::from_bytes(&[8u8; 32]);
+    let rotated_signer = BytesN::from_array(&env, &rotated_key.verifying_key().to_bytes());
+    elapse_proposal(&env, &client, TimelockAction::SetVoucherSigner(rotated_signer.clone()));
+    client.set_voucher_signer(&rotated_signer);
+    assert!(client.try_voucher_withdraw(&signed, &sign(&signing_key, &signed)).is_err());
+    client.voucher_withdraw(&signed, &sign(&rotated_key, &signed));
+    assert_eq!(token_client.balance(&recipient), 2000);
+}
+
+#[test]
+fn test_batch_off_ramp_withdraw() {
+    extern crate std;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_admin, &10000i128);
+    token_client.approve(&test_admin, &contract_id, &10000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+
+    let first_recipient = Address::generate(&env);
+    let second_recipient = Address::generate(&env);
+
+    // liquidity is checked for the batch total, not per payout
+    assert_eq!(
+        client.try_batch_off_ramp_withdraw(&token_id, &vec![&env, (first_recipient.clone(), 6000i128), (second_recipient.clone(), 6000i128)]),
+        Err(Ok(RampContractError::InsufficientFunds))
+    );
+    assert_eq!(
+        client.try_batch_off_ramp_withdraw(&token_id, &vec![&env, (first_recipient.clone(), 0i128)]),
+        Err(Ok(RampContractError::InvalidBatch))
+    );
+
+    client.batch_off_ramp_withdraw(&token_id, &vec![&env, (first_recipient.clone(), 1000i128), (second_recipient.clone(), 2500i128)]);
+    // one OFFRAMP event per recipient, after each transfer
+    let mut offramp_events = vec![&env];
+    for event in env.events().all().iter().filter(|(contract, _, _)| *contract == contract_id) {
+        offramp_events.push_back(event);
+    }
+    assert_eq!(offramp_events.len(), 2);
+    assert_eq!(
+        offramp_events.slice(1..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "OFFRAMP"), token_id.clone(), second_recipient.clone()).into_val(&env),
+                OffRampWithdrawEvent { decimals: 18, amount: 2500 }.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(token_client.balance(&first_recipient), 1000);
+    assert_eq!(token_client.balance(&second_recipient), 2500);
+    assert_eq!(token_client.balance(&contract_id), 6500);
+}
+
+#[test]
+fn test_set_asset_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_admin, &10000i128);
+    token_client.mint(&test_user, &10000i128);
+    token_client.approve(&test_admin, &contract_id, &10000i128, &(env.ledger().sequence() + 100));
+    token_client.approve(&test_user, &contract_id, &10000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
+    assert_eq!(
+        client.try_set_asset_paused(&Address::generate(&env), &true, &false),
+        Err(Ok(RampContractError::AssetNotFound))
+    );
+
+    client.set_asset_paused(&token_id, &true, &false);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "ASSET_PAUSE_CHANGED"), token_id.clone()).into_val(&env),
+                AssetPauseChangedEvent { deposits_paused: true, withdrawals_paused: false }.into_val(&env),
+            ),
+        ]
+    );
+    assert!(client.is_asset_deposit_paused(&token_id));
+    assert!(!client.is_asset_withdrawal_paused(&token_id));
+
+    assert_eq!(
+        client.try_onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32]))),
+        Err(Ok(RampContractError::AssetDepositsPaused))
+    );
+    assert_eq!(
+        client.try_escrow_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])), &(env.ledger().timestamp() + 100)),
+        Err(Ok(RampContractError::AssetDepositsPaused))
+    );
+    // withdrawals keep working while only deposits are paused
+    client.off_ramp_withdraw(&token_id, &test_user, &100i128);
+
+    client.set_asset_paused(&token_id, &false, &true);
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+    assert_eq!(
+        client.try_off_ramp_withdraw(&token_id, &test_user, &100i128),
+        Err(Ok(RampContractError::AssetWithdrawalsPaused))
+    );
+    assert_eq!(
+        client.try_batch_off_ramp_withdraw(&token_id, &vec![&env, (test_user.clone(), 100i128)]),
+        Err(Ok(RampContractError::AssetWithdrawalsPaused))
+    );
+    assert_eq!(
+        client.try_refund_deposit(&BytesN::from_array(&env, &[1u8; 32]), &false),
+        Err(Ok(RampContractError::AssetWithdrawalsPaused))
+    );
+}
+
+#[test]
+fn test_fee_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_admin, &10000i128);
+    token_client.mint(&test_user, &100000i128);
+    token_client.approve(&test_admin, &contract_id, &10000i128, &(env.ledger().sequence() + 100));
+    token_client.approve(&test_user, &contract_id, &100000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &10i128);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
+    // flat 10% without a schedule
+    assert_eq!(client.quote_fee(&token_id, &1000i128, &None), 100);
+
+    let schedule = FeeSchedule {
+        tiers: vec![
+            &env,
+            FeeTier { threshold: 0, fee_bps: 200 },
+            FeeTier { threshold: 10000, fee_bps: 100 },
+        ],
+        min_fee: Some(5),
+        max_fee: Some(150),
+    };
+    let unsorted = FeeSchedule {
+        tiers: vec![
+            &env,
+            FeeTier { threshold: 10000, fee_bps: 100 },
+            FeeTier { threshold: 0, fee_bps: 200 },
+        ],
+        min_fee: None,
+        max_fee: None,
+    };
+    assert_eq!(
+        client.try_set_fee_schedule(&token_id, &Some(unsorted)),
+        Err(Ok(RampContractError::InvalidFeeSchedule))
+    );
+    assert_eq!(
+        client.try_set_fee_schedule(&Address::generate(&env), &Some(schedule.clone())),
+        Err(Ok(RampContractError::AssetNotFound))
+    );
+
+    client.set_fee_schedule(&token_id, &Some(schedule.clone()));
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "FEE_SCHEDULE_CHANGED"), token_id.clone()).into_val(&env),
+                Some(schedule.clone()).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_fee_schedule(&token_id), Some(schedule));
+
+    // 2% tier, raised to the minimum fee
+    assert_eq!(client.quote_fee(&token_id, &1000i128, &None), 20);
+    assert_eq!(client.quote_fee(&token_id, &100i128, &None), 5);
+    // 1% tier, capped at the maximum fee
+    assert_eq!(client.quote_fee(&token_id, &50000i128, &None), 150);
+
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+    assert_eq!(client.get_asset_revenue(&token_id), 20);
+    assert_eq!(client.get_deposit_receipt(&BytesN::from_array(&env, &[1u8; 32])).unwrap().fee, 20);
+
+    // region overrides win over the schedule tiers, the caps still apply
+    client.set_region_fee_percentage(&symbol_short!("KEN"), &Some(50i128));
+    assert_eq!(client.quote_fee(&token_id, &1000i128, &Some(symbol_short!("KEN"))), 150);
+
+    client.set_fee_schedule(&token_id, &None);
+    assert_eq!(client.get_fee_schedule(&token_id), None);
+    assert_eq!(client.quote_fee(&token_id, &1000i128, &None), 100);
+}
+
+#[test]
+fn test_access_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&test_admin, &10000i128);
+    token_client.mint(&test_user, &10000i128);
+    token_client.approve(&test_admin, &contract_id, &10000i128, &(env.ledger().sequence() + 100));
+    token_client.approve(&test_user, &contract_id, &10000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
+    let deposit = |reference: u8| client.try_onramp_deposit(
+        &token_id,
+        &1000i128,
+        &test_user,
+        &deposit_params(&env, BytesN::from_array(&env, &[reference; 32]))
+    );
+
+    client.update_access_list(&vec![&env, test_user.clone()], &Some(AccessStatus::Denied));
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "ACCESS_LIST_UPDATED"), Some(AccessStatus::Denied)).into_val(&env),
+                vec![&env, test_user.clone()].into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_access_status(&test_user), Some(AccessStatus::Denied));
+    assert_eq!(deposit(1), Err(Ok(RampContractError::AddressDenied)));
+    // payouts check the recipient
+    assert_eq!(
+        client.try_off_ramp_withdraw(&token_id, &test_user, &100i128),
+        Err(Ok(RampContractError::AddressDenied))
+    );
+    assert_eq!(
+        client.try_batch_off_ramp_withdraw(&token_id, &vec![&env, (test_user.clone(), 100i128)]),
+        Err(Ok(RampContractError::AddressDenied))
+    );
+
+    client.update_access_list(&vec![&env, test_user.clone()], &None);
+    assert_eq!(client.get_access_status(&test_user), None);
+    assert!(deposit(1).is_ok());
+
+    // allowlist mode rejects addresses without an entry
+    client.set_allowlist_enabled(&true);
+    assert!(client.is_allowlist_enabled());
+    assert_eq!(deposit(2), Err(Ok(RampContractError::AddressNotAllowed)));
+    client.update_access_list(&vec![&env, test_user.clone(), Address::generate(&env)], &Some(AccessStatus::Allowed));
+    assert!(deposit(2).is_ok());
+    client.off_ramp_withdraw(&token_id, &test_user, &100i128);
+
+    assert_eq!(
+        client.try_update_access_list(&Vec::new(&env), &None),
+        Err(Ok(RampContractError::InvalidBatch))
+    );
+}
+
+#[test]
+fn test_withdraw_all_revenue() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address.clone(), 10u32));
+    let first_token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let second_token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let idle_token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+    client.add_asset(&idle_token_id, &test_admin, &1i128);
+    for (index, token_id) in [first_token_id.clone(), second_token_id.clone()].iter().enumerate() {
+        let token_client = ramp_token::RampTokenClient::new(&env, token_id);
+        token_client.mint(&test_user, &10000i128);
+        token_client.approve(&test_user, &contract_id, &1000i128, &(env.ledger().sequence() + 100));
+        client.add_asset(token_id, &test_admin, &((index as i128 + 1) * 2));
+        client.onramp_deposit(token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[index as u8; 32])));
+    }
+    assert_eq!(client.get_assets(), vec![&env, idle_token_id.clone(), first_token_id.clone(), second_token_id.clone()]);
+
+    client.withdraw_all_revenue();
+
+    // the asset without revenue is skipped
+    let revenue_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| *contract == contract_id)
+        .count();
+    assert_eq!(revenue_events, 2);
+
+    assert_eq!(ramp_token::RampTokenClient::new(&env, &first_token_id).balance(&vault_address), 20);
+    assert_eq!(ramp_token::RampTokenClient::new(&env, &second_token_id).balance(&vault_address), 40);
+    assert_eq!(client.get_asset_revenue(&first_token_id), 0);
+    assert_eq!(client.get_asset_revenue(&second_token_id), 0);
+}
+
+#[test]
+fn test_revenue_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let beneficiaries = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address.clone(), 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+    token_client.mint(&test_user, &10000i128);
+    token_client.approve(&test_user, &contract_id, &2000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+    assert_eq!(client.get_asset_revenue(&token_id), 10);
+
+    // invalid splits are rejected when proposed
+    assert_eq!(
+        client.try_propose(&TimelockAction::SetRevenueSplit(vec![&env, (beneficiaries[0].clone(), 5000u32), (beneficiaries[1].clone(), 4000u32)])),
+        Err(Ok(RampContractError::InvalidRevenueSplit))
+    );
+    assert_eq!(
+        client.try_propose(&TimelockAction::SetRevenueSplit(vec![&env, (beneficiaries[0].clone(), 5000u32), (beneficiaries[0].clone(), 5000u32)])),
+        Err(Ok(RampContractError::InvalidRevenueSplit))
+    );
+    let split = vec![
+        &env,
+        (beneficiaries[0].clone(), 3333u32),
+        (beneficiaries[1].clone(), 3333u32),
+        (beneficiaries[2].clone(), 3334u32),
+    ];
+
+    // the split redirects revenue, so it waits for the timelock
+    let eta = client.propose(&TimelockAction::SetRevenueSplit(split.clone()));
+    assert_eq!(client.try_set_revenue_split(&split), Err(Ok(RampContractError::TimelockNotElapsed)));
+    env.ledger().set_timestamp(eta);
+    client.set_revenue_split(&split);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "REVENUE_SPLIT_CHANGED"),).into_val(&env),
+                split.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_revenue_split(), split);
+
+    // the rounding dust goes to the first beneficiary
+    client.withdraw_asset_revenue(&token_id);
+    assert_eq!(token_client.balance(&beneficiaries[0]), 4);
+    assert_eq!(token_client.balance(&beneficiaries[1]), 3);
+    assert_eq!(token_client.balance(&beneficiaries[2]), 3);
+    assert_eq!(token_client.balance(&vault_address), 0);
+    assert_eq!(client.get_asset_revenue(&token_id), 0);
+
+    // clearing the split sends revenue to the vault again
+    elapse_proposal(&env, &client, TimelockAction::SetRevenueSplit(vec![&env]));
+    client.set_revenue_split(&vec![&env]);
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[2u8; 32])));
+    client.withdraw_all_revenue();
+    assert_eq!(token_client.balance(&vault_address), 10);
+}
+
+#[test]
+fn test_available_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    assert_eq!(client.try_get_available_liquidity(&token_id), Err(Ok(RampContractError::AssetNotFound)));
+
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+    token_client.mint(&test_user, &10000i128);
+    token_client.approve(&test_user, &contract_id, &1000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+
+    // the 1% fee is revenue, not liquidity
+    assert_eq!(client.get_available_liquidity(&token_id), 990);
+
+    assert_eq!(client.try_set_low_water_mark(&token_id, &Some(-1i128)), Err(Ok(RampContractError::InvalidLowWaterMark)));
+    client.set_low_water_mark(&token_id, &Some(500i128));
+    assert_eq!(client.get_low_water_mark(&token_id), Some(500));
+
+    // staying above the mark emits no alert
+    client.off_ramp_withdraw(&token_id, &recipient, &400i128);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (Symbol::new(&env, "OFFRAMP"), token_id.clone(), recipient.clone()).into_val(&env)
+    );
+
+    client.off_ramp_withdraw(&token_id, &recipient, &100i128);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "LIQUIDITY_LOW"), token_id.clone()).into_val(&env),
+                LiquidityLowEvent {
+                    decimals: 18,
+                    available: 490,
+                    low_water_mark: 500,
+                }.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_available_liquidity(&token_id), 490);
+
+    // only the crossing alerts, withdrawals already below the mark do not
+    client.off_ramp_withdraw(&token_id, &recipient, &10i128);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (Symbol::new(&env, "OFFRAMP"), token_id.clone(), recipient.clone()).into_val(&env)
+    );
+}
+
+#[test]
+fn test_fund_defund_asset() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    token_client.mint(&treasury, &5000i128);
+    assert_eq!(client.try_fund_asset(&token_id, &treasury, &1000i128), Err(Ok(RampContractError::AssetNotFound)));
+
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+    client.add_asset(&token_id, &test_admin, &1i128);
+    assert_eq!(client.try_fund_asset(&token_id, &treasury, &0i128), Err(Ok(RampContractError::InvalidAmount)));
+    client.fund_asset(&token_id, &treasury, &3000i128);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "LIQUIDITY_FUNDED"), token_id.clone()).into_val(&env),
+                LiquidityFundedEvent {
+                    funder: treasury.clone(),
+                    decimals: 18,
+                    amount: 3000,
+                }.into_val(&env),
+            ),
+        ]
+    );
+
+    token_client.mint(&test_user, &1000i128);
+    token_client.approve(&test_user, &contract_id, &1000i128, &(env.ledger().sequence() + 100));
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+    assert_eq!(client.get_available_liquidity(&token_id), 3990);
+
+    // revenue cannot be drained
+    assert_eq!(client.try_defund_asset(&token_id, &treasury, &4000i128), Err(Ok(RampContractError::InsufficientFunds)));
+    client.defund_asset(&token_id, &treasury, &3990i128);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "LIQUIDITY_DEFUNDED"), token_id.clone()).into_val(&env),
+                LiquidityDefundedEvent {
+                    recipient: treasury.clone(),
+                    decimals: 18,
+                    amount: 3990,
+                }.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(token_client.balance(&treasury), 5990);
+    assert_eq!(token_client.balance(&contract_id), 10);
+    assert_eq!(client.get_asset_revenue(&token_id), 10);
+    assert_eq!(client.get_assets(), vec![&env, token_id.clone()]);
+}
+
+#[test]
+fn test_asset_wind_down() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address.clone(), 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+    token_client.mint(&test_admin, &1000i128);
+    token_client.approve(&test_admin, &contract_id, &1000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+    token_client.mint(&test_user, &3000i128);
+    token_client.approve(&test_user, &contract_id, &3000i128, &(env.ledger().sequence() + 100));
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+    // the delisting is proposed up front, the escrow expires only after it is executable
+    let eta = client.propose(&TimelockAction::RemoveAsset(token_id.clone(), test_admin.clone()));
+    client.escrow_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[2u8; 32])), &(eta + 100));
+
+    client.set_asset_winding_down(&token_id, &true);
+    assert!(client.is_asset_winding_down(&token_id));
+
+    // new deposits are rejected, pending payouts still complete
+    assert_eq!(
+        client.try_onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[3u8; 32]))),
+        Err(Ok(RampContractError::AssetWindingDown))
+    );
+    assert_eq!(
+        client.try_escrow_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[4u8; 32])), &1100u64),
+        Err(Ok(RampContractError::AssetWindingDown))
+    );
+    client.off_ramp_withdraw(&token_id, &recipient, &500i128);
+
+    // the open escrow has to settle before delisting
+    env.ledger().set_timestamp(eta);
+    assert_eq!(client.try_remove_asset(&token_id, &test_admin), Err(Ok(RampContractError::PendingEscrows)));
+    client.release_escrow(&BytesN::from_array(&env, &[2u8; 32]));
+
+    // both deposits paid a 10 fee, which is swept to the vault before the liquidity is returned
+    client.remove_asset(&token_id, &test_admin);
+    assert_eq!(token_client.balance(&vault_address), 20);
+    assert_eq!(token_client.balance(&test_admin), 2480);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(client.get_asset_revenue(&token_id), 0);
+    assert!(!client.is_asset_winding_down(&token_id));
+}
+
+#[test]
+fn test_max_assets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 1u32));
+    let first_token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let second_token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+
+    client.add_asset(&first_token_id, &test_admin, &1i128);
+    assert_eq!(
+        client.try_add_asset(&second_token_id, &test_admin, &1i128),
+        Err(Ok(RampContractError::MaxAssetsReached))
+    );
+
+    // removing frees the slot
+    elapse_proposal(&env, &client, TimelockAction::RemoveAsset(first_token_id.clone(), test_admin.clone()));
+    client.remove_asset(&first_token_id, &test_admin);
+    assert_eq!(client.get_assets().len(), 0);
+    client.add_asset(&second_token_id, &test_admin, &1i128);
+}
+
+// Natively registered contracts run under the hash of empty Wasm, which testutils allows uploading.
+// Upgrading to it goes through the host while calls keep dispatching to this crate
+fn upload_upgrade_wasm(env: &Env) -> BytesN<32> {
+    env.deployer().upload_contract_wasm(Bytes::new(env))
+}
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let test_user = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address.clone(), 10u32));
+    let token_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    let client = RampContractClient::new(&env, &contract_id);
+    let token_client = ramp_token::RampTokenClient::new(&env, &token_id);
+    let delisted_id = env.register(ramp_token::RampToken, (test_admin.clone(),));
+    assert_eq!(client.version(), 1);
+
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+    token_client.mint(&test_user, &10000i128);
+    token_client.approve(&test_user, &contract_id, &2000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[1u8; 32])));
+
+    // roll the storage back to the version 0 layout: no version, no asset list, the old AssetInfo
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(
+            &RampContractState::AssetsInfo(token_id.clone()),
+            &AssetInfoV0 { is_added: true, asset_fee_percentage: 1, asset_revenue: 10 },
+        );
+        // an asset delisted before the upgrade keeps its old entry and is not passed to migrate
+        env.storage().instance().set(
+            &RampContractState::AssetsInfo(delisted_id.clone()),
+            &AssetInfoV0 { is_added: false, asset_fee_percentage: 5, asset_revenue: 0 },
+        );
+        env.storage().instance().remove(&RampContractState::AssetList);
+        env.storage().instance().remove(&RampContractState::StorageVersion);
+    });
+    assert_eq!(client.version(), 0);
+
+    let wasm_hash = upload_upgrade_wasm(&env);
+    let legacy_assets = vec![&env, token_id.clone()];
+
+    // both steps need the contract paused, and migrate needs an upgrade first
+    assert!(client.try_upgrade(&wasm_hash, &test_admin).is_err());
+    client.pause(&test_admin);
+    assert!(client.try_migrate(&legacy_assets, &test_admin).is_err());
+
+    // the upgrade has to be proposed first
+    assert_eq!(
+        client.try_upgrade(&wasm_hash, &test_admin),
+        Err(Ok(RampContractError::ProposalNotFound.into()))
+    );
+    let eta = client.propose(&TimelockAction::Upgrade(wasm_hash.clone()));
+    env.ledger().set_timestamp(eta);
+    client.upgrade(&wasm_hash, &test_admin);
+    client.migrate(&legacy_assets, &test_admin);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "CONTRACT_MIGRATED"), 0u32).into_val(&env),
+                1u32.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.version(), 1);
+
+    // once per version, even after another upgrade
+    assert!(client.try_migrate(&legacy_assets, &test_admin).is_err());
+    let eta = client.propose(&TimelockAction::Upgrade(wasm_hash.clone()));
+    env.ledger().set_timestamp(eta);
+    client.upgrade(&wasm_hash, &test_admin);
+    assert_eq!(
+        client.try_migrate(&legacy_assets, &test_admin),
+        Err(Ok(RampContractError::AlreadyMigrated.into()))
+    );
+
+    // the migrated state is intact and usable
+    assert_eq!(client.get_owner(), Some(test_admin.clone()));
+    assert_eq!(client.get_vault_address(), vault_address);
+    assert_eq!(client.get_assets(), vec![&env, token_id.clone()]);
+    assert_eq!(client.get_asset_fee_percentage(&token_id), 1);
+    assert_eq!(client.get_asset_revenue(&token_id), 10);
+    assert!(!client.is_asset_deposit_paused(&token_id));
+    assert_eq!(client.get_deposit_receipt(&BytesN::from_array(&env, &[1u8; 32])).unwrap().amount, 1000);
+
+    client.unpause(&test_admin);
+    client.withdraw_asset_revenue(&token_id);
+    assert_eq!(token_client.balance(&vault_address), 10);
+    client.onramp_deposit(&token_id, &1000i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[2u8; 32])));
+    assert_eq!(client.get_asset_revenue(&token_id), 10);
+
+    // entries left out of legacy_assets are still decoded and rewritten on first use
+    assert_eq!(client.get_asset_fee_percentage(&delisted_id), 5);
+    let delisted_client = ramp_token::RampTokenClient::new(&env, &delisted_id);
+    delisted_client.mint(&test_admin, &1000i128);
+    delisted_client.approve(&test_admin, &contract_id, &1000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&delisted_id, &test_admin, &2i128);
+    assert_eq!(client.get_asset_fee_percentage(&delisted_id), 2);
+    assert_eq!(client.get_assets(), vec![&env, token_id.clone(), delisted_id.clone()]);
+}
+
+
+#[test]
+fn test_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address.clone(), 10u32));
+    let client = RampContractClient::new(&env, &contract_id);
+    env.ledger().set_timestamp(1000);
+
+    // the guardian is set through the timelock as well
+    let delay = client.get_timelock_delay();
+    assert_eq!(client.get_guardian(), None);
+    let action = TimelockAction::SetGuardian(Some(guardian.clone()));
+    assert_eq!(client.propose(&action), 1000 + delay);
+    let proposal = TimelockProposal { action: action.clone(), eta: 1000 + delay };
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "TIMELOCK_PROPOSED"), TimelockKind::SetGuardian).into_val(&env),
+                proposal.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_proposal(&TimelockKind::SetGuardian), Some(proposal.clone()));
+    assert_eq!(client.try_propose(&action), Err(Ok(RampContractError::ProposalAlreadyPending)));
+    env.ledger().set_timestamp(999 + delay);
+    assert_eq!(client.try_set_guardian(&Some(guardian.clone())), Err(Ok(RampContractError::TimelockNotElapsed)));
+    env.ledger().set_timestamp(1000 + delay);
+    assert_eq!(client.try_set_guardian(&None), Err(Ok(RampContractError::ProposalMismatch)));
+    client.set_guardian(&Some(guardian.clone()));
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "TIMELOCK_EXECUTED"), TimelockKind::SetGuardian).into_val(&env),
+                proposal.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_guardian(), Some(guardian.clone()));
+    assert_eq!(client.get_proposal(&TimelockKind::SetGuardian), None);
+    assert_eq!(client.try_set_guardian(&Some(guardian.clone())), Err(Ok(RampContractError::ProposalNotFound)));
+
+    // the guardian can cancel during the window, anyone else can't
+    let new_vault_address = Address::generate(&env);
+    let eta = client.propose(&TimelockAction::ChangeVaultAddress(new_vault_address.clone()));
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_proposal(&stranger, &TimelockKind::ChangeVaultAddress),
+        Err(Ok(RampContractError::Unauthorized))
+    );
+    client.cancel_proposal(&guardian, &TimelockKind::ChangeVaultAddress);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "TIMELOCK_CANCELLED"), TimelockKind::ChangeVaultAddress).into_val(&env),
+                TimelockProposal {
+                    action: TimelockAction::ChangeVaultAddress(new_vault_address.clone()),
+                    eta,
+                }.into_val(&env),
+            ),
+        ]
+    );
+    env.ledger().set_timestamp(eta);
+    assert_eq!(
+        client.try_change_vault_address(&new_vault_address),
+        Err(Ok(RampContractError::ProposalNotFound))
+    );
+    assert_eq!(client.get_vault_address(), vault_address);
+    assert_eq!(
+        client.try_cancel_proposal(&guardian, &TimelockKind::ChangeVaultAddress),
+        Err(Ok(RampContractError::ProposalNotFound))
+    );
+
+    // the owner can cancel too, and a shorter delay only applies to later proposals
+    assert_eq!(
+        client.try_propose(&TimelockAction::SetTimelockDelay(2_592_001)),
+        Err(Ok(RampContractError::InvalidTimelockDelay))
+    );
+    let eta = client.propose(&TimelockAction::SetTimelockDelay(3600));
+    client.propose(&TimelockAction::ChangeVaultAddress(new_vault_address.clone()));
+    client.cancel_proposal(&test_admin, &TimelockKind::ChangeVaultAddress);
+    env.ledger().set_timestamp(eta);
+    client.set_timelock_delay(&3600);
+    assert_eq!(client.get_timelock_delay(), 3600);
+    assert_eq!(client.propose(&TimelockAction::ChangeVaultAddress(new_vault_address.clone())), eta + 3600);
+    env.ledger().set_timestamp(eta + 3600);
+    client.change_vault_address(&new_vault_address);
+    assert_eq!(client.get_vault_address(), new_vault_address);
+}
+
+
+#[test]
+fn test_native_deposit_and_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address.clone(), 10u32));
+    let client = RampContractClient::new(&env, &contract_id);
+    let native_id = register_native_asset(&env);
+    let native_client = token::Client::new(&env, &native_id);
+    assert_eq!(client.get_native_asset(), native_id);
+
+    let test_user = create_native_account(&env, 100_000_000);
+    let recipient = create_native_account(&env, 0);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+
+    assert_eq!(client.try_set_native_fee_percentage(&61i128), Err(Ok(RampContractError::InvalidFeePercentage)));
+    client.set_native_fee_percentage(&2i128);
+    assert_eq!(client.get_native_fee_percentage(), 2);
+
+    // deposits charge the native fee and emit the same ONRAMP event as token deposits
+    let amount = 50_000_000i128;
+    let fee = amount * 2 / 100;
+    let reference = BytesN::from_array(&env, &[1u8; 32]);
+    let data = Bytes::from_slice(&env, b"test");
+    // the deposit is pulled from an allowance
+    assert!(client.try_native_deposit(&amount, &test_user, &deposit_params(&env, reference.clone())).is_err());
+    native_client.approve(&test_user, &contract_id, &amount, &(env.ledger().sequence() + 100));
+    client.native_deposit(&amount, &test_user, &deposit_params(&env, reference.clone()));
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "ONRAMP"), native_id.clone(), test_user.clone()).into_val(&env),
+                OnRampDepositEvent {
+                    decimals: 7,
+                    gross_amount: amount,
+                    fee,
+                    net_amount: amount - fee,
+                    medium: symbol_short!("MPESA"),
+                    region: symbol_short!("KEN"),
+                    data: data.clone(),
+                    reference: reference.clone(),
+                    sequence: 1,
+                }.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(native_client.balance(&contract_id), amount);
+    assert_eq!(native_client.balance(&test_user), 100_000_000 - amount);
+    assert_eq!(client.get_native_revenue(), fee);
+    assert_eq!(client.get_native_liquidity(), amount - fee);
+    assert_eq!(
+        client.try_native_deposit(&amount, &test_user, &deposit_params(&env, reference.clone())),
+        Err(Ok(RampContractError::DuplicateReference))
+    );
+    assert_eq!(
+        client.try_native_deposit(&0i128, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[2u8; 32]))),
+        Err(Ok(RampContractError::InvalidAmount))
+    );
+
+    // XLM is not listed as a token asset, it has its own entry points
+    assert_eq!(client.try_add_asset(&native_id, &test_user, &2i128), Err(Ok(RampContractError::NativeAssetNotAllowed)));
+    assert_eq!(client.try_fund_asset(&native_id, &test_user, &amount), Err(Ok(RampContractError::NativeAssetNotAllowed)));
+
+    // the per-asset pause switches accept the native asset
+    client.set_asset_paused(&native_id, &true, &true);
+    assert!(client.is_asset_deposit_paused(&native_id));
+    assert!(client.is_asset_withdrawal_paused(&native_id));
+    assert_eq!(
+        client.try_native_deposit(&amount, &test_user, &deposit_params(&env, BytesN::from_array(&env, &[3u8; 32]))),
+        Err(Ok(RampContractError::AssetDepositsPaused))
+    );
+    assert_eq!(client.try_native_withdraw(&recipient, &(amount - fee)), Err(Ok(RampContractError::AssetWithdrawalsPaused)));
+    client.set_asset_paused(&native_id, &false, &false);
+
+    // payouts never touch the native revenue
+    assert_eq!(client.try_native_withdraw(&recipient, &(amount - fee + 1)), Err(Ok(RampContractError::InsufficientFunds)));
+    client.native_withdraw(&recipient, &(amount - fee));
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "OFFRAMP"), native_id.clone(), recipient.clone()).into_val(&env),
+                OffRampWithdrawEvent { decimals: 7, amount: amount - fee }.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(native_client.balance(&recipient), amount - fee);
+    assert_eq!(client.get_native_liquidity(), 0);
+
+    client.withdraw_native_revenue();
+    assert_eq!(native_client.balance(&vault_address), fee);
+    assert_eq!(client.get_native_revenue(), 0);
+    assert_eq!(native_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_native_refund_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address, 10u32));
+    let client = RampContractClient::new(&env, &contract_id);
+    let native_client = token::Client::new(&env, &register_native_asset(&env));
+    let test_user = create_native_account(&env, 10_000);
+    client.add_region(&symbol_short!("KEN"), &vec![&env, symbol_short!("MPESA")]);
+    client.set_native_fee_percentage(&1i128);
+
+    let first_reference = BytesN::from_array(&env, &[1u8; 32]);
+    let second_reference = BytesN::from_array(&env, &[2u8; 32]);
+    native_client.approve(&test_user, &contract_id, &2000i128, &(env.ledger().sequence() + 100));
+    client.native_deposit(&1000i128, &test_user, &deposit_params(&env, first_reference.clone()));
+    client.native_deposit(&1000i128, &test_user, &deposit_params(&env, second_reference.clone()));
+    assert_eq!(client.get_native_revenue(), 20);
+
+    // refunds settle against the native revenue instead of an asset entry
+    client.refund_deposit(&first_reference, &true);
+    assert_eq!(native_client.balance(&test_user), 9000);
+    assert_eq!(client.get_native_revenue(), 10);
+    client.refund_deposit(&second_reference, &false);
+    assert_eq!(native_client.balance(&test_user), 9990);
+    assert_eq!(client.get_native_revenue(), 10);
+    assert_eq!(native_client.balance(&contract_id), 10);
+}
+
+#[test]
+fn test_undeliverable_payout_credit_and_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let test_admin = Address::generate(&env);
+    let vault_address = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let contract_id = env.register(RampContract, (test_admin.clone(), vault_address.clone(), 10u32));
+    let client = RampContractClient::new(&env, &contract_id);
+
+    // a Stellar asset whose issuer can deauthorize balances, so transfers to the recipient fail
+    let sac = env.register_stellar_asset_contract_v2(test_admin.clone());
+    sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token_id = sac.address();
+    let token_client = token::Client::new(&env, &token_id);
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+    token_admin_client.mint(&test_admin, &10000i128);
+    token_client.approve(&test_admin, &contract_id, &1000i128, &(env.ledger().sequence() + 100));
+    client.add_asset(&token_id, &test_admin, &1i128);
+    token_admin_client.set_authorized(&recipient, &false);
+
+    assert_eq!(client.try_claim(&recipient, &token_id), Err(Ok(RampContractError::NoCredit)));
+
+    // the failed transfer is credited instead of reverting the payout
+    client.off_ramp_withdraw(&token_id, &recipient, &300i128);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "CREDIT_CREATED"), token_id.clone(), recipient.clone()).into_val(&env),
+                CreditEvent { decimals: 7, amount: 300, credit: 300 }.into_val(&env),
+            ),
+        ]
+    );
+    client.batch_off_ramp_withdraw(&token_id, &vec![&env, (recipient.clone(), 200i128), (vault_address.clone(), 100i128)]);
+    assert_eq!(token_client.balance(&vault_address), 100);
+    assert_eq!(client.get_credit(&recipient, &token_id), 500);
+    assert_eq!(client.get_credited_total(&token_id), 500);
+    assert_eq!(token_client.balance(&contract_id), 900);
+
+    // credits are reserved, neither liquidity nor removable with the asset
+    assert_eq!(client.get_available_liquidity(&token_id), 400);
+    assert_eq!(client.try_off_ramp_withdraw(&token_id, &vault_address, &401i128), Err(Ok(RampContractError::InsufficientFunds)));
+    elapse_proposal(&env, &client, TimelockAction::RemoveAsset(token_id.clone(), test_admin.clone()));
+    assert_eq!(client.try_remove_asset(&token_id, &test_admin), Err(Ok(RampContractError::PendingCredits)));
+
+    // claiming needs the recipient to be able to receive, and is stopped by both pauses
+    assert!(client.try_claim(&recipient, &token_id).is_err());
+    assert_eq!(client.get_credit(&recipient, &token_id), 500);
+    token_admin_client.set_authorized(&recipient, &true);
+    client.pause(&test_admin);
+    assert!(client.try_claim(&recipient, &token_id).is_err());
+    client.unpause(&test_admin);
+    client.set_asset_paused(&token_id, &false, &true);
+    assert_eq!(client.try_claim(&recipient, &token_id), Err(Ok(RampContractError::AssetWithdrawalsPaused)));
+    client.set_asset_paused(&token_id, &false, &false);
+    client.claim(&recipient, &token_id);
+    assert_eq!(
+        last_event(&env),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "CREDIT_CLAIMED"), token_id.clone(), recipient.clone()).into_val(&env),
+                CreditEvent { decimals: 7, amount: 500, credit: 0 }.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(token_client.balance(&recipient), 500);
+    assert_eq!(client.get_credit(&recipient, &token_id), 0);
+    assert_eq!(client.get_credited_total(&token_id), 0);
+    assert_eq!(client.get_available_liquidity(&token_id), 400);
+    assert_eq!(client.try_claim(&recipient, &token_id), Err(Ok(RampContractError::NoCredit)));
+}